@@ -396,6 +396,14 @@ impl<F: Font + Sync, H: BuildHasher> GlyphBrush<F, H> {
 }
 
 impl<F: Font, H: BuildHasher> GlyphBrush<F, H> {
+    /// Deletes the OpenGL objects of this brush, which mustn't be used after.
+    pub fn destroy(&mut self, gl: &elysium_gl::Context) {
+        match self {
+            GlyphBrush::Core { pipeline, .. } => pipeline.destroy(gl),
+            GlyphBrush::Compatibility { pipeline, .. } => pipeline.destroy(gl),
+        }
+    }
+
     fn new(
         gl: &elysium_gl::Context,
         raw_builder: glyph_brush::GlyphBrushBuilder<F, H>,
@@ -74,6 +74,11 @@ impl Cache {
         Cache { texture, format }
     }
 
+    /// Deletes the texture.
+    pub unsafe fn destroy(&self, gl: &elysium_gl::Context) {
+        gl.delete_texture(self.texture);
+    }
+
     pub unsafe fn update(
         &self,
         gl: &elysium_gl::Context,
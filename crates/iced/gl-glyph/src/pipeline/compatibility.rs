@@ -204,6 +204,18 @@ impl Pipeline {
         self.current_vertices = vertex_count;
     }
 
+    /// Deletes the program, buffers and texture.
+    pub fn destroy(&mut self, gl: &elysium_gl::Context) {
+        gl.delete_program(self.program);
+        gl.delete_vertex_array(self.vertex_array);
+        gl.delete_buffer(self.vertices);
+        gl.delete_buffer(self.indices);
+
+        unsafe {
+            self.cache.destroy(gl);
+        }
+    }
+
     pub fn get_max_texture_size(&self) -> u32 {
         self.max_texture_size
     }
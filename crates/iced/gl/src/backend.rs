@@ -41,6 +41,13 @@ impl Backend {
         }
     }
 
+    /// Deletes every OpenGL object of the backend, it mustn't be used after.
+    pub fn destroy(&mut self, gl: &elysium_gl::Context) {
+        self.quad_pipeline.destroy(gl);
+        self.text_pipeline.destroy(gl);
+        self.triangle_pipeline.destroy(gl);
+    }
+
     /// Draws the provided primitives in the default framebuffer.
     ///
     /// The text provided as overlay will be rendered on top of the primitives.
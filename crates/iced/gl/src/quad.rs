@@ -17,6 +17,14 @@ impl Pipeline {
         Pipeline::Core(core::Pipeline::new(gl, shader_version))
     }
 
+    /// Deletes the program and buffers.
+    pub fn destroy(&mut self, gl: &elysium_gl::Context) {
+        match self {
+            Pipeline::Core(pipeline) => pipeline.destroy(gl),
+            Pipeline::Compatibility(pipeline) => pipeline.destroy(gl),
+        }
+    }
+
     pub fn draw(
         &mut self,
         gl: &elysium_gl::Context,
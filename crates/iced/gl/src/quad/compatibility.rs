@@ -93,6 +93,14 @@ impl Pipeline {
         }
     }
 
+    /// Deletes the program and buffers.
+    pub fn destroy(&mut self, gl: &elysium_gl::Context) {
+        gl.delete_program(self.program);
+        gl.delete_vertex_array(self.vertex_array);
+        gl.delete_buffer(self.vertex_buffer);
+        gl.delete_buffer(self.index_buffer);
+    }
+
     pub fn draw(
         &mut self,
         gl: &elysium_gl::Context,
@@ -81,6 +81,13 @@ impl Pipeline {
         }
     }
 
+    /// Deletes the program and buffers.
+    pub fn destroy(&mut self, gl: &elysium_gl::Context) {
+        gl.delete_program(self.program);
+        gl.delete_vertex_array(self.vertex_array);
+        gl.delete_buffer(self.instances);
+    }
+
     pub fn draw(
         &mut self,
         gl: &elysium_gl::Context,
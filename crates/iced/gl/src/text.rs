@@ -61,6 +61,11 @@ impl Pipeline {
         }
     }
 
+    /// Deletes the glyph cache.
+    pub fn destroy(&mut self, gl: &elysium_gl::Context) {
+        self.draw_brush.borrow_mut().destroy(gl);
+    }
+
     pub fn queue(&mut self, section: elysium_gl_glyph::Section<'_>) {
         self.draw_brush.borrow_mut().queue(section);
     }
@@ -1,15 +1,39 @@
+#![feature(once_cell)]
+
 //! Convienience wrappers around `libloading::os::unix` structures.
 
 use libloading::os::unix;
-use std::ffi::OsStr;
-use std::fmt;
+use std::ffi::{CStr, OsStr};
+use std::mem::MaybeUninit;
 use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::{fmt, io, mem, ptr};
+use symbols::LinkMap;
+
+pub use notify::{loaded, on_load, uninstall};
+pub use symbols::{Location, SymbolInfo, SymbolTable, Symbolizer};
+
+mod notify;
+mod symbols;
 
 const FLAGS: libc::c_int = libc::RTLD_NOLOAD /* dont load the library if it isnt already resident */
     | unix::RTLD_LAZY;
 
 const FLAGS_GLOBAL: libc::c_int = libc::RTLD_NOW | unix::RTLD_GLOBAL;
 
+const FLAGS_LOCAL: libc::c_int = libc::RTLD_NOW | unix::RTLD_LOCAL;
+
+/// `dlinfo` should return the `link_map` of the library.
+const RTLD_DI_LINKMAP: libc::c_int = 2;
+
+extern "C" {
+    fn dlinfo(
+        handle: *mut libc::c_void,
+        request: libc::c_int,
+        info: *mut libc::c_void,
+    ) -> libc::c_int;
+}
+
 /// Convenience wrapper for `libloading::os::unix::Library`.
 pub struct Library {
     library: unix::Library,
@@ -57,6 +81,22 @@ impl Library {
         Some(Self { library })
     }
 
+    /// Load the library, `library`, unlike `open` it needn't already be resident.
+    ///
+    /// It's symbols aren't made available to other libraries, and it's unloaded when dropped, if
+    /// nothing else refers to it.
+    #[inline]
+    pub fn load<L>(library: L) -> io::Result<Self>
+    where
+        L: AsRef<OsStr>,
+    {
+        // SAFETY: a null terminator is appended if it isnt present.
+        let library = unsafe { unix::Library::open(Some(library), FLAGS_LOCAL) }
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+
+        Ok(Self { library })
+    }
+
     /// Checks if the library, `library`, is resident.
     #[inline]
     pub fn exists<L>(library: L) -> bool
@@ -74,6 +114,109 @@ impl Library {
         Self { library }
     }
 
+    /// Open the library containing `address`, such as a function within it.
+    #[inline]
+    pub fn containing<T>(address: *const T) -> Option<Self> {
+        let mut info = MaybeUninit::<libc::Dl_info>::uninit();
+
+        // SAFETY: `dladdr` only looks `address` up, it's never read.
+        let info = unsafe {
+            if libc::dladdr(address.cast(), info.as_mut_ptr()) == 0 {
+                return None;
+            }
+
+            info.assume_init()
+        };
+
+        if info.dli_fname.is_null() {
+            return None;
+        }
+
+        // SAFETY: `dli_fname` is a nul terminated string owned by the dynamic linker.
+        let path = unsafe { CStr::from_ptr(info.dli_fname) };
+
+        Self::open(OsStr::from_bytes(path.to_bytes()))
+    }
+
+    /// Drop `extra` references to the library, then this one from a new thread, once the calling
+    /// thread has exited.
+    ///
+    /// This is how a library unloads itself. The new thread runs a trampoline mapped outside of the
+    /// library, which joins the calling thread, then calls `dlclose`, so no code of the library is
+    /// executing when it's unmapped. The trampoline's page is leaked.
+    ///
+    /// # Safety
+    ///
+    /// The calling thread must be joinable, and never joined, or detached, elsewhere. Threads
+    /// started by `std::thread` are detached once their `JoinHandle` is dropped, so it must be
+    /// started with `pthread_create` instead. Nothing else may call into the library once the
+    /// calling thread exits.
+    pub unsafe fn close_detached(self, extra: usize) -> io::Result<()> {
+        let pthread_join = libc::dlsym(libc::RTLD_DEFAULT, b"pthread_join\0".as_ptr().cast());
+        let dlclose = libc::dlsym(libc::RTLD_DEFAULT, b"dlclose\0".as_ptr().cast());
+
+        if pthread_join.is_null() || dlclose.is_null() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "unable to find `pthread_join`, or `dlclose`",
+            ));
+        }
+
+        let handle = self.library.into_raw();
+        let code = close_trampoline(libc::pthread_self(), pthread_join, handle, dlclose);
+        let trampoline = libc::mmap(
+            ptr::null_mut(),
+            code.len(),
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+
+        if trampoline == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        ptr::copy_nonoverlapping(code.as_ptr(), trampoline.cast(), code.len());
+
+        if libc::mprotect(trampoline, code.len(), libc::PROT_READ | libc::PROT_EXEC) != 0 {
+            let error = io::Error::last_os_error();
+
+            libc::munmap(trampoline, code.len());
+
+            return Err(error);
+        }
+
+        let start_routine: extern "C" fn(*mut libc::c_void) -> *mut libc::c_void =
+            mem::transmute(trampoline);
+
+        let mut thread = MaybeUninit::uninit();
+
+        match libc::pthread_create(
+            thread.as_mut_ptr(),
+            ptr::null(),
+            start_routine,
+            ptr::null_mut(),
+        ) {
+            0 => {
+                libc::pthread_detach(thread.assume_init());
+
+                // the trampoline's reference keeps the library resident until it's joined us
+                for _ in 0..extra {
+                    libc::dlclose(handle);
+                }
+
+                Ok(())
+            }
+            // leave the library resident, rather than unmapping it beneath the caller
+            error => {
+                libc::munmap(trampoline, code.len());
+
+                Err(io::Error::from_raw_os_error(error))
+            }
+        }
+    }
+
     /// Load the symbol, `symbol`.
     #[inline]
     pub fn symbol<S>(&self, symbol: S) -> Option<Symbol>
@@ -97,6 +240,90 @@ impl Library {
             None
         }
     }
+
+    /// The dynamic linker's entry for this library.
+    #[inline]
+    fn link_map(&self) -> Option<&LinkMap> {
+        let mut link_map: *const LinkMap = ptr::null();
+
+        // SAFETY: `RTLD_DI_LINKMAP` writes a pointer to the entry, which lives as long as the
+        // library is loaded.
+        unsafe {
+            if dlinfo(
+                self.as_ptr() as *mut libc::c_void,
+                RTLD_DI_LINKMAP,
+                (&mut link_map as *mut *const LinkMap).cast(),
+            ) != 0
+            {
+                return None;
+            }
+
+            link_map.as_ref()
+        }
+    }
+
+    /// Path of the library on disk.
+    #[inline]
+    pub fn path(&self) -> Option<PathBuf> {
+        Some(self.link_map()?.path())
+    }
+
+    /// Address the library is loaded at, which symbol offsets are relative to.
+    #[inline]
+    pub fn base_address(&self) -> Option<usize> {
+        Some(self.link_map()?.l_addr)
+    }
+
+    /// Read every symbol of the library, from it's file on disk.
+    ///
+    /// Unlike `symbol`, this includes `.symtab` symbols, which aren't exported.
+    pub fn symbols(&self) -> io::Result<SymbolTable> {
+        let link_map = self
+            .link_map()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "library isn't loaded"))?;
+
+        SymbolTable::read(link_map.path(), link_map.l_addr)
+    }
+}
+
+/// Code which calls `pthread_join(thread, NULL)`, then `dlclose(handle)`, as a thread's start
+/// routine.
+fn close_trampoline(
+    thread: libc::pthread_t,
+    pthread_join: *mut libc::c_void,
+    handle: *mut libc::c_void,
+    dlclose: *mut libc::c_void,
+) -> Vec<u8> {
+    let mut code = Vec::with_capacity(64);
+
+    // sub rsp, 8, aligning the stack for calls
+    code.extend_from_slice(&[0x48, 0x83, 0xEC, 0x08]);
+    // mov rdi, thread
+    code.extend_from_slice(&[0x48, 0xBF]);
+    code.extend_from_slice(&thread.to_le_bytes());
+    // xor esi, esi
+    code.extend_from_slice(&[0x31, 0xF6]);
+    // mov rax, pthread_join
+    code.extend_from_slice(&[0x48, 0xB8]);
+    code.extend_from_slice(&(pthread_join as u64).to_le_bytes());
+    // call rax
+    code.extend_from_slice(&[0xFF, 0xD0]);
+    // mov rdi, handle
+    code.extend_from_slice(&[0x48, 0xBF]);
+    code.extend_from_slice(&(handle as u64).to_le_bytes());
+    // mov rax, dlclose
+    code.extend_from_slice(&[0x48, 0xB8]);
+    code.extend_from_slice(&(dlclose as u64).to_le_bytes());
+    // call rax
+    code.extend_from_slice(&[0xFF, 0xD0]);
+    // add rsp, 8
+    code.extend_from_slice(&[0x48, 0x83, 0xC4, 0x08]);
+    // xor eax, eax
+    code.extend_from_slice(&[0x31, 0xC0]);
+    // ret
+    code.push(0xC3);
+
+    code
 }
 
 impl fmt::Debug for Library {
@@ -118,3 +345,56 @@ impl Symbol {
         self.address
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elysium_elf::{Builder, PF_R, PF_X};
+    use std::env;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn close_detached() {
+        extern "C" fn close(library: *mut libc::c_void) -> *mut libc::c_void {
+            let library = unsafe { Box::from_raw(library.cast::<Library>()) };
+
+            unsafe { library.close_detached(0).unwrap() };
+
+            ptr::null_mut()
+        }
+
+        let path = env::temp_dir().join(format!("elysium-dl-close-{}.so", std::process::id()));
+        let bytes = Builder::new()
+            .segment(".text", PF_R | PF_X, 0x1000, &[0xC3])
+            .loadable()
+            .build();
+
+        std::fs::write(&path, bytes).unwrap();
+
+        let library = Box::new(Library::load(&path).unwrap());
+        let mut thread = MaybeUninit::uninit();
+
+        unsafe {
+            assert_eq!(
+                libc::pthread_create(
+                    thread.as_mut_ptr(),
+                    ptr::null(),
+                    close,
+                    Box::into_raw(library).cast()
+                ),
+                0
+            );
+        }
+
+        let start = Instant::now();
+
+        while Library::exists(&path) {
+            assert!(start.elapsed() < Duration::from_secs(5));
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let _ = std::fs::remove_file(path);
+    }
+}
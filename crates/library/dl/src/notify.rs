@@ -0,0 +1,398 @@
+//! Notification of libraries being loaded.
+//!
+//! The dynamic linker calls `r_debug.r_brk` (`_dl_debug_state`) whenever it changes the list of
+//! loaded objects, which is how debuggers notice libraries being loaded. It's an empty function,
+//! which is replaced with a jump to `debug_state`, that wakes a thread which runs callbacks once
+//! the load has finished.
+//!
+//! If `_dl_debug_state` isn't as expected, that thread checks for new libraries periodically
+//! instead.
+//!
+//! `uninstall` must be called before the library containing this is unloaded, as the dynamic
+//! linker calls `_dl_debug_state` while unloading it.
+
+use crate::Library;
+use elysium_mem::decode::{self, Map};
+use elysium_mem::Patch;
+use std::ffi::{CStr, OsStr, OsString};
+use std::io;
+use std::lazy::SyncOnceCell;
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle, Thread};
+use std::time::Duration;
+
+/// How often to check for new libraries, if `_dl_debug_state` couldn't be replaced.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// `jmp [rip]`, followed by the absolute address.
+const JMP_ABSOLUTE: [u8; 6] = [0xFF, 0x25, 0x00, 0x00, 0x00, 0x00];
+const JMP_ABSOLUTE_LEN: usize = JMP_ABSOLUTE.len() + 8;
+
+/// The dynamic linker's debugging interface.
+#[repr(C)]
+struct RDebug {
+    r_version: libc::c_int,
+    r_map: *const (),
+    /// Called whenever the list of loaded objects changes.
+    r_brk: usize,
+    r_state: libc::c_int,
+    r_ldbase: usize,
+}
+
+type Callback = Box<dyn FnOnce(Library) + Send>;
+
+/// Callbacks waiting for a library, by file name.
+static PENDING: Mutex<Vec<(OsString, Callback)>> = Mutex::new(Vec::new());
+
+/// The thread that runs callbacks, and the jump to `debug_state`, once started.
+struct Dispatcher {
+    thread: JoinHandle<()>,
+    patch: Option<Patch>,
+}
+
+static DISPATCHER: Mutex<Option<Dispatcher>> = Mutex::new(None);
+
+/// Thread that runs callbacks, which `debug_state` wakes without locking.
+static THREAD: SyncOnceCell<Thread> = SyncOnceCell::new();
+
+/// Whether `uninstall` was called.
+static STOPPED: AtomicBool = AtomicBool::new(false);
+
+/// File name of `library`, which may be a path, or have a nul terminator.
+fn file_name(library: &OsStr) -> &OsStr {
+    let bytes = library.as_bytes();
+    let bytes = bytes.strip_suffix(b"\0").unwrap_or(bytes);
+    let path = Path::new(OsStr::from_bytes(bytes));
+
+    path.file_name().unwrap_or(path.as_os_str())
+}
+
+/// Paths of every loaded library, excluding the executable.
+pub fn loaded() -> Vec<PathBuf> {
+    unsafe extern "C" fn push(
+        info: *mut libc::dl_phdr_info,
+        _size: libc::size_t,
+        paths: *mut libc::c_void,
+    ) -> libc::c_int {
+        let paths = &mut *paths.cast::<Vec<PathBuf>>();
+        let name = (*info).dlpi_name;
+
+        if !name.is_null() {
+            let name = CStr::from_ptr(name).to_bytes();
+
+            if !name.is_empty() {
+                paths.push(PathBuf::from(OsStr::from_bytes(name)));
+            }
+        }
+
+        0
+    }
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+
+    // SAFETY: `push` is only called during `dl_iterate_phdr`, while `paths` is borrowed
+    unsafe {
+        libc::dl_iterate_phdr(Some(push), (&mut paths as *mut Vec<PathBuf>).cast());
+    }
+
+    paths
+}
+
+/// Call `callback` with `library` once it's loaded, immediately if it already is.
+///
+/// `library` is matched by file name, so `./bin/linux64/client_client.so` and
+/// `client_client.so` are equivalent. If the library is already loaded, `callback` runs on the
+/// calling thread, otherwise it runs on the notification thread, after the library's
+/// constructors.
+///
+/// After `uninstall`, only libraries that are already loaded are noticed.
+pub fn on_load<L, F>(library: L, callback: F)
+where
+    L: AsRef<OsStr>,
+    F: FnOnce(Library) + Send + 'static,
+{
+    let name = file_name(library.as_ref()).to_os_string();
+    let mut pending = PENDING.lock().unwrap();
+    let path = loaded()
+        .into_iter()
+        .find(|path| path.file_name() == Some(&name));
+
+    if let Some(library) = path.and_then(Library::open) {
+        drop(pending);
+        callback(library);
+
+        return;
+    }
+
+    // anything loaded after the check above wakes the dispatcher, which checks again
+    pending.push((name, Box::new(callback)));
+    drop(pending);
+
+    let mut dispatcher = DISPATCHER.lock().unwrap();
+
+    if dispatcher.is_some() || STOPPED.load(Ordering::Acquire) {
+        return;
+    }
+
+    let patch = unsafe { install() };
+    let installed = patch.is_some();
+    let thread = thread::Builder::new()
+        .name("elysium-dl".into())
+        .spawn(move || dispatch(installed))
+        .expect("failed to spawn the library notification thread");
+
+    let _ = THREAD.set(thread.thread().clone());
+
+    *dispatcher = Some(Dispatcher { thread, patch });
+}
+
+/// Stop noticing libraries being loaded, restoring `_dl_debug_state`, and wait for the
+/// notification thread to exit.
+///
+/// Callbacks still waiting for a library are dropped, without being called. If
+/// `_dl_debug_state` couldn't be restored, the library containing this mustn't be unloaded.
+pub fn uninstall() -> io::Result<()> {
+    STOPPED.store(true, Ordering::Release);
+
+    // taken, so callbacks running meanwhile may call `on_load`
+    let dispatcher = match DISPATCHER.lock().unwrap().take() {
+        Some(dispatcher) => dispatcher,
+        None => return Ok(()),
+    };
+
+    let restored = match dispatcher.patch {
+        Some(patch) => patch.revert(),
+        None => Ok(()),
+    };
+
+    dispatcher.thread.thread().unpark();
+
+    let _ = dispatcher.thread.join();
+
+    PENDING.lock().unwrap().clear();
+
+    restored
+}
+
+/// Wait for every load in progress, then run the callbacks of what's now loaded.
+fn dispatch(installed: bool) {
+    while !STOPPED.load(Ordering::Acquire) {
+        // `dlopen` holds the loader lock until the library is initialized, which this waits for
+        drop(Library::this());
+
+        let loaded = loaded();
+        let mut ready = Vec::new();
+
+        {
+            let mut pending = PENDING.lock().unwrap();
+
+            for (name, callback) in mem::take(&mut *pending) {
+                match loaded
+                    .iter()
+                    .find(|path| path.file_name() == Some(name.as_os_str()))
+                {
+                    Some(path) => ready.push((name, path, callback)),
+                    None => pending.push((name, callback)),
+                }
+            }
+        }
+
+        for (name, path, callback) in ready {
+            match Library::open(path) {
+                Some(library) => callback(library),
+                // unloaded since
+                None => PENDING.lock().unwrap().push((name, callback)),
+            }
+        }
+
+        if installed {
+            thread::park();
+        } else {
+            thread::park_timeout(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Replaces `_dl_debug_state`.
+///
+/// This is called with the loader lock held, so it mustn't block.
+extern "C" fn debug_state() {
+    if let Some(thread) = THREAD.get() {
+        thread.unpark();
+    }
+}
+
+/// Whether `code` is an empty function, followed by enough padding for an absolute jump.
+fn is_replaceable(code: &[u8]) -> bool {
+    let mut offset = 0;
+    let mut returned = false;
+
+    while offset < JMP_ABSOLUTE_LEN {
+        let instruction = match decode::decode(&code[offset..]) {
+            Some(instruction) => instruction,
+            None => return false,
+        };
+
+        let expected = match (instruction.map, instruction.opcode) {
+            // endbr64
+            (Map::Secondary, 0x1E) => !returned,
+            (Map::Primary, 0xC3) => !mem::replace(&mut returned, true),
+            // padding, nop and int3
+            (Map::Secondary, 0x1F) | (Map::Primary, 0x90 | 0xCC) => returned,
+            _ => false,
+        };
+
+        if !expected {
+            return false;
+        }
+
+        offset += instruction.len as usize;
+    }
+
+    true
+}
+
+/// `_dl_debug_state`, `None` if the dynamic linker doesn't have a debugging interface.
+fn debug_state_address() -> Option<*mut u8> {
+    let r_debug = unsafe { libc::dlsym(libc::RTLD_DEFAULT, b"_r_debug\0".as_ptr().cast()) };
+    let r_debug = unsafe { r_debug.cast::<RDebug>().as_ref()? };
+    let brk = r_debug.r_brk as *mut u8;
+
+    if brk.is_null() {
+        None
+    } else {
+        Some(brk)
+    }
+}
+
+/// Replace `_dl_debug_state` with a jump to `debug_state`, which is undone when the patch is
+/// dropped.
+unsafe fn install() -> Option<Patch> {
+    let brk = debug_state_address()?;
+
+    // the jump is written in one store, so nothing executes half of it, which needs it within
+    // 16 bytes
+    if brk.align_offset(16) != 0 {
+        return None;
+    }
+
+    let code = std::slice::from_raw_parts(brk, 16);
+
+    if !is_replaceable(code) {
+        return None;
+    }
+
+    let mut jmp = [0; JMP_ABSOLUTE_LEN];
+
+    jmp[..6].copy_from_slice(&JMP_ABSOLUTE);
+    jmp[6..].copy_from_slice(&(debug_state as *const () as usize).to_le_bytes());
+
+    Patch::atomic(brk, &jmp).ok()
+}
+
+impl Library {
+    /// Wait for the library, `library`, to be loaded.
+    ///
+    /// See `on_load` for how libraries are matched.
+    pub fn wait<L>(library: L) -> Self
+    where
+        L: AsRef<OsStr>,
+    {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+
+        on_load(library, move |library| {
+            let _ = sender.send(library);
+        });
+
+        receiver
+            .recv()
+            .expect("the library notification thread exited")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elysium_elf::{Builder, PF_R, PF_X};
+    use std::env;
+    use std::sync::mpsc;
+
+    #[test]
+    fn replaceable() {
+        // ret, then padding
+        let mut code = [0x90; 16];
+
+        code[0] = 0xC3;
+
+        assert!(is_replaceable(&code));
+
+        // endbr64, ret, int3
+        let mut code = [0xCC; 16];
+
+        code[..5].copy_from_slice(&[0xF3, 0x0F, 0x1E, 0xFA, 0xC3]);
+
+        assert!(is_replaceable(&code));
+
+        // not empty
+        code[4] = 0x55;
+
+        assert!(!is_replaceable(&code));
+
+        // the next function begins too soon
+        let mut code = [0x90; 16];
+
+        code[0] = 0xC3;
+        code[8] = 0x55;
+
+        assert!(!is_replaceable(&code));
+    }
+
+    #[test]
+    fn on_load() {
+        let debug_state = debug_state_address().map(|brk| unsafe { *brk.cast::<[u8; 16]>() });
+        let path = env::temp_dir().join(format!("elysium-dl-{}.so", std::process::id()));
+        let bytes = Builder::new()
+            .segment(".text", PF_R | PF_X, 0x1000, &[0xC3])
+            .loadable()
+            .build();
+
+        std::fs::write(&path, bytes).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let name = path.file_name().unwrap().to_os_string();
+
+        {
+            let sender = sender.clone();
+
+            super::on_load(&name, move |library| sender.send(library.path()).unwrap());
+        }
+
+        assert!(receiver.try_recv().is_err());
+
+        let library = Library::open_global(&path).unwrap();
+        let loaded = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        assert_eq!(loaded, Some(path.clone()));
+
+        // already loaded
+        super::on_load(&name, move |library| sender.send(library.path()).unwrap());
+
+        assert_eq!(receiver.try_recv().unwrap(), Some(path.clone()));
+
+        drop(library);
+
+        super::uninstall().unwrap();
+
+        // `_dl_debug_state` is restored
+        assert_eq!(
+            debug_state_address().map(|brk| unsafe { *brk.cast::<[u8; 16]>() }),
+            debug_state
+        );
+
+        let _ = std::fs::remove_file(path);
+    }
+}
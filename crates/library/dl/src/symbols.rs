@@ -0,0 +1,365 @@
+//! Symbol tables of loaded libraries, read from their files on disk.
+
+use elysium_elf::{Elf, SHT_DYNSYM, SHT_SYMTAB, STT_FUNC, STT_GNU_IFUNC, STT_OBJECT};
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, OsStr};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io, mem, ptr};
+
+/// `dladdr1` should return the `link_map` of the object.
+const RTLD_DL_LINKMAP: libc::c_int = 2;
+
+/// An entry of the dynamic linker's list of loaded objects.
+#[repr(C)]
+pub(crate) struct LinkMap {
+    /// Difference between addresses in the file and in memory.
+    pub l_addr: usize,
+    /// Path of the object, empty for the executable.
+    pub l_name: *const libc::c_char,
+    pub l_ld: *const (),
+    pub l_next: *const LinkMap,
+    pub l_prev: *const LinkMap,
+}
+
+impl LinkMap {
+    /// Path of the object.
+    pub(crate) fn path(&self) -> PathBuf {
+        // SAFETY: `l_name` is always a nul terminated string
+        let name = unsafe { CStr::from_ptr(self.l_name) };
+
+        match name.to_bytes() {
+            b"" => PathBuf::from("/proc/self/exe"),
+            name => PathBuf::from(OsStr::from_bytes(name)),
+        }
+    }
+}
+
+extern "C" {
+    fn dladdr1(
+        address: *const libc::c_void,
+        info: *mut libc::Dl_info,
+        extra_info: *mut *mut libc::c_void,
+        flags: libc::c_int,
+    ) -> libc::c_int;
+}
+
+/// A function or object within a library.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SymbolInfo {
+    name: Box<str>,
+    offset: usize,
+    size: usize,
+    version: Option<Box<str>>,
+    dynamic: bool,
+}
+
+impl SymbolInfo {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Offset from the libraries base address.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Size in bytes, zero if unknown.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Version, such as `GLIBC_2.2.5`, only dynamic symbols have versions.
+    #[inline]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Whether this symbol is exported, rather than only being within `.symtab`.
+    #[inline]
+    pub fn is_dynamic(&self) -> bool {
+        self.dynamic
+    }
+
+    /// Whether `offset` is within this symbol.
+    #[inline]
+    pub fn contains(&self, offset: usize) -> bool {
+        offset >= self.offset && offset - self.offset < self.size
+    }
+}
+
+/// Every symbol of a library.
+#[derive(Debug)]
+pub struct SymbolTable {
+    name: Box<str>,
+    base_address: usize,
+    /// Sorted by offset.
+    symbols: Vec<SymbolInfo>,
+}
+
+impl SymbolTable {
+    /// Read the symbol tables of the file at `path`, loaded at `base_address`.
+    pub fn read<P>(path: P, base_address: usize) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let bytes = fs::read(path)?;
+        let elf = Elf::parse(&bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        // `/proc/self/exe` is a link to the executable
+        let path = fs::read_link(path).unwrap_or_else(|_| path.into());
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        Ok(Self::from_elf(&name, &elf, base_address))
+    }
+
+    /// Every dynamic symbol, and every `.symtab` symbol if the file has one.
+    pub fn from_elf(name: &str, elf: &Elf<'_>, base_address: usize) -> Self {
+        let versions = elf.versions().unwrap_or_default();
+        let mut symbols = Vec::new();
+        let mut exported = HashSet::new();
+
+        for (kind, dynamic) in [(SHT_DYNSYM, true), (SHT_SYMTAB, false)] {
+            let table = match elf.symbols(kind) {
+                Some(table) => table,
+                None => continue,
+            };
+
+            for (index, symbol) in table.enumerate() {
+                if !symbol.is_defined()
+                    || symbol.name.is_empty()
+                    || !matches!(symbol.kind(), STT_FUNC | STT_OBJECT | STT_GNU_IFUNC)
+                {
+                    continue;
+                }
+
+                // `.symtab` repeats the dynamic symbols
+                if dynamic {
+                    exported.insert((symbol.value, symbol.name));
+                } else if exported.contains(&(symbol.value, symbol.name)) {
+                    continue;
+                }
+
+                symbols.push(SymbolInfo {
+                    name: symbol.name.into(),
+                    offset: symbol.value as usize,
+                    size: symbol.size as usize,
+                    version: dynamic
+                        .then(|| versions.get(index))
+                        .flatten()
+                        .map(Into::into),
+                    dynamic,
+                });
+            }
+        }
+
+        symbols.sort_by_key(|symbol| symbol.offset);
+
+        Self {
+            name: name.into(),
+            base_address,
+            symbols,
+        }
+    }
+
+    /// File name of the library, such as `client_client.so`.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Address the library is loaded at.
+    #[inline]
+    pub fn base_address(&self) -> usize {
+        self.base_address
+    }
+
+    /// Iterate the symbols, in order of address.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &SymbolInfo> {
+        self.symbols.iter()
+    }
+
+    /// The first symbol named `name`.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&SymbolInfo> {
+        self.symbols.iter().find(|symbol| *symbol.name == *name)
+    }
+
+    /// Address of the symbol named `name`.
+    #[inline]
+    pub fn address_of(&self, name: &str) -> Option<*const ()> {
+        let symbol = self.get(name)?;
+
+        Some(self.base_address.wrapping_add(symbol.offset) as *const ())
+    }
+
+    /// The symbol containing `address`, or preceding it if the size of that symbol is unknown.
+    pub fn nearest(&self, address: *const ()) -> Option<&SymbolInfo> {
+        let offset = (address as usize).checked_sub(self.base_address)?;
+        let end = self
+            .symbols
+            .partition_point(|symbol| symbol.offset <= offset);
+
+        let preceding = &self.symbols[..end];
+
+        preceding
+            .iter()
+            .rev()
+            .find(|symbol| symbol.contains(offset))
+            .or_else(|| preceding.last().filter(|symbol| symbol.size == 0))
+    }
+
+    /// Where `address` is, relative to the nearest symbol.
+    pub fn locate(&self, address: *const ()) -> Location<'_> {
+        let offset = (address as usize).wrapping_sub(self.base_address);
+
+        match self.nearest(address) {
+            Some(symbol) => Location {
+                library: &self.name,
+                symbol: Some(&symbol.name),
+                offset: offset - symbol.offset,
+            },
+            None => Location {
+                library: &self.name,
+                symbol: None,
+                offset,
+            },
+        }
+    }
+}
+
+/// Where an address is, displayed as `client_client.so!CreateMove+0x12`, or
+/// `client_client.so+0x1a2b30` when there is no symbol for it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Location<'a> {
+    pub library: &'a str,
+    pub symbol: Option<&'a str>,
+    /// Offset from the symbol, or the library if there is no symbol.
+    pub offset: usize,
+}
+
+impl<'a> fmt::Display for Location<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Location {
+            library,
+            symbol,
+            offset,
+        } = self;
+
+        match symbol {
+            Some(symbol) if *offset == 0 => write!(fmt, "{library}!{symbol}"),
+            Some(symbol) => write!(fmt, "{library}!{symbol}+{offset:#x}"),
+            None => write!(fmt, "{library}+{offset:#x}"),
+        }
+    }
+}
+
+/// The `link_map` of the loaded object containing `address`.
+fn link_map_of(address: *const ()) -> Option<&'static LinkMap> {
+    // SAFETY: `dladdr1` only writes to `info` and `link_map`
+    unsafe {
+        let mut info = mem::zeroed();
+        let mut link_map: *mut libc::c_void = ptr::null_mut();
+
+        if dladdr1(address.cast(), &mut info, &mut link_map, RTLD_DL_LINKMAP) == 0 {
+            return None;
+        }
+
+        link_map.cast::<LinkMap>().as_ref()
+    }
+}
+
+/// Reverse lookup of addresses, like `dladdr`, but including `.symtab` symbols.
+///
+/// Symbol tables are read once per library, then kept.
+#[derive(Debug, Default)]
+pub struct Symbolizer {
+    tables: HashMap<PathBuf, Option<SymbolTable>>,
+}
+
+impl Symbolizer {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Symbol table of the library containing `address`.
+    pub fn table_of(&mut self, address: *const ()) -> Option<&SymbolTable> {
+        let link_map = link_map_of(address)?;
+        let path = link_map.path();
+
+        self.tables
+            .entry(path)
+            .or_insert_with_key(|path| SymbolTable::read(path, link_map.l_addr).ok())
+            .as_ref()
+    }
+
+    /// Where `address` is, `None` if it isn't within a loaded library.
+    #[inline]
+    pub fn locate(&mut self, address: *const ()) -> Option<Location<'_>> {
+        Some(self.table_of(address)?.locate(address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elysium_elf::{Builder, PF_R, PF_X};
+
+    #[test]
+    fn table() {
+        let bytes = Builder::new()
+            .segment(".text", PF_R | PF_X, 0x1000, &[0xC3; 0x40])
+            .dynamic_symbol("CreateInterface", 0x1000, 0x10, Some("ELYSIUM_1.0"))
+            .symbol("CreateInterface", 0x1000, 0x10)
+            .symbol("CreateMove", 0x1020, 0x20)
+            .build();
+
+        let elf = Elf::parse(&bytes).unwrap();
+        let table = SymbolTable::from_elf("client_client.so", &elf, 0x7000_0000);
+        let names: Vec<_> = table.iter().map(SymbolInfo::name).collect();
+
+        assert_eq!(names, ["CreateInterface", "CreateMove"]);
+
+        let interface = table.get("CreateInterface").unwrap();
+
+        assert!(interface.is_dynamic());
+        assert_eq!(interface.version(), Some("ELYSIUM_1.0"));
+        assert_eq!(table.get("CreateMove").unwrap().version(), None);
+        assert_eq!(
+            table.address_of("CreateMove"),
+            Some(0x7000_1020 as *const ())
+        );
+
+        let location = |address: usize| table.locate(address as *const ()).to_string();
+
+        assert_eq!(location(0x7000_1032), "client_client.so!CreateMove+0x12");
+        assert_eq!(location(0x7000_1000), "client_client.so!CreateInterface");
+        // between symbols
+        assert_eq!(location(0x7000_1018), "client_client.so+0x1018");
+    }
+
+    #[test]
+    fn symbolizer() {
+        let mut symbolizer = Symbolizer::new();
+        let malloc = libc::malloc as *const ();
+        let location = symbolizer.locate(malloc).unwrap();
+
+        assert!(location.library.starts_with("libc"));
+        assert_eq!(location.offset, 0);
+
+        let local = 0u8;
+
+        // the stack isn't a library
+        assert_eq!(symbolizer.locate(&local as *const u8 as *const ()), None);
+    }
+}
@@ -0,0 +1,490 @@
+use crate::symbol::{SHT_GNU_VERDEF, SHT_GNU_VERSYM, STB_GLOBAL, STT_FUNC};
+use crate::*;
+
+const PAGE_SIZE: u64 = 4096;
+
+/// Symbol has an absolute value, rather than being within a section.
+const SHN_ABS: u16 = 0xFFF1;
+
+struct Segment {
+    name: String,
+    flags: u32,
+    address: u64,
+    bytes: Vec<u8>,
+}
+
+struct Symbol {
+    name: String,
+    address: u64,
+    size: u64,
+    dynamic: bool,
+    version: Option<String>,
+}
+
+#[derive(Default)]
+struct Section {
+    name: String,
+    kind: u32,
+    flags: u64,
+    address: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    info: u32,
+    entry_size: u64,
+}
+
+/// Builds minimal ELF files, for test fixtures.
+///
+/// Each segment gets a section of the same name covering it, which is enough to exercise
+/// everything that reads segments, sections, notes, or symbols.
+#[derive(Default)]
+pub struct Builder {
+    segments: Vec<Segment>,
+    build_id: Option<Vec<u8>>,
+    symbols: Vec<Symbol>,
+    loadable: bool,
+}
+
+/// Append `data` aligned to `align`, returning it's offset.
+fn push_data(bytes: &mut Vec<u8>, align: usize, data: &[u8]) -> u64 {
+    bytes.resize((bytes.len() + align - 1) & !(align - 1), 0);
+
+    let offset = bytes.len() as u64;
+
+    bytes.extend_from_slice(data);
+    offset
+}
+
+/// Append `string` to a string table, returning it's offset.
+fn push_string(table: &mut Vec<u8>, string: &str) -> u32 {
+    let offset = table.len() as u32;
+
+    table.extend_from_slice(string.as_bytes());
+    table.push(0);
+    offset
+}
+
+impl Builder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a `PT_LOAD` segment with `flags` (`PF_*`), and a section named `name` covering it.
+    pub fn segment(mut self, name: &str, flags: u32, address: u64, bytes: &[u8]) -> Self {
+        self.segments.push(Segment {
+            name: name.into(),
+            flags,
+            address,
+            bytes: bytes.into(),
+        });
+
+        self
+    }
+
+    /// Add a GNU build-id note, as a `PT_NOTE` segment and `.note.gnu.build-id` section.
+    pub fn build_id(mut self, build_id: &[u8]) -> Self {
+        self.build_id = Some(build_id.into());
+        self
+    }
+
+    /// Add a dynamic section with empty tables, and a non-executable stack, so the file can be
+    /// loaded with `dlopen`.
+    pub fn loadable(mut self) -> Self {
+        self.loadable = true;
+        self
+    }
+
+    /// Add a function to `.symtab`.
+    pub fn symbol(mut self, name: &str, address: u64, size: u64) -> Self {
+        self.symbols.push(Symbol {
+            name: name.into(),
+            address,
+            size,
+            dynamic: false,
+            version: None,
+        });
+
+        self
+    }
+
+    /// Add a function to `.dynsym`, with a version defined in `.gnu.version_d`.
+    pub fn dynamic_symbol(
+        mut self,
+        name: &str,
+        address: u64,
+        size: u64,
+        version: Option<&str>,
+    ) -> Self {
+        self.symbols.push(Symbol {
+            name: name.into(),
+            address,
+            size,
+            dynamic: true,
+            version: version.map(Into::into),
+        });
+
+        self
+    }
+
+    /// Index of the section covering `address`.
+    fn section_index_of(&self, address: u64) -> u16 {
+        self.segments
+            .iter()
+            .position(|segment| {
+                address >= segment.address && address < segment.address + segment.bytes.len() as u64
+            })
+            .map(|index| index as u16 + 1)
+            .unwrap_or(SHN_ABS)
+    }
+
+    /// Symbol table, and it's string table, `None` if there are no symbols for it.
+    fn symbol_table(&self, dynamic: bool) -> Option<(Vec<u8>, Vec<u8>)> {
+        let mut symbols = self
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.dynamic == dynamic)
+            .peekable();
+
+        symbols.peek()?;
+
+        // the first symbol is null
+        let mut table = vec![0; 24];
+        let mut names = vec![0];
+
+        for symbol in symbols {
+            let name = push_string(&mut names, &symbol.name);
+
+            table.extend_from_slice(&name.to_le_bytes());
+            table.push(STB_GLOBAL << 4 | STT_FUNC);
+            table.push(0);
+            table.extend_from_slice(&self.section_index_of(symbol.address).to_le_bytes());
+            table.extend_from_slice(&symbol.address.to_le_bytes());
+            table.extend_from_slice(&symbol.size.to_le_bytes());
+        }
+
+        Some((table, names))
+    }
+
+    /// `.gnu.version` and `.gnu.version_d` for the dynamic symbols, `None` if none are
+    /// versioned. Version names are appended to `strings`.
+    fn versions(&self, strings: &mut Vec<u8>) -> Option<(Vec<u8>, Vec<u8>, u32)> {
+        let symbols: Vec<&Symbol> = self
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.dynamic)
+            .collect();
+        let mut versions: Vec<&str> = Vec::new();
+
+        for version in symbols
+            .iter()
+            .filter_map(|symbol| symbol.version.as_deref())
+        {
+            if !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+
+        if versions.is_empty() {
+            return None;
+        }
+
+        // the null symbol is local, and unversioned symbols are global
+        let mut indices = 0u16.to_le_bytes().to_vec();
+
+        for symbol in &symbols {
+            let index = match symbol.version.as_deref() {
+                Some(version) => versions.iter().position(|name| *name == version).unwrap() + 2,
+                None => 1,
+            };
+
+            indices.extend_from_slice(&(index as u16).to_le_bytes());
+        }
+
+        // the first definition is the library itself
+        let mut definitions = Vec::new();
+        let count = versions.len() + 1;
+
+        for (index, name) in ["fixture.so"].iter().chain(&versions).enumerate() {
+            let flags: u16 = if index == 0 { 1 } else { 0 };
+            let next: u32 = if index + 1 == count { 0 } else { 28 };
+            let name = push_string(strings, name);
+
+            // version, flags, index, aux count, hash, aux offset, next
+            definitions.extend_from_slice(&1u16.to_le_bytes());
+            definitions.extend_from_slice(&flags.to_le_bytes());
+            definitions.extend_from_slice(&(index as u16 + 1).to_le_bytes());
+            definitions.extend_from_slice(&1u16.to_le_bytes());
+            definitions.extend_from_slice(&0u32.to_le_bytes());
+            definitions.extend_from_slice(&20u32.to_le_bytes());
+            definitions.extend_from_slice(&next.to_le_bytes());
+            // name, next
+            definitions.extend_from_slice(&name.to_le_bytes());
+            definitions.extend_from_slice(&0u32.to_le_bytes());
+        }
+
+        Some((indices, definitions, count as u32))
+    }
+
+    /// Write the file.
+    pub fn build(mut self) -> Vec<u8> {
+        // the dynamic linker expects a hash table, string table and symbol table, which are
+        // empty, and placed after the dynamic section on a page after everything else
+        let dynamic = self.loadable.then(|| {
+            let address = self
+                .segments
+                .iter()
+                .map(|segment| segment.address + segment.bytes.len() as u64)
+                .max()
+                .unwrap_or(0);
+
+            let address = (address + PAGE_SIZE - 1) / PAGE_SIZE * PAGE_SIZE + PAGE_SIZE;
+            let tables = address + 6 * 16;
+            let entries: [(u64, u64); 6] = [
+                // DT_HASH, DT_STRTAB, DT_SYMTAB, DT_STRSZ, DT_SYMENT, DT_NULL
+                (4, tables),
+                (5, tables + 16),
+                (6, tables + 24),
+                (10, 1),
+                (11, 24),
+                (0, 0),
+            ];
+
+            let mut bytes = Vec::new();
+
+            for (tag, value) in entries {
+                bytes.extend_from_slice(&tag.to_le_bytes());
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+
+            // one bucket, one chain, both empty
+            bytes.extend_from_slice(&1u32.to_le_bytes());
+            bytes.extend_from_slice(&1u32.to_le_bytes());
+            bytes.extend_from_slice(&[0; 8]);
+            // string table, then the null symbol
+            bytes.extend_from_slice(&[0; 8]);
+            bytes.extend_from_slice(&[0; 24]);
+
+            self.segments.push(Segment {
+                name: ".dynamic".into(),
+                flags: PF_R | PF_W,
+                address,
+                bytes,
+            });
+
+            self.segments.len() - 1
+        });
+
+        let note = self.build_id.as_ref().map(|build_id| {
+            let mut note = Vec::new();
+
+            note.extend_from_slice(&4u32.to_le_bytes());
+            note.extend_from_slice(&(build_id.len() as u32).to_le_bytes());
+            note.extend_from_slice(&note::NT_GNU_BUILD_ID.to_le_bytes());
+            note.extend_from_slice(b"GNU\0");
+            note.extend_from_slice(build_id);
+            note.resize((note.len() + 3) & !3, 0);
+            note
+        });
+
+        let program_header_count =
+            self.segments.len() + note.is_some() as usize + dynamic.map_or(0, |_| 2);
+        let mut bytes = vec![0; HEADER_SIZE + program_header_count * PROGRAM_HEADER_SIZE];
+        let mut program_headers = Vec::new();
+        let mut sections = vec![Section::default()];
+
+        for segment in &self.segments {
+            // file offsets must be congruent to addresses, modulo the page size
+            let page_offset = segment.address % PAGE_SIZE;
+            let mut offset = (bytes.len() as u64 & !(PAGE_SIZE - 1)) + page_offset;
+
+            if offset < bytes.len() as u64 {
+                offset += PAGE_SIZE;
+            }
+
+            bytes.resize(offset as usize, 0);
+            bytes.extend_from_slice(&segment.bytes);
+
+            let len = segment.bytes.len() as u64;
+            let mut flags = SHF_ALLOC;
+
+            if segment.flags & PF_W != 0 {
+                flags |= SHF_WRITE;
+            }
+
+            if segment.flags & PF_X != 0 {
+                flags |= SHF_EXECINSTR;
+            }
+
+            program_headers.push((PT_LOAD, segment.flags, offset, segment.address, len));
+            sections.push(Section {
+                name: segment.name.clone(),
+                kind: SHT_PROGBITS,
+                flags,
+                address: segment.address,
+                offset,
+                size: len,
+                ..Section::default()
+            });
+        }
+
+        if let Some(index) = dynamic {
+            let (_, _, offset, address, _) = program_headers[index];
+
+            program_headers.push((PT_DYNAMIC, PF_R | PF_W, offset, address, 6 * 16));
+            program_headers.push((PT_GNU_STACK, PF_R | PF_W, 0, 0, 0));
+        }
+
+        if let Some(note) = &note {
+            let offset = push_data(&mut bytes, 4, note);
+            let len = note.len() as u64;
+
+            program_headers.push((PT_NOTE, PF_R, offset, 0, len));
+            sections.push(Section {
+                name: ".note.gnu.build-id".into(),
+                kind: SHT_NOTE,
+                offset,
+                size: len,
+                ..Section::default()
+            });
+        }
+
+        for dynamic in [false, true] {
+            let (table, mut strings) = match self.symbol_table(dynamic) {
+                Some(tables) => tables,
+                None => continue,
+            };
+
+            let (table_name, strings_name, kind) = if dynamic {
+                (".dynsym", ".dynstr", SHT_DYNSYM)
+            } else {
+                (".symtab", ".strtab", SHT_SYMTAB)
+            };
+
+            let table_index = sections.len() as u32;
+            let versions = dynamic.then(|| self.versions(&mut strings)).flatten();
+
+            sections.push(Section {
+                name: table_name.into(),
+                kind,
+                offset: push_data(&mut bytes, 8, &table),
+                size: table.len() as u64,
+                link: table_index + 1,
+                // index of the first global symbol
+                info: 1,
+                entry_size: 24,
+                ..Section::default()
+            });
+
+            sections.push(Section {
+                name: strings_name.into(),
+                kind: SHT_STRTAB,
+                offset: push_data(&mut bytes, 1, &strings),
+                size: strings.len() as u64,
+                ..Section::default()
+            });
+
+            if let Some((indices, definitions, count)) = versions {
+                sections.push(Section {
+                    name: ".gnu.version".into(),
+                    kind: SHT_GNU_VERSYM,
+                    offset: push_data(&mut bytes, 2, &indices),
+                    size: indices.len() as u64,
+                    link: table_index,
+                    entry_size: 2,
+                    ..Section::default()
+                });
+
+                sections.push(Section {
+                    name: ".gnu.version_d".into(),
+                    kind: SHT_GNU_VERDEF,
+                    offset: push_data(&mut bytes, 8, &definitions),
+                    size: definitions.len() as u64,
+                    link: table_index + 1,
+                    info: count,
+                    ..Section::default()
+                });
+            }
+        }
+
+        // section name string table
+        let mut names = vec![0];
+        let names_index = sections.len();
+
+        sections.push(Section {
+            name: ".shstrtab".into(),
+            kind: SHT_STRTAB,
+            ..Section::default()
+        });
+
+        let name_offsets: Vec<u32> = sections
+            .iter()
+            .map(|section| match section.name.as_str() {
+                "" => 0,
+                name => push_string(&mut names, name),
+            })
+            .collect();
+
+        sections[names_index].offset = push_data(&mut bytes, 1, &names);
+        sections[names_index].size = names.len() as u64;
+        bytes.resize((bytes.len() + 7) & !7, 0);
+
+        let section_headers_offset = bytes.len() as u64;
+
+        for (section, name) in sections.iter().zip(&name_offsets) {
+            bytes.extend_from_slice(&name.to_le_bytes());
+            bytes.extend_from_slice(&section.kind.to_le_bytes());
+            bytes.extend_from_slice(&section.flags.to_le_bytes());
+            bytes.extend_from_slice(&section.address.to_le_bytes());
+            bytes.extend_from_slice(&section.offset.to_le_bytes());
+            bytes.extend_from_slice(&section.size.to_le_bytes());
+            bytes.extend_from_slice(&section.link.to_le_bytes());
+            bytes.extend_from_slice(&section.info.to_le_bytes());
+            // align
+            bytes.extend_from_slice(&1u64.to_le_bytes());
+            bytes.extend_from_slice(&section.entry_size.to_le_bytes());
+        }
+
+        for (index, (kind, flags, offset, address, len)) in program_headers.iter().enumerate() {
+            let start = HEADER_SIZE + index * PROGRAM_HEADER_SIZE;
+            let header = &mut bytes[start..start + PROGRAM_HEADER_SIZE];
+            let align = match *kind {
+                PT_LOAD => PAGE_SIZE,
+                PT_DYNAMIC => 8,
+                _ => 4,
+            };
+
+            header[0..4].copy_from_slice(&kind.to_le_bytes());
+            header[4..8].copy_from_slice(&flags.to_le_bytes());
+            header[8..16].copy_from_slice(&offset.to_le_bytes());
+            header[16..24].copy_from_slice(&address.to_le_bytes());
+            header[24..32].copy_from_slice(&address.to_le_bytes());
+            header[32..40].copy_from_slice(&len.to_le_bytes());
+            header[40..48].copy_from_slice(&len.to_le_bytes());
+            header[48..56].copy_from_slice(&align.to_le_bytes());
+        }
+
+        let header = &mut bytes[..HEADER_SIZE];
+
+        header[..4].copy_from_slice(&MAGIC);
+        header[4] = CLASS_64;
+        header[5] = DATA_LE;
+        // version
+        header[6] = 1;
+        // ET_DYN, EM_X86_64, version
+        header[16..18].copy_from_slice(&3u16.to_le_bytes());
+        header[18..20].copy_from_slice(&62u16.to_le_bytes());
+        header[20..24].copy_from_slice(&1u32.to_le_bytes());
+        header[32..40].copy_from_slice(&(HEADER_SIZE as u64).to_le_bytes());
+        header[40..48].copy_from_slice(&section_headers_offset.to_le_bytes());
+        header[52..54].copy_from_slice(&(HEADER_SIZE as u16).to_le_bytes());
+        header[54..56].copy_from_slice(&(PROGRAM_HEADER_SIZE as u16).to_le_bytes());
+        header[56..58].copy_from_slice(&(program_headers.len() as u16).to_le_bytes());
+        header[58..60].copy_from_slice(&(SECTION_HEADER_SIZE as u16).to_le_bytes());
+        header[60..62].copy_from_slice(&(sections.len() as u16).to_le_bytes());
+        header[62..64].copy_from_slice(&(names_index as u16).to_le_bytes());
+
+        bytes
+    }
+}
@@ -0,0 +1,140 @@
+use crate::*;
+use std::io::{self, Read, Seek, SeekFrom};
+
+#[inline]
+fn invalid(error: ElfError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Read `len` bytes at `offset`.
+fn read_at<R>(reader: &mut R, (offset, len): (u64, u64)) -> io::Result<Vec<u8>>
+where
+    R: Read + Seek,
+{
+    let len = usize::try_from(len).map_err(|_| invalid(ElfError::Truncated))?;
+    let mut bytes = vec![0; len];
+
+    reader.seek(SeekFrom::Start(offset))?;
+    reader
+        .read_exact(&mut bytes)
+        .map_err(|error| match error.kind() {
+            io::ErrorKind::UnexpectedEof => invalid(ElfError::Truncated),
+            _ => error,
+        })?;
+
+    Ok(bytes)
+}
+
+/// The program headers, and sections, of an ELF file.
+///
+/// Unlike `Elf`, only the headers, and section names, are read, rather than the whole file.
+#[derive(Clone, Debug)]
+pub struct Headers {
+    program_headers: Vec<ProgramHeader>,
+    section_headers: Vec<SectionHeader>,
+    section_names: Vec<u8>,
+}
+
+impl Headers {
+    /// Read the headers of an ELF file.
+    pub fn read<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: Read + Seek,
+    {
+        let mut header = Vec::with_capacity(HEADER_SIZE);
+
+        reader.seek(SeekFrom::Start(0))?;
+        reader
+            .by_ref()
+            .take(HEADER_SIZE as u64)
+            .read_to_end(&mut header)?;
+
+        check_header(&header).map_err(invalid)?;
+
+        let program_headers = program_header_table(&header).map_err(invalid)?;
+        let program_headers = read_at(reader, program_headers)?
+            .chunks_exact(PROGRAM_HEADER_SIZE)
+            .map(ProgramHeader::parse)
+            .collect();
+
+        let section_headers = section_header_table(&header).map_err(invalid)?;
+        let section_headers: Vec<_> = read_at(reader, section_headers)?
+            .chunks_exact(SECTION_HEADER_SIZE)
+            .map(SectionHeader::parse)
+            .collect();
+
+        let section_names = match section_headers.get(section_names_index(&header)) {
+            Some(names) if names.kind != SHT_NOBITS => read_at(reader, (names.offset, names.size))?,
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            program_headers,
+            section_headers,
+            section_names,
+        })
+    }
+
+    /// Iterate the program headers.
+    #[inline]
+    pub fn program_headers(&self) -> impl Iterator<Item = ProgramHeader> + '_ {
+        self.program_headers.iter().copied()
+    }
+
+    /// Iterate the section headers.
+    #[inline]
+    pub fn section_headers(&self) -> impl Iterator<Item = SectionHeader> + '_ {
+        self.section_headers.iter().copied()
+    }
+
+    /// Iterate the sections, with their names.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'_>> + '_ {
+        self.section_headers().map(move |header| Section {
+            name: self.section_name(&header).unwrap_or(""),
+            header,
+        })
+    }
+
+    /// The name of a section.
+    #[inline]
+    pub fn section_name(&self, header: &SectionHeader) -> Option<&str> {
+        string(&self.section_names, header.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn headers() {
+        let bytes = Builder::new()
+            .segment(".text", PF_R | PF_X, 0x1000, &[0x55, 0xC3])
+            .segment(".rodata", PF_R, 0x2000, b"hello")
+            .build();
+
+        let elf = Elf::parse(&bytes).unwrap();
+        let headers = Headers::read(&mut Cursor::new(&bytes)).unwrap();
+
+        assert!(headers.program_headers().eq(elf.program_headers()));
+
+        let sections: Vec<_> = headers
+            .sections()
+            .map(|section| (section.name, section.header))
+            .collect();
+
+        let expected: Vec<_> = elf
+            .sections()
+            .map(|section| (section.name, section.header))
+            .collect();
+
+        assert_eq!(sections, expected);
+        assert!(sections.iter().any(|(name, _)| *name == ".text"));
+
+        // the program headers are past the end
+        let error = Headers::read(&mut Cursor::new(&bytes[..80])).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,496 @@
+//! Reading 64-bit little-endian ELF files.
+//!
+//! Everything borrows from the bytes of the file, nothing is copied, except `Headers`, which
+//! reads only the headers of a file too large to read whole.
+
+use std::fmt;
+
+#[cfg(any(test, feature = "builder"))]
+pub use builder::Builder;
+pub use headers::Headers;
+pub use note::{build_id, notes, Note, Notes};
+pub use symbol::{
+    Symbol, Symbols, Versions, SHN_UNDEF, SHT_GNU_VERDEF, SHT_GNU_VERNEED, SHT_GNU_VERSYM,
+    STB_GLOBAL, STB_LOCAL, STB_WEAK, STT_FUNC, STT_GNU_IFUNC, STT_OBJECT,
+};
+
+#[cfg(any(test, feature = "builder"))]
+mod builder;
+mod headers;
+mod note;
+mod symbol;
+
+/// Loadable segment.
+pub const PT_LOAD: u32 = 1;
+/// Dynamic linking information.
+pub const PT_DYNAMIC: u32 = 2;
+/// Auxiliary information, such as the build-id.
+pub const PT_NOTE: u32 = 4;
+/// Permissions of the stack, executable if absent.
+pub const PT_GNU_STACK: u32 = 0x6474_E551;
+
+/// Segment is executable.
+pub const PF_X: u32 = 1;
+/// Segment is writable.
+pub const PF_W: u32 = 2;
+/// Segment is readable.
+pub const PF_R: u32 = 4;
+
+/// Section holds program defined data, such as code.
+pub const SHT_PROGBITS: u32 = 1;
+/// Section holds a symbol table.
+pub const SHT_SYMTAB: u32 = 2;
+/// Section holds a string table.
+pub const SHT_STRTAB: u32 = 3;
+/// Section holds notes.
+pub const SHT_NOTE: u32 = 7;
+/// Section occupies no space in the file, such as `.bss`.
+pub const SHT_NOBITS: u32 = 8;
+/// Section holds the dynamic symbol table.
+pub const SHT_DYNSYM: u32 = 11;
+
+/// Section is writable.
+pub const SHF_WRITE: u64 = 1;
+/// Section is loaded into memory.
+pub const SHF_ALLOC: u64 = 2;
+/// Section is executable.
+pub const SHF_EXECINSTR: u64 = 4;
+
+const MAGIC: [u8; 4] = *b"\x7FELF";
+const CLASS_64: u8 = 2;
+const DATA_LE: u8 = 1;
+
+const HEADER_SIZE: usize = 64;
+const PROGRAM_HEADER_SIZE: usize = 56;
+const SECTION_HEADER_SIZE: usize = 64;
+
+/// Why an ELF file couldn't be read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ElfError {
+    /// Doesn't begin with `\x7FELF`.
+    Magic,
+    /// Not a 64-bit little-endian file.
+    Unsupported,
+    /// A header, or what it refers to, is past the end of the file.
+    Truncated,
+}
+
+impl fmt::Display for ElfError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ElfError::Magic => "not an ELF file",
+            ElfError::Unsupported => "not a 64-bit little-endian ELF file",
+            ElfError::Truncated => "truncated ELF file",
+        };
+
+        fmt.write_str(message)
+    }
+}
+
+impl std::error::Error for ElfError {}
+
+#[inline]
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+#[inline]
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut value = [0; 4];
+
+    value.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(value)
+}
+
+#[inline]
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    let mut value = [0; 8];
+
+    value.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(value)
+}
+
+/// Slice `len` bytes at `offset`, `None` if out of bounds.
+#[inline]
+fn slice(bytes: &[u8], offset: u64, len: u64) -> Option<&[u8]> {
+    let offset = usize::try_from(offset).ok()?;
+    let len = usize::try_from(len).ok()?;
+
+    bytes.get(offset..offset.checked_add(len)?)
+}
+
+/// Check the start of an ELF file is a header we can read.
+fn check_header(bytes: &[u8]) -> Result<(), ElfError> {
+    if bytes.len() < 4 || bytes[..4] != MAGIC {
+        return Err(ElfError::Magic);
+    }
+
+    if bytes.len() < HEADER_SIZE {
+        return Err(ElfError::Truncated);
+    }
+
+    if bytes[4] != CLASS_64 || bytes[5] != DATA_LE {
+        return Err(ElfError::Unsupported);
+    }
+
+    Ok(())
+}
+
+/// Offset and length of a table of `count` entries, which must be `expected` bytes each.
+fn table(
+    offset: u64,
+    entry_size: u16,
+    count: u16,
+    expected: usize,
+) -> Result<(u64, u64), ElfError> {
+    // an empty table may have any offset
+    if count == 0 {
+        return Ok((0, 0));
+    }
+
+    if entry_size as usize != expected {
+        return Err(ElfError::Unsupported);
+    }
+
+    Ok((offset, entry_size as u64 * count as u64))
+}
+
+/// Offset and length of the program header table, from a checked header.
+#[inline]
+fn program_header_table(header: &[u8]) -> Result<(u64, u64), ElfError> {
+    table(
+        u64_at(header, 32),
+        u16_at(header, 54),
+        u16_at(header, 56),
+        PROGRAM_HEADER_SIZE,
+    )
+}
+
+/// Offset and length of the section header table, from a checked header.
+#[inline]
+fn section_header_table(header: &[u8]) -> Result<(u64, u64), ElfError> {
+    table(
+        u64_at(header, 40),
+        u16_at(header, 58),
+        u16_at(header, 60),
+        SECTION_HEADER_SIZE,
+    )
+}
+
+/// Index of the section name string table, from a checked header.
+#[inline]
+fn section_names_index(header: &[u8]) -> usize {
+    u16_at(header, 62) as usize
+}
+
+/// A program header, describing a segment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ProgramHeader {
+    pub kind: u32,
+    pub flags: u32,
+    pub offset: u64,
+    pub address: u64,
+    pub file_size: u64,
+    pub memory_size: u64,
+    pub align: u64,
+}
+
+impl ProgramHeader {
+    fn parse(bytes: &[u8]) -> Self {
+        Self {
+            kind: u32_at(bytes, 0),
+            flags: u32_at(bytes, 4),
+            offset: u64_at(bytes, 8),
+            address: u64_at(bytes, 16),
+            file_size: u64_at(bytes, 32),
+            memory_size: u64_at(bytes, 40),
+            align: u64_at(bytes, 48),
+        }
+    }
+
+    /// Whether this segment is loaded into memory.
+    #[inline]
+    pub const fn is_load(&self) -> bool {
+        self.kind == PT_LOAD
+    }
+
+    #[inline]
+    pub const fn is_readable(&self) -> bool {
+        self.flags & PF_R != 0
+    }
+
+    #[inline]
+    pub const fn is_writable(&self) -> bool {
+        self.flags & PF_W != 0
+    }
+
+    #[inline]
+    pub const fn is_executable(&self) -> bool {
+        self.flags & PF_X != 0
+    }
+}
+
+/// A section header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SectionHeader {
+    /// Offset of the name within the section name string table.
+    pub name: u32,
+    pub kind: u32,
+    pub flags: u64,
+    pub address: u64,
+    pub offset: u64,
+    pub size: u64,
+    pub link: u32,
+    pub info: u32,
+    pub align: u64,
+    pub entry_size: u64,
+}
+
+impl SectionHeader {
+    fn parse(bytes: &[u8]) -> Self {
+        Self {
+            name: u32_at(bytes, 0),
+            kind: u32_at(bytes, 4),
+            flags: u64_at(bytes, 8),
+            address: u64_at(bytes, 16),
+            offset: u64_at(bytes, 24),
+            size: u64_at(bytes, 32),
+            link: u32_at(bytes, 40),
+            info: u32_at(bytes, 44),
+            align: u64_at(bytes, 48),
+            entry_size: u64_at(bytes, 56),
+        }
+    }
+
+    /// Whether this section is loaded into memory.
+    #[inline]
+    pub const fn is_alloc(&self) -> bool {
+        self.flags & SHF_ALLOC != 0
+    }
+
+    #[inline]
+    pub const fn is_writable(&self) -> bool {
+        self.flags & SHF_WRITE != 0
+    }
+
+    #[inline]
+    pub const fn is_executable(&self) -> bool {
+        self.flags & SHF_EXECINSTR != 0
+    }
+}
+
+/// A section, and it's name.
+#[derive(Clone, Copy, Debug)]
+pub struct Section<'a> {
+    pub name: &'a str,
+    pub header: SectionHeader,
+}
+
+/// An ELF file.
+#[derive(Clone, Copy)]
+pub struct Elf<'a> {
+    bytes: &'a [u8],
+    program_headers: &'a [u8],
+    section_headers: &'a [u8],
+    section_names: Option<&'a [u8]>,
+}
+
+impl<'a> Elf<'a> {
+    /// Read the headers of an ELF file.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, ElfError> {
+        check_header(bytes)?;
+
+        let read = |(offset, len)| slice(bytes, offset, len).ok_or(ElfError::Truncated);
+        let program_headers = read(program_header_table(bytes)?)?;
+        let section_headers = read(section_header_table(bytes)?)?;
+
+        let mut elf = Self {
+            bytes,
+            program_headers,
+            section_headers,
+            section_names: None,
+        };
+
+        if let Some(header) = elf.section_headers().nth(section_names_index(bytes)) {
+            elf.section_names = Some(elf.section_data(&header).ok_or(ElfError::Truncated)?);
+        }
+
+        Ok(elf)
+    }
+
+    /// The bytes of the file.
+    #[inline]
+    pub const fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Iterate the program headers.
+    #[inline]
+    pub fn program_headers(&self) -> impl Iterator<Item = ProgramHeader> + 'a {
+        self.program_headers
+            .chunks_exact(PROGRAM_HEADER_SIZE)
+            .map(ProgramHeader::parse)
+    }
+
+    /// Iterate the section headers.
+    #[inline]
+    pub fn section_headers(&self) -> impl Iterator<Item = SectionHeader> + 'a {
+        self.section_headers
+            .chunks_exact(SECTION_HEADER_SIZE)
+            .map(SectionHeader::parse)
+    }
+
+    /// Iterate the sections, with their names.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'a>> + 'a {
+        let this = *self;
+
+        self.section_headers().map(move |header| Section {
+            name: this.section_name(&header).unwrap_or(""),
+            header,
+        })
+    }
+
+    /// Find a section by name.
+    #[inline]
+    pub fn section(&self, name: &str) -> Option<Section<'a>> {
+        self.sections().find(|section| section.name == name)
+    }
+
+    /// The name of a section.
+    pub fn section_name(&self, header: &SectionHeader) -> Option<&'a str> {
+        string(self.section_names?, header.name)
+    }
+
+    /// The bytes of a segment within the file, excluding what's only in memory.
+    #[inline]
+    pub fn segment_data(&self, header: &ProgramHeader) -> Option<&'a [u8]> {
+        slice(self.bytes, header.offset, header.file_size)
+    }
+
+    /// The bytes of a section within the file, empty for `SHT_NOBITS`.
+    #[inline]
+    pub fn section_data(&self, header: &SectionHeader) -> Option<&'a [u8]> {
+        if header.kind == SHT_NOBITS {
+            return Some(&[]);
+        }
+
+        slice(self.bytes, header.offset, header.size)
+    }
+
+    /// The GNU build-id, from the first note that has one.
+    pub fn build_id(&self) -> Option<&'a [u8]> {
+        let from_segments = self
+            .program_headers()
+            .filter(|header| header.kind == PT_NOTE)
+            .filter_map(|header| build_id(self.segment_data(&header)?));
+
+        let from_sections = self
+            .section_headers()
+            .filter(|header| header.kind == SHT_NOTE)
+            .filter_map(|header| build_id(self.section_data(&header)?));
+
+        from_segments.chain(from_sections).next()
+    }
+}
+
+impl<'a> fmt::Debug for Elf<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Elf")
+            .field(
+                "program_headers",
+                &self.program_headers().collect::<Vec<_>>(),
+            )
+            .field("sections", &self.sections().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The nul terminated string at `offset` within a string table.
+pub fn string(table: &[u8], offset: u32) -> Option<&str> {
+    let bytes = table.get(offset as usize..)?;
+    let len = bytes.iter().position(|byte| *byte == 0)?;
+
+    std::str::from_utf8(&bytes[..len]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let bytes = Builder::new()
+            .segment(".text", PF_R | PF_X, 0x1000, &[0x55, 0xC3])
+            .segment(".rodata", PF_R, 0x2000, b"hello")
+            .build_id(&[0xDE, 0xAD, 0xBE, 0xEF])
+            .build();
+
+        let elf = Elf::parse(&bytes).unwrap();
+        let loads: Vec<_> = elf.program_headers().filter(|ph| ph.is_load()).collect();
+
+        assert_eq!(loads.len(), 2);
+        assert!(loads[0].is_executable());
+        assert_eq!(loads[0].address, 0x1000);
+        assert_eq!(elf.segment_data(&loads[0]), Some(&[0x55, 0xC3][..]));
+        assert!(!loads[1].is_executable());
+
+        let text = elf.section(".text").unwrap();
+
+        assert!(text.header.is_executable());
+        assert_eq!(text.header.address, 0x1000);
+        assert_eq!(
+            elf.section_data(&elf.section(".rodata").unwrap().header),
+            Some(&b"hello"[..])
+        );
+
+        assert_eq!(elf.build_id(), Some(&[0xDE, 0xAD, 0xBE, 0xEF][..]));
+    }
+
+    #[test]
+    fn symbols() {
+        let bytes = Builder::new()
+            .segment(".text", PF_R | PF_X, 0x1000, &[0xC3; 16])
+            .symbol("hidden", 0x1000, 4)
+            .dynamic_symbol("create_move", 0x1004, 8, Some("ELYSIUM_1.0"))
+            .dynamic_symbol("unversioned", 0x100C, 4, None)
+            .build();
+
+        let elf = Elf::parse(&bytes).unwrap();
+        let symtab: Vec<_> = elf.symbols(SHT_SYMTAB).unwrap().collect();
+
+        assert_eq!(symtab.len(), 2);
+        assert!(!symtab[0].is_defined());
+        assert_eq!(symtab[1].name, "hidden");
+        assert_eq!(symtab[1].kind(), STT_FUNC);
+        assert_eq!(symtab[1].bind(), STB_GLOBAL);
+        assert_eq!((symtab[1].value, symtab[1].size), (0x1000, 4));
+
+        let dynsym: Vec<_> = elf.symbols(SHT_DYNSYM).unwrap().collect();
+        let versions = elf.versions().unwrap();
+
+        assert_eq!(dynsym[1].name, "create_move");
+        assert_eq!(versions.get(1), Some("ELYSIUM_1.0"));
+        assert_eq!(dynsym[2].name, "unversioned");
+        assert_eq!(versions.get(2), None);
+
+        let bytes = Builder::new().segment(".text", PF_X, 0, &[0xC3]).build();
+
+        assert!(Elf::parse(&bytes).unwrap().symbols(SHT_SYMTAB).is_none());
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = Builder::new().segment(".text", PF_X, 0, &[0xC3]).build();
+
+        assert_eq!(Elf::parse(b"MZ").unwrap_err(), ElfError::Magic);
+        assert_eq!(Elf::parse(&bytes[..32]).unwrap_err(), ElfError::Truncated);
+        assert_eq!(Elf::parse(&bytes[..80]).unwrap_err(), ElfError::Truncated);
+
+        let mut bytes = bytes;
+
+        bytes[4] = 1;
+
+        assert_eq!(Elf::parse(&bytes).unwrap_err(), ElfError::Unsupported);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::u32_at;
+
+/// Note type of the GNU build-id.
+pub const NT_GNU_BUILD_ID: u32 = 3;
+
+/// A note, from a `PT_NOTE` segment or `SHT_NOTE` section.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Note<'a> {
+    /// Owner, without the nul terminator.
+    pub name: &'a [u8],
+    pub kind: u32,
+    pub desc: &'a [u8],
+}
+
+/// Iterator over notes, see `notes`.
+pub struct Notes<'a> {
+    bytes: &'a [u8],
+}
+
+/// Iterate the notes within a note segment or section.
+#[inline]
+pub fn notes(bytes: &[u8]) -> Notes<'_> {
+    Notes { bytes }
+}
+
+#[inline]
+const fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+impl<'a> Iterator for Notes<'a> {
+    type Item = Note<'a>;
+
+    fn next(&mut self) -> Option<Note<'a>> {
+        let bytes = self.bytes;
+
+        if bytes.len() < 12 {
+            return None;
+        }
+
+        let name_len = u32_at(bytes, 0) as usize;
+        let desc_len = u32_at(bytes, 4) as usize;
+        let kind = u32_at(bytes, 8);
+
+        let desc_start = 12 + align(name_len);
+        let end = desc_start + align(desc_len);
+
+        let name = bytes.get(12..12 + name_len)?;
+        let desc = bytes.get(desc_start..desc_start + desc_len)?;
+
+        // strip the nul terminator
+        let name = name.strip_suffix(&[0]).unwrap_or(name);
+
+        self.bytes = bytes.get(end..).unwrap_or(&[]);
+
+        Some(Note { name, kind, desc })
+    }
+}
+
+/// The GNU build-id within some notes.
+pub fn build_id(bytes: &[u8]) -> Option<&[u8]> {
+    notes(bytes)
+        .find(|note| note.kind == NT_GNU_BUILD_ID && note.name == b"GNU")
+        .map(|note| note.desc)
+}
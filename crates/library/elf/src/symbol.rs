@@ -0,0 +1,208 @@
+use crate::{string, u16_at, u32_at, u64_at, Elf, SectionHeader};
+use std::collections::HashMap;
+
+/// Symbol version definitions.
+pub const SHT_GNU_VERDEF: u32 = 0x6FFF_FFFD;
+/// Symbol versions required from other libraries.
+pub const SHT_GNU_VERNEED: u32 = 0x6FFF_FFFE;
+/// Version of each dynamic symbol.
+pub const SHT_GNU_VERSYM: u32 = 0x6FFF_FFFF;
+
+/// Symbol is a data object.
+pub const STT_OBJECT: u8 = 1;
+/// Symbol is a function.
+pub const STT_FUNC: u8 = 2;
+/// Symbol is a function, which returns the implementation to use, such as `memcpy`.
+pub const STT_GNU_IFUNC: u8 = 10;
+
+/// Symbol is only visible within it's file.
+pub const STB_LOCAL: u8 = 0;
+/// Symbol is visible to everything.
+pub const STB_GLOBAL: u8 = 1;
+/// Symbol is global, but may be overridden.
+pub const STB_WEAK: u8 = 2;
+
+/// Symbol isn't defined in this file.
+pub const SHN_UNDEF: u16 = 0;
+
+const SYMBOL_SIZE: usize = 24;
+
+/// Versions with the hidden bit, only the default version of a symbol may be linked against.
+const VERSYM_HIDDEN: u16 = 0x8000;
+
+/// Version definition of the library itself, rather than a version of it's symbols.
+const VER_FLG_BASE: u16 = 1;
+
+/// A symbol.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Symbol<'a> {
+    pub name: &'a str,
+    pub info: u8,
+    pub other: u8,
+    /// Index of the section this symbol is within.
+    pub section: u16,
+    pub value: u64,
+    pub size: u64,
+}
+
+impl<'a> Symbol<'a> {
+    fn parse(bytes: &[u8], names: &'a [u8]) -> Self {
+        Self {
+            name: string(names, u32_at(bytes, 0)).unwrap_or(""),
+            info: bytes[4],
+            other: bytes[5],
+            section: u16_at(bytes, 6),
+            value: u64_at(bytes, 8),
+            size: u64_at(bytes, 16),
+        }
+    }
+
+    /// `STT_*`
+    #[inline]
+    pub const fn kind(&self) -> u8 {
+        self.info & 0xF
+    }
+
+    /// `STB_*`
+    #[inline]
+    pub const fn bind(&self) -> u8 {
+        self.info >> 4
+    }
+
+    /// Whether this symbol is defined within this file, rather than imported.
+    #[inline]
+    pub const fn is_defined(&self) -> bool {
+        self.section != SHN_UNDEF
+    }
+}
+
+/// Iterator over a symbol table, see `Elf::symbols`.
+#[derive(Clone)]
+pub struct Symbols<'a> {
+    entries: std::slice::ChunksExact<'a, u8>,
+    names: &'a [u8],
+}
+
+impl<'a> Iterator for Symbols<'a> {
+    type Item = Symbol<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Symbol<'a>> {
+        let entry = self.entries.next()?;
+
+        Some(Symbol::parse(entry, self.names))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+/// Versions of dynamic symbols, from `.gnu.version`, `.gnu.version_d` and `.gnu.version_r`.
+#[derive(Clone, Debug, Default)]
+pub struct Versions<'a> {
+    /// Version index of each dynamic symbol.
+    indices: &'a [u8],
+    names: HashMap<u16, &'a str>,
+}
+
+impl<'a> Versions<'a> {
+    /// Version of the dynamic symbol at `index`, `None` for unversioned symbols.
+    pub fn get(&self, index: usize) -> Option<&'a str> {
+        let offset = index.checked_mul(2)?;
+        let version = self.indices.get(offset..offset + 2)?;
+        let version = u16::from_le_bytes([version[0], version[1]]) & !VERSYM_HIDDEN;
+
+        self.names.get(&version).copied()
+    }
+}
+
+impl<'a> Elf<'a> {
+    /// The first section of `kind`.
+    fn section_of_kind(&self, kind: u32) -> Option<SectionHeader> {
+        self.section_headers().find(|header| header.kind == kind)
+    }
+
+    /// The data of the section a section links to, such as a symbol table's string table.
+    fn linked_data(&self, header: &SectionHeader) -> Option<&'a [u8]> {
+        let linked = self.section_headers().nth(header.link as usize)?;
+
+        self.section_data(&linked)
+    }
+
+    /// Iterate the symbol table of `kind`, `SHT_SYMTAB` or `SHT_DYNSYM`, if there is one.
+    pub fn symbols(&self, kind: u32) -> Option<Symbols<'a>> {
+        let header = self.section_of_kind(kind)?;
+        let bytes = self.section_data(&header)?;
+        let names = self.linked_data(&header)?;
+
+        Some(Symbols {
+            entries: bytes.chunks_exact(SYMBOL_SIZE),
+            names,
+        })
+    }
+
+    /// Versions of the dynamic symbols, if there are any.
+    pub fn versions(&self) -> Option<Versions<'a>> {
+        let header = self.section_of_kind(SHT_GNU_VERSYM)?;
+        let indices = self.section_data(&header)?;
+        let mut names = HashMap::new();
+
+        // versions defined by this library
+        if let Some(header) = self.section_of_kind(SHT_GNU_VERDEF) {
+            let bytes = self.section_data(&header)?;
+            let strings = self.linked_data(&header)?;
+            let mut offset = 0;
+
+            for _ in 0..header.info {
+                let entry = bytes.get(offset..offset + 20)?;
+                let flags = u16_at(entry, 2);
+                let index = u16_at(entry, 4);
+                let aux = offset + u32_at(entry, 12) as usize;
+                let name = string(strings, u32_at(bytes.get(aux..aux + 8)?, 0))?;
+
+                if flags & VER_FLG_BASE == 0 {
+                    names.insert(index, name);
+                }
+
+                match u32_at(entry, 16) {
+                    0 => break,
+                    next => offset += next as usize,
+                }
+            }
+        }
+
+        // versions required from other libraries
+        if let Some(header) = self.section_of_kind(SHT_GNU_VERNEED) {
+            let bytes = self.section_data(&header)?;
+            let strings = self.linked_data(&header)?;
+            let mut offset = 0;
+
+            for _ in 0..header.info {
+                let entry = bytes.get(offset..offset + 16)?;
+                let mut aux = offset + u32_at(entry, 8) as usize;
+
+                for _ in 0..u16_at(entry, 2) {
+                    let entry = bytes.get(aux..aux + 16)?;
+                    let index = u16_at(entry, 6);
+                    let name = string(strings, u32_at(entry, 8))?;
+
+                    names.insert(index, name);
+
+                    match u32_at(entry, 12) {
+                        0 => break,
+                        next => aux += next as usize,
+                    }
+                }
+
+                match u32_at(entry, 12) {
+                    0 => break,
+                    next => offset += next as usize,
+                }
+            }
+        }
+
+        Some(Versions { indices, names })
+    }
+}
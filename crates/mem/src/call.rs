@@ -0,0 +1,229 @@
+//! Redirecting calls.
+
+use crate::detour::{self, trampoline, JMP_ABSOLUTE};
+use crate::patch;
+use std::{fmt, io, ptr};
+
+/// `call rel32`
+const CALL: u8 = 0xE8;
+
+/// Length of `call rel32`.
+const CALL_LEN: usize = 5;
+
+/// Write the `rel32` of the call at `address` in one store, as another thread may be making the
+/// call.
+unsafe fn write_relative(address: *mut u8, relative: i32) -> io::Result<()> {
+    detour::write_atomic(address.add(1), &relative.to_le_bytes())?;
+
+    Ok(())
+}
+
+/// `target - next` as a `rel32`.
+#[inline]
+fn relative(target: usize, next: usize) -> Option<i32> {
+    i32::try_from((target as isize).wrapping_sub(next as isize)).ok()
+}
+
+/// A `call rel32` that's been redirected, which is restored when dropped.
+pub struct CallSite {
+    address: *mut u8,
+    /// What was called before.
+    original: *const u8,
+    redirected: bool,
+}
+
+unsafe impl Send for CallSite {}
+unsafe impl Sync for CallSite {}
+
+impl CallSite {
+    /// The function called by the `call rel32` at `address`.
+    ///
+    /// # Safety
+    ///
+    /// `address` must be readable, and so must the 4 bytes after it, if it's a call.
+    pub unsafe fn target_of(address: *const u8) -> io::Result<*const u8> {
+        if address.read() != CALL {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{address:?} isn't a `call rel32`"),
+            ));
+        }
+
+        let relative = address.add(1).cast::<i32>().read_unaligned();
+
+        Ok(address.add(CALL_LEN).wrapping_offset(relative as isize))
+    }
+
+    /// Redirect the `call rel32` at `address` to `function`.
+    ///
+    /// If `function` is out of reach, the call goes through a trampoline near `address`. Fails if
+    /// the `rel32` spans a 16 byte boundary, as it can't be written in one store.
+    ///
+    /// # Safety
+    ///
+    /// `address` must be the start of an instruction, and `function` must have the signature
+    /// of the function that's called, and remain callable until the call is restored.
+    pub unsafe fn redirect(address: *mut u8, function: *const u8) -> io::Result<Self> {
+        let original = Self::target_of(address)?;
+        let next = address.addr() + CALL_LEN;
+        let relative = match relative(function.addr(), next) {
+            Some(relative) => relative,
+            None => {
+                let trampoline = trampoline::allocate(address.addr())?;
+                let mut bytes = [0xCC; 14];
+
+                bytes[..6].copy_from_slice(&JMP_ABSOLUTE);
+                bytes[6..].copy_from_slice(&function.addr().to_le_bytes());
+
+                patch::with_writable(trampoline, bytes.len(), || {
+                    ptr::copy_nonoverlapping(bytes.as_ptr(), trampoline, bytes.len());
+                })?;
+
+                // the trampoline was allocated near the call
+                relative(trampoline.addr(), next).unwrap()
+            }
+        };
+
+        write_relative(address, relative)?;
+
+        Ok(Self {
+            address,
+            original,
+            redirected: true,
+        })
+    }
+
+    /// Where the call is.
+    #[inline]
+    pub fn address(&self) -> *const u8 {
+        self.address
+    }
+
+    /// The function that was called before.
+    #[inline]
+    pub fn original(&self) -> *const u8 {
+        self.original
+    }
+
+    /// Call the original function again.
+    pub fn restore(mut self) -> io::Result<()> {
+        unsafe { self.restore_in_place()? };
+
+        self.redirected = false;
+
+        Ok(())
+    }
+
+    unsafe fn restore_in_place(&self) -> io::Result<()> {
+        let relative = relative(self.original.addr(), self.address.addr() + CALL_LEN).unwrap();
+
+        write_relative(self.address, relative)
+    }
+}
+
+impl Drop for CallSite {
+    #[inline]
+    fn drop(&mut self) {
+        if self.redirected {
+            let _ = unsafe { self.restore_in_place() };
+        }
+    }
+}
+
+impl fmt::Debug for CallSite {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("CallSite")
+            .field("address", &self.address)
+            .field("original", &self.original)
+            .field("redirected", &self.redirected)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PAGE_SIZE;
+    use std::arch::global_asm;
+    use std::hint::black_box;
+
+    // the stack is kept aligned around the call
+    global_asm!(
+        ".pushsection .text",
+        // so the `rel32` doesn't span a 16 byte boundary
+        ".p2align 4",
+        ".globl elysium_mem_caller",
+        "elysium_mem_caller:",
+        "    push rax",
+        "elysium_mem_call:",
+        "    call elysium_mem_callee",
+        "    pop rcx",
+        "    ret",
+        "elysium_mem_callee:",
+        "    lea eax, [rdi + 1]",
+        "    ret",
+        ".popsection",
+    );
+
+    extern "C" {
+        fn elysium_mem_caller(value: i32) -> i32;
+    }
+
+    extern "C" fn double(value: i32) -> i32 {
+        black_box(value) * 2
+    }
+
+    fn call(value: i32) -> i32 {
+        unsafe { black_box(elysium_mem_caller as unsafe extern "C" fn(i32) -> i32)(value) }
+    }
+
+    #[test]
+    fn call_site() {
+        unsafe {
+            let address = (elysium_mem_caller as *const u8 as *mut u8).add(1);
+            let callee = CallSite::target_of(address).unwrap();
+
+            assert!(CallSite::target_of(address.sub(1)).is_err());
+            assert_eq!(call(10), 11);
+
+            let call_site = CallSite::redirect(address, double as *const u8).unwrap();
+
+            assert_eq!(call_site.original(), callee);
+            assert_eq!(CallSite::target_of(address).unwrap(), double as *const u8);
+            assert_eq!(call(10), 20);
+
+            call_site.restore().unwrap();
+
+            assert_eq!(call(10), 11);
+
+            // `lea eax, [rdi + rdi]; ret`, far enough away to need a trampoline
+            let far = libc::mmap(
+                address.wrapping_add(1 << 33).cast(),
+                PAGE_SIZE,
+                libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+            .cast::<u8>();
+
+            assert_ne!(far, libc::MAP_FAILED.cast());
+
+            far.copy_from_nonoverlapping([0x8D, 0x04, 0x3F, 0xC3].as_ptr(), 4);
+
+            {
+                let _call_site = CallSite::redirect(address, far).unwrap();
+
+                assert_ne!(CallSite::target_of(address).unwrap(), far as *const u8);
+                assert_eq!(call(10), 20);
+            }
+
+            // restored on drop
+            assert_eq!(CallSite::target_of(address).unwrap(), callee);
+            assert_eq!(call(10), 11);
+
+            libc::munmap(far.cast(), PAGE_SIZE);
+        }
+    }
+}
@@ -0,0 +1,499 @@
+//! x86-64 instruction length decoding.
+//!
+//! Only what's needed to step over instructions and find the operands that depend on where the
+//! instruction is located, operands themselves aren't decoded.
+
+/// Where an operand is within an instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Operand {
+    /// Offset from the start of the instruction.
+    pub offset: u8,
+    /// Size in bytes.
+    pub size: u8,
+}
+
+/// Opcode map an instruction belongs to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Map {
+    /// One byte opcodes.
+    Primary,
+    /// `0F xx`
+    Secondary,
+    /// `0F 38 xx`
+    Secondary38,
+    /// `0F 3A xx`
+    Secondary3A,
+}
+
+/// A decoded instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    /// Total length in bytes.
+    pub len: u8,
+    /// Opcode map.
+    pub map: Map,
+    /// Opcode byte, within `map`.
+    pub opcode: u8,
+    /// Offset of the opcode byte.
+    pub opcode_offset: u8,
+    /// ModRM byte, if present.
+    pub modrm: Option<u8>,
+    /// Memory displacement, if present.
+    pub displacement: Option<Operand>,
+    /// Whether the displacement is relative to the next instruction, `[rip + disp32]`.
+    pub rip_relative: bool,
+    /// Immediate, if present.
+    pub immediate: Option<Operand>,
+    /// Whether the immediate is a branch target relative to the next instruction.
+    pub branch: bool,
+}
+
+impl Instruction {
+    /// The operand that must be adjusted if this instruction is moved.
+    #[inline]
+    pub const fn relative(&self) -> Option<Operand> {
+        if self.rip_relative {
+            self.displacement
+        } else if self.branch {
+            self.immediate
+        } else {
+            None
+        }
+    }
+
+    /// Whether this instruction ends the current flow of execution, `ret`, `jmp` or `ud2`.
+    #[inline]
+    pub const fn is_terminator(&self) -> bool {
+        match (self.map, self.opcode) {
+            (Map::Primary, 0xC2 | 0xC3 | 0xCA | 0xCB | 0xCF | 0xE9 | 0xEB) => true,
+            // jmp r/m64
+            (Map::Primary, 0xFF) => match self.modrm {
+                Some(modrm) => matches!((modrm >> 3) & 7, 4 | 5),
+                None => false,
+            },
+            (Map::Secondary, 0x0B) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Immediate sizes.
+#[derive(Clone, Copy)]
+enum Imm {
+    None,
+    /// 8-bit.
+    B,
+    /// 16-bit.
+    W,
+    /// 16 or 32-bit, depending on the operand size prefix.
+    Z,
+    /// `enter`, 16-bit and 8-bit.
+    Enter,
+    /// 16, 32 or 64-bit, `mov r64, imm64`.
+    V,
+    /// Address sized memory offset, `mov al, [moffs]`.
+    Moffs,
+    /// `test` group, only present when ModRM.reg is 0 or 1.
+    Test(u8),
+}
+
+/// How an opcode is encoded.
+#[derive(Clone, Copy)]
+struct Encoding {
+    modrm: bool,
+    imm: Imm,
+    branch: bool,
+}
+
+const fn op(modrm: bool, imm: Imm) -> Option<Encoding> {
+    Some(Encoding {
+        modrm,
+        imm,
+        branch: false,
+    })
+}
+
+const fn rel(imm: Imm) -> Option<Encoding> {
+    Some(Encoding {
+        modrm: false,
+        imm,
+        branch: true,
+    })
+}
+
+/// `None` for prefixes, escapes and opcodes which are invalid in 64-bit mode.
+const fn primary(opcode: u8) -> Option<Encoding> {
+    match opcode {
+        // add, or, adc, sbb, and, sub, xor, cmp
+        0x00..=0x3F => match opcode & 7 {
+            0..=3 => op(true, Imm::None),
+            4 => op(false, Imm::B),
+            5 => op(false, Imm::Z),
+            _ => None,
+        },
+        0x50..=0x5F => op(false, Imm::None),
+        0x63 => op(true, Imm::None),
+        0x68 => op(false, Imm::Z),
+        0x69 => op(true, Imm::Z),
+        0x6A => op(false, Imm::B),
+        0x6B => op(true, Imm::B),
+        0x6C..=0x6F => op(false, Imm::None),
+        0x70..=0x7F => rel(Imm::B),
+        0x80 | 0x83 => op(true, Imm::B),
+        0x81 => op(true, Imm::Z),
+        0x84..=0x8F => op(true, Imm::None),
+        0x90..=0x99 | 0x9B..=0x9F => op(false, Imm::None),
+        0xA0..=0xA3 => op(false, Imm::Moffs),
+        0xA4..=0xA7 | 0xAA..=0xAF => op(false, Imm::None),
+        0xA8 => op(false, Imm::B),
+        0xA9 => op(false, Imm::Z),
+        0xB0..=0xB7 => op(false, Imm::B),
+        0xB8..=0xBF => op(false, Imm::V),
+        0xC0 | 0xC1 | 0xC6 => op(true, Imm::B),
+        0xC2 | 0xCA => op(false, Imm::W),
+        0xC3 | 0xC9 | 0xCB | 0xCC | 0xCF => op(false, Imm::None),
+        0xC7 => op(true, Imm::Z),
+        0xC8 => op(false, Imm::Enter),
+        0xCD => op(false, Imm::B),
+        0xD0..=0xD3 | 0xD8..=0xDF => op(true, Imm::None),
+        0xD7 => op(false, Imm::None),
+        0xE0..=0xE3 | 0xEB => rel(Imm::B),
+        0xE4..=0xE7 => op(false, Imm::B),
+        0xE8 | 0xE9 => rel(Imm::Z),
+        0xEC..=0xEF | 0xF1 | 0xF4 | 0xF5 | 0xF8..=0xFD => op(false, Imm::None),
+        0xF6 => op(true, Imm::Test(1)),
+        0xF7 => op(true, Imm::Test(4)),
+        0xFE | 0xFF => op(true, Imm::None),
+        _ => None,
+    }
+}
+
+const fn secondary(opcode: u8) -> Option<Encoding> {
+    match opcode {
+        0x00..=0x03 | 0x0D | 0x10..=0x1F | 0x20..=0x23 | 0x28..=0x2F => op(true, Imm::None),
+        0x05..=0x09 | 0x0B | 0x0E | 0x30..=0x37 | 0x77 | 0xA0..=0xA2 | 0xA8..=0xAA => {
+            op(false, Imm::None)
+        }
+        0xC8..=0xCF => op(false, Imm::None),
+        // 3dnow!
+        0x0F => op(true, Imm::B),
+        0x40..=0x6F | 0x74..=0x76 | 0x78 | 0x79 | 0x7C..=0x7F => op(true, Imm::None),
+        0x70..=0x73 | 0xA4 | 0xAC | 0xBA | 0xC2 | 0xC4..=0xC6 => op(true, Imm::B),
+        0x80..=0x8F => rel(Imm::Z),
+        0x90..=0x9F | 0xA3 | 0xA5 | 0xAB | 0xAD..=0xB9 | 0xBB..=0xC1 | 0xC3 | 0xC7 => {
+            op(true, Imm::None)
+        }
+        0xD0..=0xFF => op(true, Imm::None),
+        _ => None,
+    }
+}
+
+const fn secondary_38(_opcode: u8) -> Option<Encoding> {
+    op(true, Imm::None)
+}
+
+const fn secondary_3a(_opcode: u8) -> Option<Encoding> {
+    op(true, Imm::B)
+}
+
+const fn encoding(map: Map, opcode: u8) -> Option<Encoding> {
+    match map {
+        Map::Primary => primary(opcode),
+        Map::Secondary => secondary(opcode),
+        Map::Secondary38 => secondary_38(opcode),
+        Map::Secondary3A => secondary_3a(opcode),
+    }
+}
+
+const fn is_legacy_prefix(byte: u8) -> bool {
+    matches!(
+        byte,
+        0x26 | 0x2E | 0x36 | 0x3E | 0x64 | 0x65 | 0x66 | 0x67 | 0xF0 | 0xF2 | 0xF3
+    )
+}
+
+/// Decode the instruction at the start of `code`, `None` if it's invalid or truncated.
+pub fn decode(code: &[u8]) -> Option<Instruction> {
+    let mut index = 0;
+    let mut operand_size_override = false;
+    let mut address_size_override = false;
+    let mut rex_w = false;
+
+    while is_legacy_prefix(*code.get(index)?) {
+        match code[index] {
+            0x66 => operand_size_override = true,
+            0x67 => address_size_override = true,
+            _ => {}
+        }
+
+        index += 1;
+
+        // instructions are at most 15 bytes long
+        if index >= 15 {
+            return None;
+        }
+    }
+
+    let byte = *code.get(index)?;
+
+    // rex must directly precede the opcode
+    if byte & 0xF0 == 0x40 {
+        rex_w = byte & 0x08 != 0;
+        index += 1;
+    }
+
+    let byte = *code.get(index)?;
+
+    let map = match byte {
+        // vex, three and two byte forms
+        0xC4 => {
+            let map = match *code.get(index + 1)? & 0x1F {
+                1 => Map::Secondary,
+                2 => Map::Secondary38,
+                3 => Map::Secondary3A,
+                _ => return None,
+            };
+
+            rex_w = *code.get(index + 2)? & 0x80 != 0;
+            index += 3;
+            map
+        }
+        0xC5 => {
+            index += 2;
+            Map::Secondary
+        }
+        // evex
+        0x62 => {
+            let map = match *code.get(index + 1)? & 0x03 {
+                1 => Map::Secondary,
+                2 => Map::Secondary38,
+                3 => Map::Secondary3A,
+                _ => return None,
+            };
+
+            rex_w = *code.get(index + 2)? & 0x80 != 0;
+            index += 4;
+            map
+        }
+        0x0F => match *code.get(index + 1)? {
+            0x38 => {
+                index += 2;
+                Map::Secondary38
+            }
+            0x3A => {
+                index += 2;
+                Map::Secondary3A
+            }
+            _ => {
+                index += 1;
+                Map::Secondary
+            }
+        },
+        _ => Map::Primary,
+    };
+
+    let opcode_offset = index;
+    let opcode = *code.get(index)?;
+    let encoding = encoding(map, opcode)?;
+
+    index += 1;
+
+    let mut modrm = None;
+    let mut displacement = None;
+    let mut rip_relative = false;
+
+    if encoding.modrm {
+        let byte = *code.get(index)?;
+        let mode = byte >> 6;
+        let rm = byte & 7;
+
+        modrm = Some(byte);
+        index += 1;
+
+        let mut displacement_size = match mode {
+            1 => 1,
+            2 => 4,
+            _ => 0,
+        };
+
+        if mode != 3 && rm == 4 {
+            let sib = *code.get(index)?;
+
+            index += 1;
+
+            // no base register
+            if mode == 0 && sib & 7 == 5 {
+                displacement_size = 4;
+            }
+        } else if mode == 0 && rm == 5 {
+            displacement_size = 4;
+            rip_relative = true;
+        }
+
+        if displacement_size != 0 {
+            displacement = Some(Operand {
+                offset: index as u8,
+                size: displacement_size,
+            });
+
+            index += displacement_size as usize;
+        }
+    }
+
+    let immediate_size = match encoding.imm {
+        Imm::None => 0,
+        Imm::B => 1,
+        Imm::W => 2,
+        // near branches are always rel32 in 64-bit mode
+        Imm::Z if encoding.branch => 4,
+        Imm::Z if operand_size_override => 2,
+        Imm::Z => 4,
+        Imm::Enter => 3,
+        Imm::V if rex_w => 8,
+        Imm::V if operand_size_override => 2,
+        Imm::V => 4,
+        Imm::Moffs if address_size_override => 4,
+        Imm::Moffs => 8,
+        Imm::Test(size) => match modrm {
+            Some(modrm) if (modrm >> 3) & 7 < 2 => {
+                if size == 4 && operand_size_override {
+                    2
+                } else {
+                    size
+                }
+            }
+            _ => 0,
+        },
+    };
+
+    let immediate = if immediate_size != 0 {
+        Some(Operand {
+            offset: index as u8,
+            size: immediate_size,
+        })
+    } else {
+        None
+    };
+
+    index += immediate_size as usize;
+
+    if index > 15 || index > code.len() {
+        return None;
+    }
+
+    Some(Instruction {
+        len: index as u8,
+        map,
+        opcode,
+        opcode_offset: opcode_offset as u8,
+        modrm,
+        displacement,
+        rip_relative,
+        immediate,
+        branch: encoding.branch,
+    })
+}
+
+/// Iterate the instructions in `code`, stopping at the first that can't be decoded.
+#[inline]
+pub fn instructions(code: &[u8]) -> Instructions<'_> {
+    Instructions { code, offset: 0 }
+}
+
+/// Iterator over instructions and their offsets, see `instructions`.
+pub struct Instructions<'a> {
+    code: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = (usize, Instruction);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offset;
+        let instruction = decode(self.code.get(offset..)?)?;
+
+        self.offset += instruction.len as usize;
+
+        Some((offset, instruction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn len(code: &[u8]) -> usize {
+        decode(code)
+            .map(|instruction| instruction.len as usize)
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn lengths() {
+        // push rbp
+        assert_eq!(len(&[0x55]), 1);
+        // mov rbp, rsp
+        assert_eq!(len(&[0x48, 0x89, 0xE5]), 3);
+        // sub rsp, 0x198
+        assert_eq!(len(&[0x48, 0x81, 0xEC, 0x98, 0x01, 0x00, 0x00]), 7);
+        // movss [rbp - 0x1a4], xmm0
+        assert_eq!(len(&[0xF3, 0x0F, 0x11, 0x85, 0x5C, 0xFE, 0xFF, 0xFF]), 8);
+        // mov rbx, [rip + disp32]
+        assert_eq!(len(&[0x48, 0x8B, 0x1D, 0xC8, 0x25, 0x94, 0x00]), 7);
+        // mov r8d, [rbx + 0x100c]
+        assert_eq!(len(&[0x44, 0x8B, 0x83, 0x0C, 0x10, 0x00, 0x00]), 7);
+        // movq r15, xmm0
+        assert_eq!(len(&[0x66, 0x41, 0x0F, 0x7E, 0xC7]), 5);
+        // mov eax, [rsp + 8]
+        assert_eq!(len(&[0x8B, 0x44, 0x24, 0x08]), 4);
+        // mov rax, imm64
+        assert_eq!(len(&[0x48, 0xB8, 1, 2, 3, 4, 5, 6, 7, 8]), 10);
+        // test byte [rdi], 1
+        assert_eq!(len(&[0xF6, 0x07, 0x01]), 3);
+        // not dword [rdi]
+        assert_eq!(len(&[0xF7, 0x17]), 2);
+        // vmovups ymm0, [rdi]
+        assert_eq!(len(&[0xC5, 0xFC, 0x10, 0x07]), 4);
+        // pshufd xmm0, xmm1, 0
+        assert_eq!(len(&[0x66, 0x0F, 0x70, 0xC1, 0x00]), 5);
+        // truncated
+        assert_eq!(len(&[0x48, 0x81, 0xEC, 0x98]), 0);
+    }
+
+    #[test]
+    fn relative() {
+        // call rel32
+        let call = decode(&[0xE8, 0x01, 0x02, 0x03, 0x04]).unwrap();
+
+        assert_eq!(call.relative(), Some(Operand { offset: 1, size: 4 }));
+
+        // jne rel32
+        let jne = decode(&[0x0F, 0x85, 0x01, 0x02, 0x03, 0x04]).unwrap();
+
+        assert_eq!(jne.relative(), Some(Operand { offset: 2, size: 4 }));
+
+        // lea r14, [rip + disp32]
+        let lea = decode(&[0x4C, 0x8D, 0x35, 0xB1, 0x19, 0x17, 0x02]).unwrap();
+
+        assert!(lea.rip_relative);
+        assert_eq!(lea.relative(), Some(Operand { offset: 3, size: 4 }));
+
+        // cmp dword [rip + disp32], imm8
+        let cmp = decode(&[0x83, 0x3D, 0x01, 0x02, 0x03, 0x04, 0x05]).unwrap();
+
+        assert_eq!(cmp.relative(), Some(Operand { offset: 2, size: 4 }));
+        assert_eq!(cmp.immediate, Some(Operand { offset: 6, size: 1 }));
+
+        // add rax, rbx
+        assert_eq!(decode(&[0x48, 0x01, 0xD8]).unwrap().relative(), None);
+    }
+
+    #[test]
+    fn terminators() {
+        assert!(decode(&[0xC3]).unwrap().is_terminator());
+        assert!(decode(&[0xFF, 0xE0]).unwrap().is_terminator());
+        assert!(!decode(&[0xFF, 0xD0]).unwrap().is_terminator());
+    }
+}
@@ -0,0 +1,503 @@
+//! Inline function hooks.
+//!
+//! The start of the target is replaced with a `jmp rel32` to a trampoline, which jumps to the
+//! detour. The instructions that were replaced are relocated to after it, followed by a jump
+//! back to the rest of the target, so calling the trampoline calls the original function.
+//!
+//! ```text
+//! target:      jmp trampoline        trampoline:  jmp [rip] ; detour
+//!              <rest of target>      original:    <relocated instructions>
+//!                                                 jmp target + len
+//! ```
+
+use crate::decode::{self, Map};
+use crate::patch;
+use std::arch::asm;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{fmt, io, ptr};
+
+pub(crate) mod trampoline;
+
+/// Length of `jmp rel32`.
+const JMP_LEN: usize = 5;
+
+/// `jmp [rip]`, followed by the absolute address.
+pub(crate) const JMP_ABSOLUTE: [u8; 6] = [0xFF, 0x25, 0x00, 0x00, 0x00, 0x00];
+
+/// Offset of the relocated instructions within a trampoline.
+const ORIGINAL_OFFSET: usize = 16;
+
+/// Most bytes that may be replaced, a jump that ends partway into the longest instruction.
+const MAX_DISPLACED: usize = JMP_LEN - 1 + 15;
+
+/// Why a detour couldn't be installed, or uninstalled.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DetourError {
+    /// Memory couldn't be allocated, or it's protection changed.
+    Io(io::Error),
+    /// The instruction at `offset` couldn't be decoded.
+    Undecodable { offset: usize },
+    /// The target returns, or jumps away, before there's room for a jump.
+    TooShort,
+    /// The instruction at `offset` can't be relocated, such as `loop`, or a branch to within the
+    /// replaced instructions.
+    Unsupported { offset: usize },
+    /// What the instruction at `offset` refers to is out of reach of the trampoline.
+    OutOfRange { offset: usize },
+    /// The jump spans 16 byte boundary, so it can't be written atomically.
+    Unaligned,
+}
+
+impl fmt::Display for DetourError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DetourError::Io(error) => write!(fmt, "{error}"),
+            DetourError::Undecodable { offset } => {
+                write!(fmt, "undecodable instruction at offset {offset}")
+            }
+            DetourError::TooShort => fmt.write_str("function is too short to detour"),
+            DetourError::Unsupported { offset } => {
+                write!(fmt, "instruction at offset {offset} can't be relocated")
+            }
+            DetourError::OutOfRange { offset } => write!(
+                fmt,
+                "instruction at offset {offset} is out of range once relocated"
+            ),
+            DetourError::Unaligned => fmt.write_str("jump can't be written atomically"),
+        }
+    }
+}
+
+impl std::error::Error for DetourError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DetourError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DetourError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        DetourError::Io(error)
+    }
+}
+
+impl From<DetourError> for io::Error {
+    #[inline]
+    fn from(error: DetourError) -> Self {
+        match error {
+            DetourError::Io(error) => error,
+            error => io::Error::new(io::ErrorKind::InvalidInput, error),
+        }
+    }
+}
+
+/// `target - next` as a `rel32`.
+#[inline]
+fn relative(target: usize, next: usize) -> Option<i32> {
+    i32::try_from((target as isize).wrapping_sub(next as isize)).ok()
+}
+
+/// How many bytes of `code` must be replaced to fit a jump.
+fn displaced_len(code: &[u8]) -> Result<usize, DetourError> {
+    let mut len = 0;
+
+    while len < JMP_LEN {
+        let instruction =
+            decode::decode(&code[len..]).ok_or(DetourError::Undecodable { offset: len })?;
+
+        len += instruction.len as usize;
+
+        if instruction.is_terminator() && len < JMP_LEN {
+            return Err(DetourError::TooShort);
+        }
+    }
+
+    Ok(len)
+}
+
+/// Relocate the first `len` bytes of `code`, from `source` to `destination`, followed by a jump
+/// back to `source + len`.
+pub(crate) fn relocate(
+    code: &[u8],
+    len: usize,
+    source: usize,
+    destination: usize,
+) -> Result<Vec<u8>, DetourError> {
+    let mut relocated = Vec::new();
+    let mut offset = 0;
+
+    while offset < len {
+        let instruction =
+            decode::decode(&code[offset..]).ok_or(DetourError::Undecodable { offset })?;
+
+        let bytes = &code[offset..offset + instruction.len as usize];
+        let operand = match instruction.relative() {
+            Some(operand) => operand,
+            None => {
+                relocated.extend_from_slice(bytes);
+                offset += bytes.len();
+
+                continue;
+            }
+        };
+
+        let start = operand.offset as usize;
+        let end = start + operand.size as usize;
+        let value = match operand.size {
+            1 => bytes[start] as i8 as isize,
+            4 => i32::from_le_bytes(bytes[start..end].try_into().unwrap()) as isize,
+            _ => return Err(DetourError::Unsupported { offset }),
+        };
+
+        let target = (source + offset + bytes.len()).wrapping_add_signed(value);
+
+        // branches to within what's replaced would land on the jump
+        if instruction.branch && target > source && target < source + len {
+            return Err(DetourError::Unsupported { offset });
+        }
+
+        let here = destination + relocated.len();
+
+        if operand.size == 4 {
+            let value =
+                relative(target, here + bytes.len()).ok_or(DetourError::OutOfRange { offset })?;
+
+            relocated.extend_from_slice(&bytes[..start]);
+            relocated.extend_from_slice(&value.to_le_bytes());
+            relocated.extend_from_slice(&bytes[end..]);
+        } else {
+            // short branches become near branches
+            let opcode: &[u8] = match (instruction.map, instruction.opcode) {
+                (Map::Primary, 0xEB) if instruction.opcode_offset == 0 => &[0xE9],
+                (Map::Primary, condition @ 0x70..=0x7F) if instruction.opcode_offset == 0 => {
+                    &[0x0F, condition + 0x10]
+                }
+                _ => return Err(DetourError::Unsupported { offset }),
+            };
+
+            let value = relative(target, here + opcode.len() + 4)
+                .ok_or(DetourError::OutOfRange { offset })?;
+
+            relocated.extend_from_slice(opcode);
+            relocated.extend_from_slice(&value.to_le_bytes());
+        }
+
+        offset += bytes.len();
+    }
+
+    let here = destination + relocated.len();
+    let value =
+        relative(source + len, here + JMP_LEN).ok_or(DetourError::OutOfRange { offset: len })?;
+
+    relocated.push(0xE9);
+    relocated.extend_from_slice(&value.to_le_bytes());
+
+    Ok(relocated)
+}
+
+/// `lock cmpxchg16b`, `address` must be 16 byte aligned.
+unsafe fn compare_exchange_u128(address: *mut u128, current: u128, new: u128) -> bool {
+    let success: u8;
+
+    // `rbx` is reserved by LLVM, so it's swapped in and out
+    asm!(
+        "xchg rbx, {new_low}",
+        "lock cmpxchg16b xmmword ptr [{address}]",
+        "sete {success}",
+        "xchg rbx, {new_low}",
+        address = in(reg) address,
+        new_low = inout(reg) new as u64 => _,
+        success = out(reg_byte) success,
+        in("rcx") (new >> 64) as u64,
+        inout("rax") current as u64 => _,
+        inout("rdx") (current >> 64) as u64 => _,
+    );
+
+    success != 0
+}
+
+/// Write `bytes` to `address` in one store, so nothing executes half of them.
+pub(crate) unsafe fn write_atomic(address: *mut u8, bytes: &[u8]) -> Result<(), DetourError> {
+    let offset = address.addr() % 16;
+
+    if offset + bytes.len() > 16 {
+        return Err(DetourError::Unaligned);
+    }
+
+    let merge = |current: &[u8], offset: usize| {
+        let mut new = current.to_vec();
+
+        new[offset..offset + bytes.len()].copy_from_slice(bytes);
+        new
+    };
+
+    patch::with_writable(address, bytes.len(), || {
+        if offset % 8 + bytes.len() <= 8 {
+            let word = &*address.sub(offset % 8).cast::<AtomicU64>();
+            let mut current = word.load(Ordering::SeqCst);
+
+            loop {
+                let new = merge(&current.to_le_bytes(), offset % 8);
+                let new = u64::from_le_bytes(new.try_into().unwrap());
+
+                match word.compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst) {
+                    Ok(_) => break,
+                    Err(actual) => current = actual,
+                }
+            }
+        } else {
+            let block = address.sub(offset).cast::<u128>();
+
+            loop {
+                let current = ptr::read_volatile(block);
+                let new = merge(&current.to_le_bytes(), offset);
+                let new = u128::from_le_bytes(new.try_into().unwrap());
+
+                if compare_exchange_u128(block, current, new) {
+                    break;
+                }
+            }
+        }
+    })?;
+
+    Ok(())
+}
+
+/// An installed inline hook, which is uninstalled when dropped.
+pub struct Detour {
+    target: *mut u8,
+    trampoline: *mut u8,
+    /// What the jump replaced.
+    original: [u8; JMP_LEN],
+    installed: bool,
+}
+
+unsafe impl Send for Detour {}
+unsafe impl Sync for Detour {}
+
+impl Detour {
+    /// Redirect calls to `target` to `detour`.
+    ///
+    /// # Safety
+    ///
+    /// `target` must be the start of a function, followed by at least 19 readable bytes, and
+    /// nothing may branch into the instructions the jump replaces. `detour` must have the same
+    /// signature, and remain valid until the detour is uninstalled.
+    pub unsafe fn new(target: *const u8, detour: *const u8) -> Result<Self, DetourError> {
+        let target = target as *mut u8;
+        let code = std::slice::from_raw_parts(target, MAX_DISPLACED);
+        let len = displaced_len(code)?;
+        let trampoline = trampoline::allocate(target.addr())?;
+        let original = trampoline.add(ORIGINAL_OFFSET);
+        let relocated = relocate(code, len, target.addr(), original.addr())?;
+
+        let mut bytes = [0xCC; trampoline::SLOT_SIZE];
+
+        bytes[..6].copy_from_slice(&JMP_ABSOLUTE);
+        bytes[6..14].copy_from_slice(&detour.addr().to_le_bytes());
+        bytes[ORIGINAL_OFFSET..ORIGINAL_OFFSET + relocated.len()].copy_from_slice(&relocated);
+
+        patch::with_writable(trampoline, bytes.len(), || {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), trampoline, bytes.len());
+        })?;
+
+        // only within reach, as the trampoline was allocated near the target
+        let value = relative(trampoline.addr(), target.addr() + JMP_LEN).unwrap();
+        let mut jmp = [0xE9; JMP_LEN];
+
+        jmp[1..].copy_from_slice(&value.to_le_bytes());
+
+        let mut detour = Self {
+            target,
+            trampoline,
+            original: [0; JMP_LEN],
+            installed: false,
+        };
+
+        detour.original.copy_from_slice(&code[..JMP_LEN]);
+        write_atomic(target, &jmp)?;
+        detour.installed = true;
+
+        Ok(detour)
+    }
+
+    /// The function that was detoured.
+    #[inline]
+    pub fn target(&self) -> *const u8 {
+        self.target
+    }
+
+    /// The trampoline, which calls the original function once cast to the target's signature.
+    #[inline]
+    pub fn original(&self) -> *const u8 {
+        unsafe { self.trampoline.add(ORIGINAL_OFFSET) }
+    }
+
+    /// Restore the target.
+    ///
+    /// The trampoline isn't freed, as another thread may still be within it.
+    pub fn uninstall(mut self) -> Result<(), DetourError> {
+        unsafe { write_atomic(self.target, &self.original)? };
+
+        self.installed = false;
+
+        Ok(())
+    }
+}
+
+impl Drop for Detour {
+    #[inline]
+    fn drop(&mut self) {
+        if self.installed {
+            let _ = unsafe { write_atomic(self.target, &self.original) };
+        }
+    }
+}
+
+impl fmt::Debug for Detour {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Detour")
+            .field("target", &self.target)
+            .field("original", &self.original())
+            .field("installed", &self.installed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::arch::global_asm;
+    use std::hint::black_box;
+    use std::mem;
+
+    // a rip-relative load, and a short branch past the replaced instructions
+    global_asm!(
+        ".pushsection .text",
+        ".p2align 4",
+        ".globl elysium_mem_load",
+        "elysium_mem_load:",
+        "    mov eax, dword ptr [rip + elysium_mem_value]",
+        "    add eax, edi",
+        "    ret",
+        ".p2align 4",
+        ".globl elysium_mem_branch",
+        "elysium_mem_branch:",
+        "    test edi, edi",
+        "    jz 2f",
+        "    mov eax, 1",
+        "    ret",
+        "2:",
+        "    mov eax, 2",
+        "    ret",
+        ".popsection",
+        ".pushsection .rodata",
+        ".p2align 2",
+        "elysium_mem_value:",
+        "    .long 40",
+        ".popsection",
+    );
+
+    extern "C" {
+        fn elysium_mem_load(value: i32) -> i32;
+        fn elysium_mem_branch(value: i32) -> i32;
+    }
+
+    type Function = unsafe extern "C" fn(i32) -> i32;
+
+    static mut ORIGINAL: Option<Function> = None;
+
+    #[inline(never)]
+    extern "C" fn multiply(value: i32) -> i32 {
+        black_box(value).wrapping_mul(3)
+    }
+
+    unsafe extern "C" fn add_hundred(value: i32) -> i32 {
+        ORIGINAL.unwrap()(value) + 100
+    }
+
+    #[test]
+    fn relocate() {
+        let source = 0x1000_0000;
+
+        // lea rax, [rip + 0x10]; jmp short +2
+        let code = [0x48, 0x8D, 0x05, 0x10, 0x00, 0x00, 0x00, 0xEB, 0x02];
+        let relocated = super::relocate(&code, code.len(), source, source + 0x100).unwrap();
+
+        // lea rax, [rip - 0xF0]; jmp +2 - 0x100 + 2; jmp back
+        assert_eq!(relocated[..7], [0x48, 0x8D, 0x05, 0x10, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(relocated[7], 0xE9);
+        assert_eq!(
+            i32::from_le_bytes(relocated[8..12].try_into().unwrap()),
+            (source + 11) as i32 - (source + 0x100 + 12) as i32,
+        );
+        assert_eq!(relocated[12], 0xE9);
+
+        // loop
+        assert!(matches!(
+            super::relocate(&[0xE2, 0x00], 2, source, source + 0x100),
+            Err(DetourError::Unsupported { offset: 0 })
+        ));
+
+        // ret
+        assert!(matches!(
+            displaced_len(&[0xC3; 16]),
+            Err(DetourError::TooShort)
+        ));
+    }
+
+    #[test]
+    fn atomic() {
+        let mut bytes = [0u8; 48];
+        let base = bytes.as_mut_ptr();
+        let aligned = unsafe { base.add(base.align_offset(16)) };
+
+        unsafe {
+            // spans two words
+            write_atomic(aligned.add(6), &[1, 2, 3, 4, 5]).unwrap();
+            write_atomic(aligned.add(16), &[6; 5]).unwrap();
+
+            assert_eq!(
+                std::slice::from_raw_parts(aligned, 21),
+                [0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 6, 6, 6, 6, 6]
+            );
+
+            assert!(matches!(
+                write_atomic(aligned.add(12), &[0; 5]),
+                Err(DetourError::Unaligned)
+            ));
+        }
+    }
+
+    #[test]
+    fn detour() {
+        unsafe {
+            let call = |function: Function, value| black_box(function)(value);
+
+            for function in [
+                multiply as Function,
+                elysium_mem_load as Function,
+                elysium_mem_branch as Function,
+            ] {
+                let before = [call(function, 0), call(function, 5)];
+                let hook = Detour::new(function as *const u8, add_hundred as *const u8).unwrap();
+
+                ORIGINAL = Some(mem::transmute(hook.original()));
+
+                assert_eq!(
+                    [call(function, 0), call(function, 5)],
+                    [before[0] + 100, before[1] + 100]
+                );
+
+                hook.uninstall().unwrap();
+
+                assert_eq!([call(function, 0), call(function, 5)], before);
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Executable memory within ±2 GiB of an address, so it can be reached with `rel32` operands.
+
+use crate::maps::{Maps, Region};
+use crate::{PAGE_MASK, PAGE_SIZE};
+use std::io;
+use std::sync::Mutex;
+
+/// Size of each trampoline.
+pub(crate) const SLOT_SIZE: usize = 128;
+
+/// Furthest a `rel32` operand can reach, less a page, so all of a page is within reach.
+const REACH: usize = i32::MAX as usize - PAGE_SIZE;
+
+/// Lowest address that may be mapped, see `vm.mmap_min_addr`.
+const MIN_ADDRESS: usize = 0x10000;
+
+/// A page of trampolines, which are handed out in order, and never freed.
+struct Page {
+    address: usize,
+    used: usize,
+}
+
+static PAGES: Mutex<Vec<Page>> = Mutex::new(Vec::new());
+
+/// Whether `a` is within reach of `b`.
+#[inline]
+pub(crate) fn is_within_reach(a: usize, b: usize) -> bool {
+    a.abs_diff(b) <= REACH
+}
+
+/// Map an executable page within reach of `near`.
+fn map_near(near: usize) -> io::Result<usize> {
+    let low = near.saturating_sub(REACH).max(MIN_ADDRESS);
+    let high = near.saturating_add(REACH);
+    let mut candidates = Vec::new();
+    let mut previous_end = MIN_ADDRESS;
+
+    let limit = Region {
+        start: high,
+        end: high,
+        protection: libc::PROT_NONE,
+        shared: false,
+        offset: 0,
+        path: None,
+    };
+
+    // the closest page of each gap between regions
+    for region in Maps::read()?.into_iter().chain([limit]) {
+        let start = (previous_end.max(low) + PAGE_SIZE - 1) & PAGE_MASK;
+        let end = region.start.min(high) & PAGE_MASK;
+
+        previous_end = previous_end.max(region.end);
+
+        if end < start + PAGE_SIZE {
+            continue;
+        }
+
+        candidates.push((near & PAGE_MASK).clamp(start, end - PAGE_SIZE));
+    }
+
+    candidates.sort_by_key(|address| address.abs_diff(near));
+
+    for address in candidates {
+        // SAFETY: `MAP_FIXED_NOREPLACE` fails rather than replace an existing mapping
+        let mapped = unsafe {
+            libc::mmap(
+                address as *mut libc::c_void,
+                PAGE_SIZE,
+                libc::PROT_READ | libc::PROT_EXEC,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_FIXED_NOREPLACE,
+                -1,
+                0,
+            )
+        };
+
+        if mapped == libc::MAP_FAILED {
+            continue;
+        }
+
+        // older kernels treat `MAP_FIXED_NOREPLACE` as a hint
+        if mapped as usize != address {
+            unsafe {
+                libc::munmap(mapped, PAGE_SIZE);
+            }
+
+            continue;
+        }
+
+        return Ok(address);
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::OutOfMemory,
+        format!("no free memory within 2 GiB of {near:#x}"),
+    ))
+}
+
+/// Allocate a trampoline of `SLOT_SIZE` bytes, within reach of `near`.
+pub(crate) fn allocate(near: usize) -> io::Result<*mut u8> {
+    let mut pages = PAGES.lock().unwrap();
+    let page = pages.iter_mut().find(|page| {
+        page.used < PAGE_SIZE / SLOT_SIZE
+            && is_within_reach(page.address, near)
+            && is_within_reach(page.address + PAGE_SIZE, near)
+    });
+
+    let page = match page {
+        Some(page) => page,
+        None => {
+            pages.push(Page {
+                address: map_near(near)?,
+                used: 0,
+            });
+
+            pages.last_mut().unwrap()
+        }
+    };
+
+    let address = page.address + page.used * SLOT_SIZE;
+
+    page.used += 1;
+
+    Ok(address as *mut u8)
+}
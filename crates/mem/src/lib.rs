@@ -3,14 +3,26 @@
 
 //! Memory related functions.
 
+pub use call::CallSite;
+pub use detour::{Detour, DetourError};
+pub use maps::{Maps, Region};
+pub use patch::Patch;
+pub use vmt::VmtHook;
+
+mod call;
+mod detour;
+mod patch;
+mod vmt;
+
+pub mod decode;
+pub mod maps;
+
 /// The size of a page.
 pub const PAGE_SIZE: usize = 4096;
 
 /// Mask used to obtain a page address from an arbitary address.
 pub const PAGE_MASK: usize = !(PAGE_SIZE - 1);
 
-const UNPROTECTED: i32 = libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC;
-
 /// Creates a new pointer with the given address and size.
 #[inline]
 pub unsafe fn to_absolute<T>(base: *const T, addr: isize, size: usize) -> *const T {
@@ -31,24 +43,6 @@ pub unsafe fn to_absolute_with_offset<T>(base: *const T, offset: usize, len: usi
     base.byte_offset(offset).byte_add(len)
 }
 
-/// Set protection for the page of the given pointer.
-#[inline]
-pub unsafe fn protect<T>(ptr: *const T, protection: i32) {
-    let page = ptr.map_addr(|addr| addr & PAGE_MASK);
-
-    libc::mprotect(page as *mut libc::c_void, PAGE_SIZE, protection);
-}
-
-/// Disable protection for the page of the given pointer.
-///
-/// Convenience function for `protect(ptr, READ | WRITE | EXECUTE)`.
-#[inline]
-pub unsafe fn unprotect<T>(ptr: *const T) -> i32 {
-    protect(ptr, UNPROTECTED);
-
-    libc::PROT_READ | libc::PROT_EXEC
-}
-
 #[cfg(test)]
 mod tests {
     const CODE: [u8; 6] = [0xFF, 0x25, 0xCA, 0xFC, 0x32, 0x00];
@@ -0,0 +1,246 @@
+//! Regions of the address space, from `/proc/self/maps`.
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::{fs, io};
+
+/// A mapped region of the address space.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Region {
+    pub start: usize,
+    pub end: usize,
+    /// `PROT_*`
+    pub protection: i32,
+    /// Whether writes are shared with other mappings of the file, rather than copied on write.
+    pub shared: bool,
+    /// Offset of the region within the file.
+    pub offset: u64,
+    /// The file, or what the region is, such as `[heap]` and `[stack]`, `None` if anonymous.
+    pub path: Option<PathBuf>,
+}
+
+impl Region {
+    /// Length in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Whether `address` is within the region.
+    #[inline]
+    pub fn contains(&self, address: usize) -> bool {
+        (self.start..self.end).contains(&address)
+    }
+
+    #[inline]
+    pub fn is_readable(&self) -> bool {
+        self.protection & libc::PROT_READ != 0
+    }
+
+    #[inline]
+    pub fn is_writable(&self) -> bool {
+        self.protection & libc::PROT_WRITE != 0
+    }
+
+    #[inline]
+    pub fn is_executable(&self) -> bool {
+        self.protection & libc::PROT_EXEC != 0
+    }
+}
+
+/// Parse a line of `/proc/self/maps`.
+///
+/// `start-end perms offset dev inode path`, where `path` may contain spaces, or be missing.
+fn parse_line(line: &str) -> Option<Region> {
+    let mut fields = line.splitn(6, ' ');
+    let (start, end) = fields.next()?.split_once('-')?;
+    let permissions = fields.next()?.as_bytes();
+    let offset = fields.next()?;
+    let _device = fields.next()?;
+    let _inode = fields.next()?;
+    let path = fields.next().map(str::trim_start).unwrap_or("");
+
+    if permissions.len() != 4 {
+        return None;
+    }
+
+    let mut protection = libc::PROT_NONE;
+
+    for (flag, permission) in [
+        (libc::PROT_READ, b'r'),
+        (libc::PROT_WRITE, b'w'),
+        (libc::PROT_EXEC, b'x'),
+    ] {
+        if permissions.contains(&permission) {
+            protection |= flag;
+        }
+    }
+
+    Some(Region {
+        start: usize::from_str_radix(start, 16).ok()?,
+        end: usize::from_str_radix(end, 16).ok()?,
+        protection,
+        shared: permissions[3] == b's',
+        offset: u64::from_str_radix(offset, 16).ok()?,
+        path: (!path.is_empty()).then(|| PathBuf::from(OsStr::from_bytes(path.as_bytes()))),
+    })
+}
+
+/// A snapshot of the address space.
+#[derive(Clone, Debug)]
+pub struct Maps {
+    /// In order of address.
+    regions: Vec<Region>,
+}
+
+impl Maps {
+    /// Read `/proc/self/maps`.
+    pub fn read() -> io::Result<Self> {
+        Self::parse(&fs::read_to_string("/proc/self/maps")?)
+    }
+
+    /// Parse the contents of a `maps` file.
+    pub fn parse(maps: &str) -> io::Result<Self> {
+        let regions = maps
+            .lines()
+            .map(|line| {
+                parse_line(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("malformed maps line `{line}`"),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { regions })
+    }
+
+    /// Every region, in order of address.
+    #[inline]
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// The region containing `ptr`.
+    pub fn region_of<T>(&self, ptr: *const T) -> Option<&Region> {
+        let address = ptr as usize;
+        let index = self.regions.partition_point(|region| region.end <= address);
+
+        self.regions
+            .get(index)
+            .filter(|region| region.contains(address))
+    }
+
+    /// Whether `len` bytes at `ptr` are mapped, and all have `protection`.
+    pub fn has_protection<T>(&self, ptr: *const T, len: usize, protection: i32) -> bool {
+        let mut address = ptr as usize;
+        let end = match address.checked_add(len.max(1)) {
+            Some(end) => end,
+            None => return false,
+        };
+
+        while address < end {
+            match self.region_of(address as *const u8) {
+                Some(region) if region.protection & protection == protection => {
+                    address = region.end
+                }
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Whether `len` bytes at `ptr` can be read.
+    #[inline]
+    pub fn is_readable<T>(&self, ptr: *const T, len: usize) -> bool {
+        self.has_protection(ptr, len, libc::PROT_READ)
+    }
+
+    /// Whether `len` bytes at `ptr` can be written.
+    #[inline]
+    pub fn is_writable<T>(&self, ptr: *const T, len: usize) -> bool {
+        self.has_protection(ptr, len, libc::PROT_READ | libc::PROT_WRITE)
+    }
+}
+
+impl IntoIterator for Maps {
+    type Item = Region;
+    type IntoIter = std::vec::IntoIter<Region>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.regions.into_iter()
+    }
+}
+
+/// The region containing `ptr`, as of now.
+#[inline]
+pub fn region_of<T>(ptr: *const T) -> io::Result<Option<Region>> {
+    Ok(Maps::read()?.region_of(ptr).cloned())
+}
+
+/// Whether `len` bytes at `ptr` can be read, as of now.
+///
+/// Reads `/proc/self/maps` each call, use `Maps` for many pointers.
+#[inline]
+pub fn is_readable<T>(ptr: *const T, len: usize) -> bool {
+    Maps::read()
+        .map(|maps| maps.is_readable(ptr, len))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parse() {
+        let maps = Maps::parse(concat!(
+            "55d0c0a00000-55d0c0a02000 r--p 00000000 fd:01 1234                       /usr/bin/cat\n",
+            "55d0c0a02000-55d0c0a07000 r-xp 00002000 fd:01 1234                       /usr/bin/a b\n",
+            "55d0c1000000-55d0c1021000 rw-p 00000000 00:00 0                          [heap]\n",
+            "7f0000000000-7f0000001000 rw-s 00000000 00:05 42                         /dev/zero (deleted)\n",
+            "7f0000001000-7f0000002000 ---p 00000000 00:00 0 \n",
+        ))
+        .unwrap();
+
+        let regions = maps.regions();
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[1].protection, libc::PROT_READ | libc::PROT_EXEC);
+        assert_eq!(regions[1].offset, 0x2000);
+        assert_eq!(regions[1].path.as_deref(), Some(Path::new("/usr/bin/a b")));
+        assert_eq!(regions[2].path.as_deref(), Some(Path::new("[heap]")));
+        assert!(regions[3].shared);
+        assert_eq!(regions[4].path, None);
+
+        let region = maps.region_of(0x55d0c0a02010 as *const u8).unwrap();
+
+        assert_eq!(region.start, 0x55d0c0a02000);
+        assert!(maps.region_of(0x55d0c0a07000 as *const u8).is_none());
+
+        // spans two readable regions
+        assert!(maps.is_readable(0x55d0c0a01ff0 as *const u8, 0x20));
+        assert!(!maps.is_readable(0x55d0c0a06ff0 as *const u8, 0x20));
+        assert!(!maps.is_readable(0x7f0000001000 as *const u8, 1));
+        assert!(!maps.is_writable(0x55d0c0a00000 as *const u8, 1));
+
+        assert!(Maps::parse("not a mapping").is_err());
+
+        // this process
+        let value = 1;
+
+        assert!(is_readable(&value, 4));
+        assert!(!is_readable(std::ptr::null::<u8>(), 1));
+        assert!(region_of(&value).unwrap().unwrap().is_writable());
+    }
+}
@@ -0,0 +1,367 @@
+//! Patching memory, and restoring it afterwards.
+
+use crate::detour;
+use crate::maps::{Maps, Region};
+use crate::{PAGE_MASK, PAGE_SIZE};
+use std::{fmt, io, mem, ptr};
+
+/// Each region within `start..end`, clipped to it.
+fn protections(start: usize, end: usize) -> io::Result<Vec<Region>> {
+    let mut regions = Vec::new();
+    let mut covered = start;
+
+    for region in Maps::read()? {
+        if region.end <= covered {
+            continue;
+        }
+
+        if region.start > covered {
+            break;
+        }
+
+        let clipped_end = region.end.min(end);
+
+        regions.push(Region {
+            start: covered,
+            end: clipped_end,
+            ..region
+        });
+
+        covered = clipped_end;
+
+        if covered == end {
+            return Ok(regions);
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{covered:#x} isn't mapped"),
+    ))
+}
+
+/// Make `address..address + len` writable while `f` runs, then restore it's protection.
+pub(crate) unsafe fn with_writable<F, R>(address: *mut u8, len: usize, f: F) -> io::Result<R>
+where
+    F: FnOnce() -> R,
+{
+    let start = address.addr() & PAGE_MASK;
+    let end = (address.addr() + len + PAGE_SIZE - 1) & PAGE_MASK;
+    let regions = protections(start, end)?;
+    let writable = libc::PROT_READ | libc::PROT_WRITE;
+    let mut unprotected = Vec::new();
+
+    let restore = |unprotected: &[Region]| {
+        for region in unprotected {
+            libc::mprotect(
+                region.start as *mut libc::c_void,
+                region.len(),
+                region.protection,
+            );
+        }
+    };
+
+    for region in regions {
+        if region.protection & writable == writable {
+            continue;
+        }
+
+        // execute permission is kept, the code being patched may be running
+        if libc::mprotect(
+            region.start as *mut libc::c_void,
+            region.len(),
+            region.protection | writable,
+        ) != 0
+        {
+            let error = io::Error::last_os_error();
+
+            restore(&unprotected);
+
+            return Err(error);
+        }
+
+        unprotected.push(region);
+    }
+
+    let result = f();
+
+    restore(&unprotected);
+
+    Ok(result)
+}
+
+/// Swap the memory at `address` with `bytes`, making it writable meanwhile.
+unsafe fn swap(address: *mut u8, bytes: &mut [u8]) -> io::Result<()> {
+    with_writable(address, bytes.len(), || {
+        ptr::swap_nonoverlapping(address, bytes.as_mut_ptr(), bytes.len())
+    })
+}
+
+/// Bytes written over memory, which are reverted when dropped.
+///
+/// Protection is changed only for as long as it takes to write, then restored to what it was,
+/// which works for code, read-only data such as vtables, and ranges that span mappings.
+pub struct Patch {
+    address: *mut u8,
+    /// What was there before, while applied.
+    bytes: Box<[u8]>,
+    applied: bool,
+    /// Written, and reverted, in one store.
+    atomic: bool,
+}
+
+unsafe impl Send for Patch {}
+unsafe impl Sync for Patch {}
+
+impl Patch {
+    /// Write `bytes` to `address`.
+    ///
+    /// # Safety
+    ///
+    /// `address..address + bytes.len()` must be mapped, and safe to overwrite until the patch is
+    /// reverted. Nothing may execute the bytes while they're written, as they aren't written at
+    /// once, see `atomic`.
+    pub unsafe fn new(address: *mut u8, bytes: &[u8]) -> io::Result<Self> {
+        let mut bytes: Box<[u8]> = bytes.into();
+
+        swap(address, &mut bytes)?;
+
+        Ok(Self {
+            address,
+            bytes,
+            applied: true,
+            atomic: false,
+        })
+    }
+
+    /// Write `bytes` to `address` in one store, so nothing executes half of them, such as a jump
+    /// over code another thread may be running. It's reverted the same way.
+    ///
+    /// Fails if `bytes` span a 16 byte boundary.
+    ///
+    /// # Safety
+    ///
+    /// `address..address + bytes.len()` must be mapped, and safe to overwrite until the patch is
+    /// reverted.
+    pub unsafe fn atomic(address: *mut u8, bytes: &[u8]) -> io::Result<Self> {
+        let original = std::slice::from_raw_parts(address, bytes.len()).into();
+
+        detour::write_atomic(address, bytes)?;
+
+        Ok(Self {
+            address,
+            bytes: original,
+            applied: true,
+            atomic: true,
+        })
+    }
+
+    /// Write `value` to `address`, such as a function pointer within a vtable.
+    ///
+    /// Values of at most 8 bytes, at a naturally aligned address, are written, and reverted, in
+    /// one store, see `atomic`, so another thread reading it meanwhile, such as by calling the
+    /// function pointer, sees either the old, or the new value, never half of each. Anything
+    /// else is written as `new` does.
+    ///
+    /// # Safety
+    ///
+    /// The same as `new`, `address` needn't be aligned.
+    #[inline]
+    pub unsafe fn write<T: Copy>(address: *mut T, value: T) -> io::Result<Self> {
+        let len = mem::size_of::<T>();
+        let bytes = std::slice::from_raw_parts((&value as *const T).cast(), len);
+
+        if len <= 8 && len.is_power_of_two() && address.addr() % len == 0 {
+            Self::atomic(address.cast(), bytes)
+        } else {
+            Self::new(address.cast(), bytes)
+        }
+    }
+
+    /// Where the patch is.
+    #[inline]
+    pub fn address(&self) -> *mut u8 {
+        self.address
+    }
+
+    /// Whether the patch was written, and is reverted, in one store.
+    #[inline]
+    pub fn is_atomic(&self) -> bool {
+        self.atomic
+    }
+
+    /// What was at `address` before the patch.
+    #[inline]
+    pub fn original(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// What was at `address` before the patch, as a `T`.
+    ///
+    /// Panics if the patch isn't the size of a `T`.
+    ///
+    /// # Safety
+    ///
+    /// The original bytes must be a valid `T`.
+    #[inline]
+    pub unsafe fn original_as<T: Copy>(&self) -> T {
+        assert_eq!(self.bytes.len(), mem::size_of::<T>());
+
+        self.bytes.as_ptr().cast::<T>().read_unaligned()
+    }
+
+    /// Restore the original bytes.
+    pub fn revert(mut self) -> io::Result<()> {
+        unsafe { self.restore()? };
+
+        self.applied = false;
+
+        Ok(())
+    }
+
+    unsafe fn restore(&mut self) -> io::Result<()> {
+        if self.atomic {
+            detour::write_atomic(self.address, &self.bytes)?;
+
+            Ok(())
+        } else {
+            swap(self.address, &mut self.bytes)
+        }
+    }
+}
+
+impl Drop for Patch {
+    #[inline]
+    fn drop(&mut self) {
+        if self.applied {
+            let _ = unsafe { self.restore() };
+        }
+    }
+}
+
+impl fmt::Debug for Patch {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Patch")
+            .field("address", &self.address)
+            .field("len", &self.bytes.len())
+            .field("applied", &self.applied)
+            .field("atomic", &self.atomic)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch() {
+        unsafe {
+            let pages = libc::mmap(
+                ptr::null_mut(),
+                PAGE_SIZE * 2,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+            .cast::<u8>();
+
+            assert_ne!(pages, libc::MAP_FAILED.cast());
+
+            pages.write_bytes(0xCC, PAGE_SIZE * 2);
+            libc::mprotect(pages.cast(), PAGE_SIZE, libc::PROT_READ);
+            libc::mprotect(
+                pages.add(PAGE_SIZE).cast(),
+                PAGE_SIZE,
+                libc::PROT_READ | libc::PROT_EXEC,
+            );
+
+            let start = pages.addr();
+            let end = start + PAGE_SIZE * 2;
+            let protections = |start, end| {
+                protections(start, end)
+                    .unwrap()
+                    .into_iter()
+                    .map(|region| (region.start, region.end, region.protection))
+                    .collect::<Vec<_>>()
+            };
+
+            let expected = vec![
+                (start, start + PAGE_SIZE, libc::PROT_READ),
+                (start + PAGE_SIZE, end, libc::PROT_READ | libc::PROT_EXEC),
+            ];
+
+            assert_eq!(protections(start, end), expected);
+
+            // spans both pages
+            let address = pages.add(PAGE_SIZE - 2);
+            let patch = Patch::new(address, &[1, 2, 3, 4]).unwrap();
+
+            assert_eq!(std::slice::from_raw_parts(address, 4), [1, 2, 3, 4]);
+            assert_eq!(patch.original(), [0xCC; 4]);
+            assert_eq!(protections(start, end), expected);
+
+            patch.revert().unwrap();
+
+            assert_eq!(std::slice::from_raw_parts(address, 4), [0xCC; 4]);
+
+            {
+                let patch = Patch::write(address.cast::<u32>(), 0xDEADBEEFu32).unwrap();
+
+                assert_eq!(address.cast::<u32>().read_unaligned(), 0xDEADBEEF);
+
+                // unaligned, so written a byte at a time
+                assert!(!patch.is_atomic());
+            }
+
+            // reverted on drop
+            assert_eq!(std::slice::from_raw_parts(address, 4), [0xCC; 4]);
+
+            {
+                let aligned = pages.add(PAGE_SIZE).cast::<u64>();
+                let patch = Patch::write(aligned, 0xDEADBEEF_CAFEBABEu64).unwrap();
+
+                assert_eq!(aligned.read(), 0xDEADBEEF_CAFEBABE);
+                assert!(patch.is_atomic());
+                assert_eq!(protections(start, end), expected);
+            }
+
+            assert_eq!(
+                pages.add(PAGE_SIZE).cast::<u64>().read(),
+                u64::from_le_bytes([0xCC; 8])
+            );
+
+            libc::munmap(pages.cast(), PAGE_SIZE * 2);
+
+            // no longer mapped
+            assert!(Patch::new(address, &[0]).is_err());
+        }
+    }
+
+    #[test]
+    fn atomic() {
+        let mut bytes = [0xCCu8; 32];
+        let base = bytes.as_mut_ptr();
+
+        unsafe {
+            let aligned = base.add(base.align_offset(16));
+
+            {
+                let _patch = Patch::atomic(aligned.add(2), &[1, 2, 3]).unwrap();
+
+                assert_eq!(
+                    std::slice::from_raw_parts(aligned, 5),
+                    [0xCC, 0xCC, 1, 2, 3]
+                );
+            }
+
+            // reverted on drop
+            assert_eq!(std::slice::from_raw_parts(aligned, 5), [0xCC; 5]);
+
+            // spans a 16 byte boundary
+            assert!(Patch::atomic(aligned.add(14), &[0; 4]).is_err());
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Hooking virtual functions.
+
+use crate::maps::{Maps, Region};
+use crate::patch::Patch;
+use std::{fmt, io, mem, ptr};
+
+/// Entries before the address point of an Itanium vtable, the offset to top, and type info.
+const HEADER_LEN: usize = 2;
+
+/// Number of functions within `vtable`, those that point to executable memory.
+unsafe fn len(vtable: *const usize) -> io::Result<usize> {
+    let maps = Maps::read()?;
+    let is_executable = |address: usize| {
+        maps.region_of(address as *const u8)
+            .map_or(false, Region::is_executable)
+    };
+
+    let mut len = 0;
+
+    // the vtable may end at the end of a mapping
+    while maps.is_readable(vtable.add(len), mem::size_of::<usize>())
+        && is_executable(vtable.add(len).read())
+    {
+        len += 1;
+    }
+
+    Ok(len)
+}
+
+enum Kind {
+    /// The entry within the object's vtable was replaced.
+    Swap(Patch),
+    /// The object's vtable was replaced with a copy.
+    Shadow {
+        object: *mut *const usize,
+        vtable: *const usize,
+        shadow: Box<[usize]>,
+    },
+}
+
+/// A hooked virtual function, of type `F`, which is unhooked when dropped.
+///
+/// `swap` replaces the entry within the vtable, which hooks every object of that class, whereas
+/// `shadow` gives a single object a copy of it's vtable, leaving the vtable untouched.
+pub struct VmtHook<F: Copy> {
+    index: usize,
+    original: F,
+    /// `None` once unhooked.
+    kind: Option<Kind>,
+}
+
+unsafe impl<F: Copy + Send> Send for VmtHook<F> {}
+unsafe impl<F: Copy + Sync> Sync for VmtHook<F> {}
+
+impl<F: Copy> VmtHook<F> {
+    /// The vtable of `object`, and the function at `index` within it.
+    unsafe fn lookup<T>(object: *const T, index: usize) -> io::Result<(*const usize, usize)> {
+        assert_eq!(
+            mem::size_of::<F>(),
+            mem::size_of::<usize>(),
+            "`F` isn't a function pointer"
+        );
+
+        let vtable = object.cast::<*const usize>().read();
+
+        // entries are replaced in one store, which needs them aligned
+        if vtable.align_offset(mem::align_of::<usize>()) != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the vtable at {vtable:?} isn't aligned"),
+            ));
+        }
+
+        let len = len(vtable)?;
+
+        if index >= len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("index {index} is outside of the vtable of {len} functions"),
+            ));
+        }
+
+        Ok((vtable, len))
+    }
+
+    /// Replace the function at `index` within the vtable of `object` with `hook`.
+    ///
+    /// This affects every object sharing the vtable. The entry is replaced, and restored, in one
+    /// store, so a thread calling it meanwhile calls either function, never half of each.
+    ///
+    /// # Safety
+    ///
+    /// `object` must begin with a pointer to it's vtable, `F` must be the type of the function at
+    /// `index`, and `hook` must remain callable until it's unhooked.
+    pub unsafe fn swap<T>(object: *const T, index: usize, hook: F) -> io::Result<Self> {
+        let (vtable, _len) = Self::lookup(object, index)?;
+        let patch = Patch::write(vtable.add(index).cast::<F>() as *mut F, hook)?;
+
+        debug_assert!(patch.is_atomic());
+
+        Ok(Self {
+            index,
+            original: patch.original_as(),
+            kind: Some(Kind::Swap(patch)),
+        })
+    }
+
+    /// Give `object` a copy of it's vtable, with the function at `index` replaced with `hook`.
+    ///
+    /// Shadowing an object that's already shadowed copies the shadow, so unhook them in the
+    /// reverse order.
+    ///
+    /// # Safety
+    ///
+    /// The same as `swap`, and `object` must outlive the hook, without anything else replacing
+    /// it's vtable meanwhile.
+    pub unsafe fn shadow<T>(object: *mut T, index: usize, hook: F) -> io::Result<Self> {
+        let (vtable, len) = Self::lookup(object, index)?;
+        let object = object.cast::<*const usize>();
+        let mut shadow: Box<[usize]> =
+            std::slice::from_raw_parts(vtable.sub(HEADER_LEN), HEADER_LEN + len).into();
+
+        let original = vtable.add(index).cast::<F>().read();
+
+        shadow[HEADER_LEN + index] = mem::transmute_copy(&hook);
+        ptr::write_volatile(object, shadow.as_ptr().add(HEADER_LEN));
+
+        Ok(Self {
+            index,
+            original,
+            kind: Some(Kind::Shadow {
+                object,
+                vtable,
+                shadow,
+            }),
+        })
+    }
+
+    /// Index of the hooked function.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The function that was replaced.
+    #[inline]
+    pub fn original(&self) -> F {
+        self.original
+    }
+
+    /// Restore the original function.
+    pub fn unhook(mut self) -> io::Result<()> {
+        match self.kind.take() {
+            Some(kind) => unsafe { unhook(kind) },
+            None => Ok(()),
+        }
+    }
+}
+
+/// Undo what `kind` did.
+unsafe fn unhook(kind: Kind) -> io::Result<()> {
+    match kind {
+        Kind::Swap(patch) => patch.revert(),
+        Kind::Shadow {
+            object,
+            vtable,
+            shadow,
+        } => {
+            // the object may have been shadowed again since, so the shadow must stay alive
+            if ptr::read_volatile(object) != shadow.as_ptr().add(HEADER_LEN) {
+                mem::forget(shadow);
+
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "the vtable was replaced since it was shadowed",
+                ));
+            }
+
+            ptr::write_volatile(object, vtable);
+
+            Ok(())
+        }
+    }
+}
+
+impl<F: Copy> Drop for VmtHook<F> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(kind) = self.kind.take() {
+            let _ = unsafe { unhook(kind) };
+        }
+    }
+}
+
+impl<F: Copy> fmt::Debug for VmtHook<F> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            Some(Kind::Swap(_)) => "swap",
+            Some(Kind::Shadow { .. }) => "shadow",
+            None => "unhooked",
+        };
+
+        fmt.debug_struct("VmtHook")
+            .field("index", &self.index)
+            .field("kind", &kind)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    type Function = unsafe extern "C" fn(this: *const Object, value: i32) -> i32;
+
+    #[repr(C)]
+    struct Object {
+        vtable: *const Option<Function>,
+    }
+
+    unsafe extern "C" fn add(_this: *const Object, value: i32) -> i32 {
+        value + 1
+    }
+
+    unsafe extern "C" fn subtract(_this: *const Object, value: i32) -> i32 {
+        value - 1
+    }
+
+    unsafe extern "C" fn multiply(_this: *const Object, value: i32) -> i32 {
+        value * 2
+    }
+
+    // a header, two functions, then the end of the vtable
+    static VTABLE: [Option<Function>; 5] = [None, None, Some(add), Some(subtract), None];
+
+    fn object() -> Object {
+        Object {
+            vtable: VTABLE[HEADER_LEN..].as_ptr(),
+        }
+    }
+
+    fn call(object: &Object, index: usize) -> i32 {
+        unsafe {
+            let function = black_box(object.vtable).add(index).read().unwrap();
+
+            function(object, 10)
+        }
+    }
+
+    #[test]
+    fn vmt_hook() {
+        unsafe {
+            assert_eq!(len(VTABLE[HEADER_LEN..].as_ptr().cast()).unwrap(), 2);
+
+            let mut first = object();
+            let second = object();
+            let hook = VmtHook::swap(&first, 1, multiply as Function).unwrap();
+
+            // replaced in one store
+            match &hook.kind {
+                Some(Kind::Swap(patch)) => {
+                    assert_eq!(patch.address().align_offset(8), 0);
+                    assert!(patch.is_atomic());
+                }
+                _ => panic!("expected a swapped entry"),
+            }
+
+            // every object
+            assert_eq!([call(&first, 1), call(&second, 1)], [20, 20]);
+            assert_eq!(hook.original()(&first, 10), 9);
+
+            hook.unhook().unwrap();
+
+            assert_eq!([call(&first, 1), call(&second, 1)], [9, 9]);
+
+            // only the one object
+            let hook = VmtHook::shadow(&mut first, 0, multiply as Function).unwrap();
+
+            assert_eq!([call(&first, 0), call(&second, 0)], [20, 11]);
+            assert_eq!(call(&first, 1), 9);
+            assert_eq!(hook.original()(&first, 10), 11);
+
+            drop(hook);
+
+            assert_eq!(first.vtable, second.vtable);
+            assert_eq!(call(&first, 0), 11);
+
+            assert!(VmtHook::swap(&first, 2, multiply as Function).is_err());
+
+            // entries that can't be replaced in one store
+            let unaligned = Object {
+                vtable: first.vtable.cast::<u8>().add(1).cast(),
+            };
+
+            assert!(VmtHook::swap(&unaligned, 0, multiply as Function).is_err());
+        }
+    }
+}
@@ -59,6 +59,14 @@ impl Menu {
         });
     }
 
+    /// Delete the OpenGL objects of the menu, which mustn't be drawn after.
+    #[inline]
+    pub fn destroy(&mut self, context: &elysium_gl::Context) {
+        self.renderer.with_primitives(|backend, _primitives| {
+            backend.destroy(context);
+        });
+    }
+
     #[inline]
     pub fn update(&mut self, viewport: Viewport, cursor_position: Point) {
         let clipboard = &mut self.clipboard;
@@ -0,0 +1,95 @@
+//! The interface between elysium and it's feature module.
+//!
+//! elysium owns the hooks and global state, and calls into the feature module, which can be
+//! rebuilt and reloaded while the game is running. Anything the module needs is passed to it,
+//! as it has it's own copy of every static, and anything it keeps between calls is stored by
+//! elysium, as it's forgotten on reload.
+//!
+//! Only `#[repr(C)]` types, and `Option`s of them, cross between the two, as both are built by the
+//! same compiler, and `ABI_VERSION` must be bumped whenever any of them, or `Command`, change.
+//! `Layout` catches a forgotten bump, whenever the change affects their sizes.
+
+#![deny(warnings)]
+
+use core::mem;
+use elysium_math::Vec3;
+use elysium_sdk::Command;
+
+/// Version of the interface, a module built against a different version is refused.
+pub const ABI_VERSION: u32 = 3;
+
+/// Name of the symbol the module is exported as.
+pub const SYMBOL: &str = "ELYSIUM_MODULE";
+
+/// Sizes of what crosses between elysium and the module, as either was built.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct Layout {
+    pub create_move: usize,
+    pub command: usize,
+}
+
+impl Layout {
+    /// Sizes as the calling crate was built.
+    pub const fn current() -> Self {
+        Self {
+            create_move: mem::size_of::<CreateMove<'_>>(),
+            command: mem::size_of::<Command>(),
+        }
+    }
+}
+
+/// Entry points of a feature module.
+#[repr(C)]
+pub struct Module {
+    /// Must be `ABI_VERSION`, checked before anything else is read.
+    pub abi_version: u32,
+    /// Must be `Layout::current()`, checked after `abi_version`.
+    pub layout: Layout,
+    /// Called by `CreateMove`, after the original.
+    pub create_move: unsafe extern "C" fn(context: &mut CreateMove<'_>),
+}
+
+/// Local player state kept between calls.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct Local {
+    pub old_yaw: f32,
+    pub was_attacking: bool,
+    pub was_on_ground: bool,
+}
+
+/// Everything `create_move` has access to.
+#[repr(C)]
+pub struct CreateMove<'a> {
+    pub command: &'a mut Command,
+    pub send_packet: &'a mut bool,
+    pub local: &'a mut Local,
+    /// The local player's flags.
+    pub flags: i32,
+    /// The local player's move type.
+    pub move_kind: i32,
+    /// The local player's velocity.
+    pub velocity: Vec3,
+    /// The engine's view angle.
+    pub view_angle: Vec3,
+    /// `cl_sidespeed`, `None` if the game doesn't have it.
+    pub horizontal_speed: Option<f32>,
+}
+
+/// Export `module` from a feature module.
+///
+/// ```ignore
+/// elysium_module::export!(Module {
+///     abi_version: ABI_VERSION,
+///     layout: Layout::current(),
+///     create_move,
+/// });
+/// ```
+#[macro_export]
+macro_rules! export {
+    ($module:expr) => {
+        #[no_mangle]
+        pub static ELYSIUM_MODULE: $crate::Module = $module;
+    };
+}
@@ -0,0 +1,99 @@
+//! Compares the regex path against the scanner on a synthetic 50 MB buffer.
+//!
+//! `cargo bench -p providence-pattern`
+
+#![feature(test)]
+
+extern crate test;
+
+use providence_pattern::Pattern;
+use test::Bencher;
+
+const LEN: usize = 50 * 1024 * 1024;
+
+const PATTERN: Pattern<31> = Pattern::new(
+    "55 48 89 E5 41 57 41 56 41 89 FE 41 55 41 54 53 48 81 EC 98 01 00 00 F3 0F 11 85 5C FE FF FF",
+);
+
+const WILDCARDS: Pattern<22> =
+    Pattern::new("55 48 89 E5 53 48 89 FB 48 83 EC 28 48 8B 05 ?? ?? ?? ?? 48 8B 00");
+
+/// Pseudo-random bytes, sprinkled with function prologues so anchors have false positives,
+/// with `pattern` placed at the very end.
+fn haystack(pattern: &[u8]) -> Vec<u8> {
+    let mut state = 0x2545_F491_4F6C_DD1D_u64;
+    let mut haystack: Vec<u8> = (0..LEN)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect();
+
+    for offset in (0..LEN - 4).step_by(4096) {
+        haystack[offset..offset + 4].copy_from_slice(&[0x55, 0x48, 0x89, 0xE5]);
+    }
+
+    let start = LEN - pattern.len();
+
+    haystack[start..].copy_from_slice(pattern);
+    haystack
+}
+
+fn concrete<const N: usize>(pattern: &Pattern<N>) -> Vec<u8> {
+    pattern.bytes().iter().map(|byte| byte.value()).collect()
+}
+
+#[bench]
+fn regex(bencher: &mut Bencher) {
+    let haystack = haystack(&concrete(&PATTERN));
+
+    bencher.iter(|| PATTERN.regex().find(&haystack).map(|found| found.start()));
+}
+
+#[bench]
+fn scanner(bencher: &mut Bencher) {
+    let haystack = haystack(&concrete(&PATTERN));
+
+    bencher.iter(|| PATTERN.find(&haystack));
+}
+
+#[bench]
+fn regex_wildcards(bencher: &mut Bencher) {
+    let haystack = haystack(&concrete(&WILDCARDS));
+
+    bencher.iter(|| WILDCARDS.regex().find(&haystack).map(|found| found.start()));
+}
+
+#[bench]
+fn scanner_wildcards(bencher: &mut Bencher) {
+    let haystack = haystack(&concrete(&WILDCARDS));
+
+    bencher.iter(|| WILDCARDS.find(&haystack));
+}
+
+// startup pays for building the regex, not just searching with it
+
+#[bench]
+fn regex_cold(bencher: &mut Bencher) {
+    let haystack = haystack(&concrete(&PATTERN));
+
+    bencher.iter(|| {
+        let pattern = Pattern::parse(PATTERN.source()).unwrap();
+
+        pattern.regex().find(&haystack).map(|found| found.start())
+    });
+}
+
+#[bench]
+fn scanner_cold(bencher: &mut Bencher) {
+    let haystack = haystack(&concrete(&PATTERN));
+
+    bencher.iter(|| {
+        let pattern = Pattern::parse(PATTERN.source()).unwrap();
+
+        pattern.find(&haystack)
+    });
+}
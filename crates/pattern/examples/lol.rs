@@ -1,6 +1,6 @@
 use providence_pattern::Pattern;
 
-const PATTERN: Pattern<48> = Pattern::new("48 8B 05 ?? ?? ?? ?? 8B 38 E8 ?? ?? ?? ?? 89 C7");
+const PATTERN: Pattern<16> = Pattern::new("48 8B 05 ?? ?? ?? ?? 8B 38 E8 ?? ?? ?? ?? 89 C7");
 
 fn main() {
     println!("Pattern {PATTERN:?}");
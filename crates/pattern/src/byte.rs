@@ -0,0 +1,114 @@
+use core::fmt;
+
+/// A single byte of a pattern, bits not set in the mask match anything.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Byte {
+    value: u8,
+    mask: u8,
+}
+
+impl Byte {
+    /// Matches any byte, `??`.
+    pub const WILDCARD: Byte = Byte::new(0x00, 0x00);
+
+    /// A byte where only the bits in `mask` are compared.
+    #[inline]
+    pub const fn new(value: u8, mask: u8) -> Self {
+        let value = value & mask;
+
+        Self { value, mask }
+    }
+
+    /// A byte which must match `value` exactly.
+    #[inline]
+    pub const fn exact(value: u8) -> Self {
+        Self::new(value, 0xFF)
+    }
+
+    /// The value to compare against, wildcard bits are zero.
+    #[inline]
+    pub const fn value(&self) -> u8 {
+        self.value
+    }
+
+    /// The bits that are compared.
+    #[inline]
+    pub const fn mask(&self) -> u8 {
+        self.mask
+    }
+
+    /// Whether every bit is compared.
+    #[inline]
+    pub const fn is_exact(&self) -> bool {
+        self.mask == 0xFF
+    }
+
+    /// Whether no bits are compared.
+    #[inline]
+    pub const fn is_wildcard(&self) -> bool {
+        self.mask == 0x00
+    }
+
+    /// Whether `byte` matches.
+    #[inline]
+    pub const fn matches(&self, byte: u8) -> bool {
+        byte & self.mask == self.value
+    }
+
+    /// The high and low nibble, `None` where the nibble is a wildcard.
+    #[inline]
+    pub const fn nibbles(&self) -> (Option<u8>, Option<u8>) {
+        let high = if self.mask & 0xF0 == 0xF0 {
+            Some(self.value >> 4)
+        } else {
+            None
+        };
+
+        let low = if self.mask & 0x0F == 0x0F {
+            Some(self.value & 0x0F)
+        } else {
+            None
+        };
+
+        (high, low)
+    }
+
+    /// Combine two nibbles, `None` being a wildcard.
+    #[inline]
+    pub const fn from_nibbles(high: Option<u8>, low: Option<u8>) -> Self {
+        let (high, high_mask) = match high {
+            Some(high) => (high << 4, 0xF0),
+            None => (0, 0),
+        };
+
+        let (low, low_mask) = match low {
+            Some(low) => (low & 0x0F, 0x0F),
+            None => (0, 0),
+        };
+
+        Self::new(high | low, high_mask | low_mask)
+    }
+}
+
+impl fmt::Debug for Byte {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (high, low) = self.nibbles();
+
+        write_nibble(fmt, high, '?')?;
+        write_nibble(fmt, low, '?')
+    }
+}
+
+/// Write an uppercase hex nibble, or `wildcard`.
+#[inline]
+pub(crate) fn write_nibble(
+    fmt: &mut dyn fmt::Write,
+    nibble: Option<u8>,
+    wildcard: char,
+) -> fmt::Result {
+    match nibble {
+        Some(nibble) => write!(fmt, "{nibble:X}"),
+        None => fmt.write_char(wildcard),
+    }
+}
@@ -0,0 +1,85 @@
+use core::fmt;
+
+/// Why a pattern was rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The pattern contains no bytes.
+    Empty,
+    /// A character that isn't valid in the pattern's style.
+    InvalidCharacter,
+    /// A hex digit is missing it's second half.
+    ExpectedCharacterInOctal,
+    /// A wildcard is missing it's second half.
+    ExpectedCharacterInWildcard,
+    /// Two bytes are not separated by a space.
+    ExpectedSpace,
+    /// The pattern starts with a space.
+    UnexpectedSpace,
+    /// The pattern ends with a space.
+    UnexpectedTrailingSpace,
+    /// The code and mask differ in length.
+    MaskLengthMismatch,
+    /// Code is expected to be written as `\xAA` escapes.
+    ExpectedEscape,
+}
+
+impl ErrorKind {
+    /// Returns a description of this error.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Empty => "empty pattern",
+            ErrorKind::InvalidCharacter => "invalid character encountered in pattern",
+            ErrorKind::ExpectedCharacterInOctal => "expected character in octal",
+            ErrorKind::ExpectedCharacterInWildcard => "expected character in wildcard",
+            ErrorKind::ExpectedSpace => "expected space",
+            ErrorKind::UnexpectedSpace => "expected a pattern not spaces",
+            ErrorKind::UnexpectedTrailingSpace => "unexpected trailing spaces",
+            ErrorKind::MaskLengthMismatch => "code and mask differ in length",
+            ErrorKind::ExpectedEscape => "expected an escape such as `\\x48`",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// An error produced while validating a pattern.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PatternError {
+    column: usize,
+    kind: ErrorKind,
+}
+
+impl PatternError {
+    #[inline]
+    pub(crate) const fn new(column: usize, kind: ErrorKind) -> Self {
+        Self { column, kind }
+    }
+
+    /// Returns the one-based column of the offending character.
+    #[inline]
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the reason the pattern was rejected.
+    #[inline]
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for PatternError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{} at column {}", self.kind, self.column)
+    }
+}
+
+impl std::error::Error for PatternError {}
@@ -0,0 +1,230 @@
+use crate::{Byte, DynPattern, Scanner, Style};
+use core::fmt;
+use elysium_mem::decode::{self, Instruction, Map};
+
+/// Longest pattern `generate` will produce.
+pub const MAX_LEN: usize = 64;
+
+/// Why a pattern couldn't be generated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GenerateError {
+    /// The offset is outside of the code.
+    OutOfBounds,
+    /// An instruction couldn't be decoded, at `offset` within the code, before the pattern was
+    /// unique.
+    Undecodable { offset: usize },
+    /// The code still wasn't unique after `len` bytes.
+    NotUnique { len: usize },
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::OutOfBounds => fmt.write_str("offset is out of bounds"),
+            GenerateError::Undecodable { offset } => {
+                write!(fmt, "unable to decode the instruction at {offset:#x}")
+            }
+            GenerateError::NotUnique { len } => write!(fmt, "not unique after {len} bytes"),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+/// Whether the immediate of `instruction` is likely an absolute address, which would be
+/// relocated when the library is loaded.
+#[inline]
+fn is_absolute(instruction: &Instruction) -> bool {
+    match instruction.immediate {
+        Some(immediate) => {
+            // mov r64, imm64, and mov rax, [moffs64]
+            immediate.size == 8
+                && instruction.map == Map::Primary
+                && matches!(instruction.opcode, 0xA0..=0xA3 | 0xB8..=0xBF)
+        }
+        None => false,
+    }
+}
+
+/// Convert an instruction to pattern bytes, wildcarding operands which depend on where the code
+/// is loaded, or where what it refers to is.
+fn push_instruction(bytes: &mut Vec<Byte>, code: &[u8], instruction: &Instruction) {
+    let start = bytes.len();
+
+    bytes.extend(code.iter().copied().map(Byte::exact));
+
+    let mut wildcard = |operand: decode::Operand| {
+        let offset = start + operand.offset as usize;
+
+        for byte in &mut bytes[offset..offset + operand.size as usize] {
+            *byte = Byte::WILDCARD;
+        }
+    };
+
+    if let Some(operand) = instruction.relative() {
+        // short branches stay within the function, they're as stable as the code around them
+        if operand.size == 4 {
+            wildcard(operand);
+        }
+    }
+
+    if is_absolute(instruction) {
+        if let Some(immediate) = instruction.immediate {
+            wildcard(immediate);
+        }
+    }
+}
+
+/// Count matches across `haystacks`, stopping at two.
+fn count(bytes: &[Byte], haystacks: &[&[u8]]) -> usize {
+    let scanner = Scanner::new(bytes);
+
+    haystacks
+        .iter()
+        .map(|haystack| scanner.find_iter(haystack).take(2).count())
+        .sum::<usize>()
+        .min(2)
+}
+
+/// Generate the shortest pattern that uniquely matches the code at `offset` within `code`.
+///
+/// `haystacks` is everywhere the pattern must be unique, which should include `code` itself,
+/// usually every executable segment of a library. Instructions are decoded from `offset` until
+/// the first `ret` or `jmp`, with `rel32` branches, `[rip + disp32]` operands and 64-bit
+/// absolute addresses wildcarded.
+///
+/// ```
+/// use providence_pattern::generate;
+///
+/// // push rbp; mov rbp, rsp; call rel32; ret
+/// let code = b"\x55\x48\x89\xE5\xE8\x01\x02\x03\x04\xC3\x55\x48\x89\xE5\xE8\x05\x06\x07\x08\x5D\xC3";
+/// let pattern = generate(code, 10, &[code]).unwrap();
+///
+/// assert_eq!(pattern.source(), "55 48 89 E5 E8 ?? ?? ?? ?? 5D");
+/// ```
+pub fn generate(
+    code: &[u8],
+    offset: usize,
+    haystacks: &[&[u8]],
+) -> Result<DynPattern, GenerateError> {
+    let function = code.get(offset..).ok_or(GenerateError::OutOfBounds)?;
+
+    if function.is_empty() {
+        return Err(GenerateError::OutOfBounds);
+    }
+
+    let mut bytes = Vec::with_capacity(MAX_LEN + 15);
+    let mut end = 0;
+    let mut finished = false;
+
+    for (start, instruction) in decode::instructions(function) {
+        end = start + instruction.len as usize;
+
+        push_instruction(&mut bytes, &function[start..end], &instruction);
+
+        if bytes.len() >= MAX_LEN || instruction.is_terminator() {
+            finished = true;
+
+            break;
+        }
+    }
+
+    bytes.truncate(MAX_LEN);
+
+    if bytes.is_empty() || count(&bytes, haystacks) != 1 {
+        // decoding stopped early, rather than at the end of the function
+        if !finished && end < function.len() {
+            return Err(GenerateError::Undecodable {
+                offset: offset + end,
+            });
+        }
+
+        return Err(GenerateError::NotUnique { len: bytes.len() });
+    }
+
+    // a longer pattern only ever has fewer matches, so search for the shortest unique length
+    let mut low = 1;
+    let mut high = bytes.len();
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        if count(&bytes[..middle], haystacks) == 1 {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    bytes.truncate(low);
+
+    let source = crate::Display::new(&bytes, Style::Elysium)
+        .to_string()
+        .into();
+
+    Ok(DynPattern::from_bytes(source, bytes.into_boxed_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 00: push rbp
+    // 01: mov rbp, rsp
+    // 04: lea rax, [rip + disp32]
+    // 0b: mov rcx, imm64
+    // 15: call rel32
+    // 1a: pop rbp
+    // 1b: ret
+    const FUNCTION: [u8; 28] = [
+        0x55, 0x48, 0x89, 0xE5, 0x48, 0x8D, 0x05, 0x01, 0x02, 0x03, 0x04, 0x48, 0xB9, 0x01, 0x02,
+        0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xE8, 0x01, 0x02, 0x03, 0x04, 0x5D, 0xC3,
+    ];
+
+    #[test]
+    fn wildcards() {
+        let mut code = FUNCTION.to_vec();
+
+        // same instructions with different operands, only unique by the last instruction
+        code.extend_from_slice(&FUNCTION[..26]);
+        code.extend_from_slice(&[0x59, 0xC3]);
+
+        let pattern = generate(&code, 0, &[&code]).unwrap();
+
+        assert_eq!(
+            pattern.source(),
+            "55 48 89 E5 48 8D 05 ?? ?? ?? ?? 48 B9 ?? ?? ?? ?? ?? ?? ?? ?? E8 ?? ?? ?? ?? 5D"
+        );
+    }
+
+    #[test]
+    fn shortest() {
+        let code = [&FUNCTION[..], &[0x55, 0x48, 0x89, 0xEC]].concat();
+        let pattern = generate(&code, 0, &[&code]).unwrap();
+
+        assert_eq!(pattern.source(), "55 48 89 E5");
+    }
+
+    #[test]
+    fn errors() {
+        let code = [&FUNCTION[..], &FUNCTION[..]].concat();
+
+        assert_eq!(
+            generate(&code, 0, &[&code]).unwrap_err(),
+            GenerateError::NotUnique { len: 28 }
+        );
+        assert_eq!(
+            generate(&code, code.len(), &[&code]).unwrap_err(),
+            GenerateError::OutOfBounds
+        );
+
+        // unique only past an invalid opcode
+        let code = [&[0x55, 0x06, 0x01][..], &[0x55, 0x06, 0x02]].concat();
+
+        assert_eq!(
+            generate(&code, 0, &[&code]).unwrap_err(),
+            GenerateError::Undecodable { offset: 1 }
+        );
+    }
+}
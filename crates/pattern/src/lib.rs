@@ -8,8 +8,21 @@ use std::lazy::SyncOnceCell;
 use std::marker::PhantomPinned;
 use std::{fmt, str};
 
+pub use byte::Byte;
+pub use error::{ErrorKind, PatternError};
+pub use generate::{generate, GenerateError};
+pub use resolve::{resolve, Memory, ResolveError, Resolved, Step};
+pub use scan::{FindIter, Scanner};
+pub use style::{Display, Style};
+
+mod byte;
+mod error;
+mod generate;
 mod panic;
 mod parse;
+mod resolve;
+mod scan;
+mod style;
 mod util;
 mod validate;
 
@@ -20,35 +33,92 @@ mod validate;
 
 pub struct Pattern<const N: usize> {
     source: &'static str,
-    pattern: [u8; N],
+    bytes: [Byte; N],
     regex: SyncOnceCell<Regex>,
+    scanner: SyncOnceCell<Scanner>,
     _pin: PhantomPinned,
 }
 
 impl<const N: usize> Pattern<N> {
     pub const fn new(pattern: &'static str) -> Pattern<N> {
         let source = pattern;
-        let pattern = parse::parse_pattern(source);
+        let bytes = parse::parse_pattern(source);
         let regex = SyncOnceCell::new();
+        let scanner = SyncOnceCell::new();
         let _pin = PhantomPinned;
 
         Self {
             source,
-            pattern,
+            bytes,
             regex,
+            scanner,
             _pin,
         }
     }
 
-    pub(crate) fn pattern(&self) -> &'static str {
-        let pattern = unsafe { str::from_utf8_unchecked(self.pattern.as_slice()) };
-        let pattern: &'static str = unsafe { util::change_lifetime(pattern) };
+    /// The pattern this was parsed from.
+    pub const fn source(&self) -> &'static str {
+        self.source
+    }
 
-        pattern
+    /// Amount of bytes this pattern matches.
+    pub const fn len(&self) -> usize {
+        N
     }
 
+    /// Whether this pattern matches no bytes.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    pub const fn bytes(&self) -> &[Byte] {
+        &self.bytes
+    }
+
+    /// A regex equivalent to this pattern, slower than `find`.
     pub fn regex(&self) -> &Regex {
-        self.regex.get_or_init(|| util::new_regex(self.pattern()))
+        self.regex.get_or_init(|| util::new_regex(&self.bytes))
+    }
+
+    pub fn scanner(&self) -> &Scanner {
+        self.scanner.get_or_init(|| Scanner::new(&self.bytes))
+    }
+
+    /// Find the offset of the first match within `haystack`.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        self.scanner().find(haystack)
+    }
+
+    /// Iterate the offsets of every match within `haystack`, including overlapping ones.
+    pub fn find_iter<'a, 'b>(&'a self, haystack: &'b [u8]) -> FindIter<'a, 'b> {
+        self.scanner().find_iter(haystack)
+    }
+
+    /// Format this pattern in `style`.
+    pub fn display(&self, style: Style) -> Display<'_> {
+        Display::new(&self.bytes, style)
+    }
+
+    /// Format this pattern as a code and mask pair, `None` if it contains nibble wildcards.
+    pub fn to_code_mask(&self) -> Option<(String, String)> {
+        style::to_code_mask(&self.bytes)
+    }
+}
+
+impl Pattern<0> {
+    /// Parse a pattern at runtime, inferring it's size.
+    ///
+    /// ```
+    /// use providence_pattern::{ErrorKind, Pattern};
+    ///
+    /// let pattern = Pattern::parse("48 8B 05 ?? ?? ?? ??").unwrap();
+    /// assert_eq!(pattern.len(), 7);
+    ///
+    /// let error = Pattern::parse("48 8B0").unwrap_err();
+    /// assert_eq!((error.column(), error.kind()), (6, ErrorKind::ExpectedSpace));
+    /// ```
+    pub fn parse(pattern: &str) -> Result<DynPattern, PatternError> {
+        DynPattern::parse(pattern)
     }
 }
 
@@ -57,3 +127,303 @@ impl<const N: usize> fmt::Debug for Pattern<N> {
         write!(fmt, "{}", self.source)
     }
 }
+
+/// A pattern, along with the steps to take from where it matches.
+///
+/// ```
+/// use providence_pattern::{Signature, Step};
+///
+/// // mov rax, [rip + disp32]
+/// const GLOBALS: Signature<7> = Signature::new(
+///     "48 8B 05 ?? ?? ?? ??",
+///     &[Step::Rip { disp: 3, len: 7 }, Step::Deref],
+/// );
+/// ```
+pub struct Signature<const N: usize> {
+    pattern: Pattern<N>,
+    steps: &'static [Step],
+}
+
+impl<const N: usize> Signature<N> {
+    pub const fn new(pattern: &'static str, steps: &'static [Step]) -> Self {
+        let pattern = Pattern::new(pattern);
+
+        Self { pattern, steps }
+    }
+
+    pub const fn pattern(&self) -> &Pattern<N> {
+        &self.pattern
+    }
+
+    pub const fn steps(&self) -> &'static [Step] {
+        self.steps
+    }
+
+    /// Apply this signature's steps to `address`, where the pattern matched.
+    pub fn resolve<M>(&self, memory: &M, address: usize) -> Result<Resolved, ResolveError>
+    where
+        M: Memory + ?Sized,
+    {
+        resolve(memory, address, self.steps)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Signature<N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.pattern, fmt)
+    }
+}
+
+/// A signature of any size, so signatures can be kept together, such as in a list of every
+/// signature.
+pub trait AnySignature: Sync {
+    /// The pattern this was parsed from.
+    fn source(&self) -> &'static str;
+
+    fn bytes(&self) -> &[Byte];
+
+    fn scanner(&self) -> &Scanner;
+
+    fn steps(&self) -> &'static [Step];
+
+    /// Apply this signature's steps to `address`, where the pattern matched.
+    fn resolve(&self, memory: &dyn Memory, address: usize) -> Result<Resolved, ResolveError> {
+        resolve(memory, address, self.steps())
+    }
+}
+
+impl<const N: usize> AnySignature for Signature<N> {
+    #[inline]
+    fn source(&self) -> &'static str {
+        self.pattern.source()
+    }
+
+    #[inline]
+    fn bytes(&self) -> &[Byte] {
+        self.pattern.bytes()
+    }
+
+    #[inline]
+    fn scanner(&self) -> &Scanner {
+        self.pattern.scanner()
+    }
+
+    #[inline]
+    fn steps(&self) -> &'static [Step] {
+        self.steps
+    }
+}
+
+impl fmt::Debug for dyn AnySignature {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.source())
+    }
+}
+
+/// A pattern parsed at runtime.
+pub struct DynPattern {
+    source: Box<str>,
+    bytes: Box<[Byte]>,
+    regex: SyncOnceCell<Regex>,
+    scanner: SyncOnceCell<Scanner>,
+}
+
+impl DynPattern {
+    fn from_bytes(source: Box<str>, bytes: Box<[Byte]>) -> Self {
+        let regex = SyncOnceCell::new();
+        let scanner = SyncOnceCell::new();
+
+        Self {
+            source,
+            bytes,
+            regex,
+            scanner,
+        }
+    }
+
+    /// Parse a pattern at runtime, inferring it's size.
+    pub fn parse(pattern: &str) -> Result<DynPattern, PatternError> {
+        Self::parse_with(pattern, Style::Elysium)
+    }
+
+    /// Parse a pattern written in `style`.
+    ///
+    /// ```
+    /// use providence_pattern::{DynPattern, Style};
+    ///
+    /// let ida = DynPattern::parse_with("48 8B 05 ? ? ? ?", Style::Ida).unwrap();
+    /// let x64dbg = DynPattern::parse_with("488B05????????", Style::X64dbg).unwrap();
+    ///
+    /// assert_eq!(ida.bytes(), x64dbg.bytes());
+    /// assert_eq!(ida.display(Style::CheatEngine).to_string(), "48 8B 05 * * * *");
+    /// ```
+    pub fn parse_with(pattern: &str, style: Style) -> Result<DynPattern, PatternError> {
+        let bytes = style::parse(pattern, style)?;
+
+        Ok(Self::from_bytes(pattern.into(), bytes))
+    }
+
+    /// Create a pattern from code and a mask, where `x` compares a byte and `?` ignores it.
+    pub fn from_code_mask(code: &[u8], mask: &str) -> Result<DynPattern, PatternError> {
+        let bytes = style::parse_code_mask(code, mask)?;
+        let source = Display::new(&bytes, Style::Elysium).to_string().into();
+
+        Ok(Self::from_bytes(source, bytes))
+    }
+
+    /// Parse code written as escapes, such as `\x48\x8B\x05`, and a mask.
+    ///
+    /// Columns in errors refer to the code, unless they concern the mask.
+    pub fn parse_code_mask(code: &str, mask: &str) -> Result<DynPattern, PatternError> {
+        let code = style::parse_escaped(code)?;
+
+        Self::from_code_mask(&code, mask)
+    }
+
+    /// The pattern this was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Amount of bytes this pattern matches.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether this pattern matches no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn bytes(&self) -> &[Byte] {
+        &self.bytes
+    }
+
+    /// A regex equivalent to this pattern, slower than `find`.
+    pub fn regex(&self) -> &Regex {
+        self.regex.get_or_init(|| util::new_regex(&self.bytes))
+    }
+
+    pub fn scanner(&self) -> &Scanner {
+        self.scanner.get_or_init(|| Scanner::new(&self.bytes))
+    }
+
+    /// Find the offset of the first match within `haystack`.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        self.scanner().find(haystack)
+    }
+
+    /// Iterate the offsets of every match within `haystack`, including overlapping ones.
+    pub fn find_iter<'a, 'b>(&'a self, haystack: &'b [u8]) -> FindIter<'a, 'b> {
+        self.scanner().find_iter(haystack)
+    }
+
+    /// Format this pattern in `style`.
+    pub fn display(&self, style: Style) -> Display<'_> {
+        Display::new(&self.bytes, style)
+    }
+
+    /// Format this pattern as a code and mask pair, `None` if it contains nibble wildcards.
+    pub fn to_code_mask(&self) -> Option<(String, String)> {
+        style::to_code_mask(&self.bytes)
+    }
+}
+
+impl str::FromStr for DynPattern {
+    type Err = PatternError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        DynPattern::parse(pattern)
+    }
+}
+
+impl fmt::Debug for DynPattern {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATTERN: Pattern<16> = Pattern::new("48 8B 05 ?? ?? ?? ?? 8B 38 E8 ?? ?? ?? ?? 89 C7");
+
+    fn error(pattern: &str) -> (usize, ErrorKind) {
+        let error = Pattern::parse(pattern).unwrap_err();
+
+        (error.column(), error.kind())
+    }
+
+    #[test]
+    fn same_as_const() {
+        let pattern = Pattern::parse(PATTERN.source).unwrap();
+
+        assert_eq!(pattern.regex().as_str(), PATTERN.regex().as_str());
+        assert_eq!(pattern.bytes(), PATTERN.bytes());
+    }
+
+    #[test]
+    fn nibbles() {
+        let pattern = Pattern::parse("4? ?b 8b").unwrap();
+
+        assert_eq!(pattern.bytes()[0], Byte::new(0x40, 0xF0));
+        assert_eq!(pattern.bytes()[1], Byte::new(0x0B, 0x0F));
+        assert_eq!(pattern.bytes()[2], Byte::exact(0x8B));
+        assert!(pattern.regex().is_match(b"\x4F\xAB\x8B"));
+        assert!(!pattern.regex().is_match(b"\x5F\xAB\x8B"));
+    }
+
+    #[test]
+    fn styles() {
+        let styles = [
+            (Style::Elysium, "48 8B 05 ?? ?? ?? ?? E8 4?"),
+            (Style::Ida, "48 8B 05 ? ? ? ? E8 4?"),
+            (Style::X64dbg, "488B05????????E84?"),
+            (Style::CheatEngine, "48 8B 05 * * * * E8 4?"),
+        ];
+
+        let expected = Pattern::parse(styles[0].1).unwrap();
+
+        for (style, source) in styles {
+            let pattern = DynPattern::parse_with(source, style).unwrap();
+
+            assert_eq!(pattern.bytes(), expected.bytes());
+
+            for (style, source) in styles {
+                assert_eq!(pattern.display(style).to_string(), source);
+            }
+        }
+    }
+
+    #[test]
+    fn code_mask() {
+        let pattern =
+            DynPattern::parse_code_mask("\\x48\\x8B\\x05\\x00\\x00\\x00\\x00", "xxx????").unwrap();
+        let (code, mask) = pattern.to_code_mask().unwrap();
+
+        assert_eq!(pattern.bytes(), &PATTERN.bytes()[..7]);
+        assert_eq!(code, "\\x48\\x8B\\x05\\x00\\x00\\x00\\x00");
+        assert_eq!(mask, "xxx????");
+
+        let error = DynPattern::from_code_mask(b"\x48\x8B", "xxx").unwrap_err();
+
+        assert_eq!(
+            (error.column(), error.kind()),
+            (3, ErrorKind::MaskLengthMismatch)
+        );
+        assert!(Pattern::parse("4?").unwrap().to_code_mask().is_none());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error(""), (1, ErrorKind::Empty));
+        assert_eq!(error(" 48"), (1, ErrorKind::UnexpectedSpace));
+        assert_eq!(error("48 8G"), (5, ErrorKind::InvalidCharacter));
+        assert_eq!(error("48 8"), (5, ErrorKind::ExpectedCharacterInOctal));
+        assert_eq!(error("48 ?"), (5, ErrorKind::ExpectedCharacterInWildcard));
+        assert_eq!(error("488B"), (3, ErrorKind::ExpectedSpace));
+        assert_eq!(error("48  8B"), (4, ErrorKind::UnexpectedSpace));
+        assert_eq!(error("48 8B "), (6, ErrorKind::UnexpectedTrailingSpace));
+    }
+}
@@ -1,3 +1,9 @@
+use crate::{ErrorKind, PatternError};
+
+pub const fn empty() -> ! {
+    panic!("empty pattern");
+}
+
 pub const fn invalid_character() -> ! {
     panic!("invalid character encountered in pattern");
 }
@@ -6,18 +12,10 @@ pub const fn expected_character_in_octal() -> ! {
     panic!("expected character in octal");
 }
 
-pub const fn unexpected_character_in_octal() -> ! {
-    panic!("unexpected character in octal");
-}
-
 pub const fn expected_character_in_wildcard() -> ! {
     panic!("expected character in wildcard");
 }
 
-pub const fn unexpected_character_in_wildcard() -> ! {
-    panic!("unexpected character in wildcard");
-}
-
 pub const fn expected_space() -> ! {
     panic!("expected space");
 }
@@ -37,3 +35,18 @@ pub const fn decrease_size() -> ! {
 pub const fn increase_size() -> ! {
     panic!("increase the pattern size");
 }
+
+/// Const contexts can't format, so map the error onto a fixed message.
+pub const fn error(error: PatternError) -> ! {
+    match error.kind() {
+        ErrorKind::Empty => empty(),
+        ErrorKind::InvalidCharacter => invalid_character(),
+        ErrorKind::ExpectedCharacterInOctal => expected_character_in_octal(),
+        ErrorKind::ExpectedCharacterInWildcard => expected_character_in_wildcard(),
+        ErrorKind::ExpectedSpace => expected_space(),
+        ErrorKind::UnexpectedSpace => unexpected_space(),
+        ErrorKind::UnexpectedTrailingSpace => unexpected_trailing_space(),
+        // only produced by runtime styles
+        _ => invalid_character(),
+    }
+}
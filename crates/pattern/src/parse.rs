@@ -1,11 +1,5 @@
-use crate::{panic, validate};
-use core::ptr;
-
-const fn enough_for(j: usize, n: usize) {
-    if j > n {
-        panic::increase_size();
-    }
-}
+use crate::validate;
+use crate::{panic, Byte};
 
 const fn reduce(j: usize, n: usize) {
     if j < n {
@@ -15,83 +9,48 @@ const fn reduce(j: usize, n: usize) {
     }
 }
 
-const PREFIX: [u8; 8] = *b"(?msx-u)";
-
-pub const fn parse_pattern<const N: usize>(pattern: &'static str) -> [u8; N] {
-    use validate::Kind::{Digit, Wildcard};
-
-    if N < 9 {
-        panic::increase_size();
-    }
-
-    validate::validate_pattern(pattern);
-
-    let src = pattern.as_ptr();
+/// Translate a validated pattern into bytes, `dst` must be exactly as long as the pattern.
+pub const fn write_pattern(pattern: &str, dst: &mut [Byte]) {
+    let src = pattern.as_bytes();
+    let len = src.len();
     let mut i = 0;
-    let len = pattern.len();
-    let rem = len % 3;
-
-    let mut pattern = [0_u8; N];
-    let dst = pattern.as_mut_ptr();
-    let mut j = 8;
+    let mut j = 0;
 
-    unsafe {
-        ptr::copy_nonoverlapping(PREFIX.as_ptr(), dst, 8);
-    }
-
-    while i + 2 < len {
-        let [a, b, _] = unsafe { *(src.add(i) as *const [u8; 3]) };
-
-        match validate::kind_of_pair(a, b) {
-            (Digit, Digit) => unsafe {
-                enough_for(j + 4, N);
-
-                dst.add(j + 0).write(b'\\');
-                dst.add(j + 1).write(b'x');
-                dst.add(j + 2).write(a | 32);
-                dst.add(j + 3).write(b | 32);
-
-                j += 4;
-            },
-            (Wildcard, Wildcard) => unsafe {
-                enough_for(j + 1, N);
-
-                dst.add(j).write(b'.');
+    // the validator ensures pairs are always followed by a space or the end of the pattern
+    while i + 1 < len {
+        let high = validate::nibble_of(src[i]);
+        let low = validate::nibble_of(src[i + 1]);
 
-                j += 1;
-            },
-            _ => {}
-        }
+        dst[j] = Byte::from_nibbles(high, low);
 
         i += 3;
+        j += 1;
     }
+}
 
-    if rem == 2 {
-        let [a, b] = unsafe { *(src.add(i) as *const [u8; 2]) };
+/// Validate `pattern`, panicking with the reason if it is invalid.
+pub const fn validate_or_panic(pattern: &str) -> usize {
+    match validate::validate_pattern(pattern) {
+        Ok(len) => len,
+        Err(error) => panic::error(error),
+    }
+}
 
-        match validate::kind_of_pair(a, b) {
-            (Digit, Digit) => unsafe {
-                enough_for(j + 4, N);
+pub const fn parse_pattern<const N: usize>(pattern: &'static str) -> [Byte; N] {
+    reduce(validate_or_panic(pattern), N);
 
-                dst.add(j + 0).write(b'\\');
-                dst.add(j + 1).write(b'x');
-                dst.add(j + 2).write(a | 32);
-                dst.add(j + 3).write(b | 32);
+    let mut bytes = [Byte::WILDCARD; N];
 
-                j += 4;
-            },
-            (Wildcard, Wildcard) => unsafe {
-                enough_for(j + 1, N);
+    write_pattern(pattern, &mut bytes);
 
-                dst.add(j).write(b'.');
+    bytes
+}
 
-                j += 1;
-            },
-            _ => {}
-        }
-    }
+/// Translate an already validated `pattern` into bytes at runtime.
+pub fn parse_dyn_pattern(pattern: &str, len: usize) -> Box<[Byte]> {
+    let mut bytes = vec![Byte::WILDCARD; len];
 
-    reduce(j, N);
+    write_pattern(pattern, &mut bytes);
 
-    pattern
+    bytes.into_boxed_slice()
 }
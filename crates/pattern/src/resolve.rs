@@ -0,0 +1,175 @@
+use core::fmt;
+
+/// A step taken after a pattern matches, to get from the match to what we're actually after.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Step {
+    /// Move the address by a number of bytes.
+    Add(isize),
+    /// Read a pointer at the address.
+    Deref,
+    /// Follow the `call rel32` (`E8`) or `jmp rel32` (`E9`) at the address.
+    Branch,
+    /// Follow a `[rip + disp32]` operand of the instruction at the address, where `disp` is the
+    /// offset of the displacement within the instruction, and `len` is the instruction's length.
+    Rip { disp: usize, len: usize },
+    /// Read a `u32` at the address, this ends the chain.
+    U32,
+}
+
+/// What a chain of steps resolved to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Resolved {
+    Address(usize),
+    Value(u32),
+}
+
+/// Why a chain of steps couldn't be resolved.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ResolveError {
+    /// Memory at `address` couldn't be read.
+    Unreadable { address: usize },
+    /// `Step::Branch` found something other than `E8` or `E9`.
+    UnexpectedOpcode { address: usize, opcode: u8 },
+    /// A step follows `Step::U32`, which produces a value rather than an address.
+    ExpectedAddress,
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::Unreadable { address } => write!(fmt, "unable to read {address:#x}"),
+            ResolveError::UnexpectedOpcode { address, opcode } => write!(
+                fmt,
+                "expected a `call` or `jmp` at {address:#x}, found {opcode:02X}"
+            ),
+            ResolveError::ExpectedAddress => fmt.write_str("step follows a value"),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// Memory that steps are resolved against.
+pub trait Memory {
+    /// Fill `bytes` with the memory at `address`, `false` if any of it is unreadable.
+    fn read(&self, address: usize, bytes: &mut [u8]) -> bool;
+}
+
+#[inline]
+fn read<M, const N: usize>(memory: &M, address: usize) -> Result<[u8; N], ResolveError>
+where
+    M: Memory + ?Sized,
+{
+    let mut bytes = [0; N];
+
+    if memory.read(address, &mut bytes) {
+        Ok(bytes)
+    } else {
+        Err(ResolveError::Unreadable { address })
+    }
+}
+
+#[inline]
+fn relative(address: usize, displacement: [u8; 4], len: usize) -> usize {
+    let displacement = i32::from_le_bytes(displacement) as isize;
+
+    (address.wrapping_add(len) as isize).wrapping_add(displacement) as usize
+}
+
+/// Apply `steps` to `address`.
+pub fn resolve<M>(memory: &M, address: usize, steps: &[Step]) -> Result<Resolved, ResolveError>
+where
+    M: Memory + ?Sized,
+{
+    let mut resolved = Resolved::Address(address);
+
+    for step in steps {
+        let address = match resolved {
+            Resolved::Address(address) => address,
+            Resolved::Value(_) => return Err(ResolveError::ExpectedAddress),
+        };
+
+        resolved = match *step {
+            Step::Add(offset) => {
+                Resolved::Address((address as isize).wrapping_add(offset) as usize)
+            }
+            Step::Deref => Resolved::Address(usize::from_ne_bytes(read(memory, address)?)),
+            Step::Branch => {
+                let [opcode, displacement @ ..] = read::<M, 5>(memory, address)?;
+
+                if !matches!(opcode, 0xE8 | 0xE9) {
+                    return Err(ResolveError::UnexpectedOpcode { address, opcode });
+                }
+
+                Resolved::Address(relative(address, displacement, 5))
+            }
+            Step::Rip { disp, len } => {
+                let displacement = read(memory, address.wrapping_add(disp))?;
+
+                Resolved::Address(relative(address, displacement, len))
+            }
+            Step::U32 => Resolved::Value(u32::from_le_bytes(read(memory, address)?)),
+        };
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Memory for [u8] {
+        fn read(&self, address: usize, bytes: &mut [u8]) -> bool {
+            match self.get(address..address.wrapping_add(bytes.len())) {
+                Some(slice) => {
+                    bytes.copy_from_slice(slice);
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    // 0: call 8
+    // 5: mov rax, [rip + 1]
+    // d: 0xDEADBEEF
+    const CODE: [u8; 20] = [
+        0xE8, 0x03, 0x00, 0x00, 0x00, 0x48, 0x8B, 0x05, 0x01, 0x00, 0x00, 0x00, 0x90, 0xEF, 0xBE,
+        0xAD, 0xDE, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn steps() {
+        let code = &CODE[..];
+
+        assert_eq!(
+            resolve(code, 0, &[Step::Branch]),
+            Ok(Resolved::Address(0x8))
+        );
+        assert_eq!(
+            resolve(
+                code,
+                0,
+                &[Step::Add(5), Step::Rip { disp: 3, len: 7 }, Step::U32]
+            ),
+            Ok(Resolved::Value(0xDEADBEEF))
+        );
+        assert_eq!(
+            resolve(code, 0, &[Step::Add(13), Step::U32, Step::Deref]),
+            Err(ResolveError::ExpectedAddress)
+        );
+        assert_eq!(
+            resolve(code, 5, &[Step::Branch]),
+            Err(ResolveError::UnexpectedOpcode {
+                address: 5,
+                opcode: 0x48
+            })
+        );
+        assert_eq!(
+            resolve(code, 0, &[Step::Add(18), Step::U32]),
+            Err(ResolveError::Unreadable { address: 18 })
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use crate::Byte;
+use memchr::memmem::Finder;
+
+/// Searches memory for a pattern without a regex.
+///
+/// The longest run of exact bytes is used as an anchor, which is found with `memchr`'s SIMD
+/// accelerated substring search, the remaining bytes are then compared at each candidate.
+pub struct Scanner {
+    bytes: Box<[Byte]>,
+    anchor: Anchor,
+}
+
+enum Anchor {
+    /// The pattern is entirely wildcards, anything long enough matches.
+    None,
+    /// A single exact byte, at `offset` within the pattern.
+    Byte { offset: usize, byte: u8 },
+    /// A run of exact bytes, at `offset` within the pattern.
+    Run {
+        offset: usize,
+        finder: Box<Finder<'static>>,
+    },
+}
+
+/// Find the longest run of exact bytes.
+fn longest_run(bytes: &[Byte]) -> (usize, usize) {
+    let mut best = (0, 0);
+    let mut start = 0;
+
+    for (index, byte) in bytes.iter().enumerate() {
+        if !byte.is_exact() {
+            start = index + 1;
+
+            continue;
+        }
+
+        let len = index + 1 - start;
+
+        if len > best.1 {
+            best = (start, len);
+        }
+    }
+
+    best
+}
+
+impl Scanner {
+    pub fn new(bytes: &[Byte]) -> Self {
+        let (offset, len) = longest_run(bytes);
+        let anchor = match len {
+            0 => Anchor::None,
+            1 => Anchor::Byte {
+                offset,
+                byte: bytes[offset].value(),
+            },
+            _ => {
+                let needle: Vec<u8> = bytes[offset..offset + len]
+                    .iter()
+                    .map(Byte::value)
+                    .collect();
+
+                Anchor::Run {
+                    offset,
+                    finder: Box::new(Finder::new(&needle).into_owned()),
+                }
+            }
+        };
+
+        let bytes = bytes.into();
+
+        Self { bytes, anchor }
+    }
+
+    /// Amount of bytes the pattern matches.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the pattern matches no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Whether the pattern matches at the start of `haystack`.
+    #[inline]
+    pub fn matches(&self, haystack: &[u8]) -> bool {
+        haystack.len() >= self.bytes.len()
+            && self
+                .bytes
+                .iter()
+                .zip(haystack)
+                .all(|(byte, value)| byte.matches(*value))
+    }
+
+    /// Find the offset of the first match within `haystack`, starting from `start`.
+    pub fn find_from(&self, haystack: &[u8], start: usize) -> Option<usize> {
+        let len = self.bytes.len();
+        let last = haystack.len().checked_sub(len)?;
+        let mut start = start;
+
+        while start <= last {
+            // candidate start of the pattern
+            let candidate = match &self.anchor {
+                Anchor::None => start,
+                Anchor::Byte { offset, byte } => {
+                    let from = start + offset;
+                    let to = last + offset + 1;
+
+                    memchr::memchr(*byte, &haystack[from..to])? + start
+                }
+                Anchor::Run { offset, finder } => {
+                    let from = start + offset;
+                    let to = last + len;
+
+                    finder.find(&haystack[from..to])? + start
+                }
+            };
+
+            if candidate > last {
+                return None;
+            }
+
+            if self.matches(&haystack[candidate..]) {
+                return Some(candidate);
+            }
+
+            start = candidate + 1;
+        }
+
+        None
+    }
+
+    /// Find the offset of the first match within `haystack`.
+    #[inline]
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        self.find_from(haystack, 0)
+    }
+
+    /// Iterate the offsets of every match within `haystack`, including overlapping ones.
+    #[inline]
+    pub fn find_iter<'a, 'b>(&'a self, haystack: &'b [u8]) -> FindIter<'a, 'b> {
+        FindIter {
+            scanner: self,
+            haystack,
+            start: 0,
+        }
+    }
+}
+
+/// Iterator over the offsets of every match, see `Scanner::find_iter`.
+pub struct FindIter<'a, 'b> {
+    scanner: &'a Scanner,
+    haystack: &'b [u8],
+    start: usize,
+}
+
+impl<'a, 'b> Iterator for FindIter<'a, 'b> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let offset = self.scanner.find_from(self.haystack, self.start)?;
+
+        self.start = offset + 1;
+
+        Some(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Pattern;
+
+    #[test]
+    fn find() {
+        let pattern = Pattern::parse("48 8B 05 ?? ?? ?? ?? 8B 38 E8 ?? ?? ?? ?? 89 C7").unwrap();
+        let bytes = b"hello\x48\x8B\x05xxxx\x8B\x38\xE8xxxx\x89\xC7world";
+
+        assert_eq!(pattern.find(bytes), Some(5));
+        assert_eq!(pattern.find(&bytes[6..]), None);
+        assert_eq!(pattern.find(&bytes[..bytes.len() - 6]), None);
+    }
+
+    #[test]
+    fn anchors() {
+        let haystack = b"\x00\x4F\x11\xE8\x4A\x22\xE8";
+
+        // single byte anchor, nibble wildcard
+        assert_eq!(Pattern::parse("E8 4?").unwrap().find(haystack), Some(3));
+        // no anchor at all
+        assert_eq!(Pattern::parse("?? ??").unwrap().find(haystack), Some(0));
+        assert_eq!(Pattern::parse("?? ??").unwrap().find(&haystack[..1]), None);
+        // anchor in the middle, with the first candidate failing to match
+        assert_eq!(Pattern::parse("?2 E8").unwrap().find(haystack), Some(5));
+    }
+
+    #[test]
+    fn find_iter() {
+        let haystack = b"\xE8\xE8\xE8\x00\xE8\xE8";
+        let pattern = Pattern::parse("E8 E8").unwrap();
+
+        assert_eq!(pattern.find_iter(haystack).collect::<Vec<_>>(), [0, 1, 4]);
+        assert_eq!(pattern.find_iter(&haystack[..1]).count(), 0);
+    }
+}
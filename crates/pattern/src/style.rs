@@ -0,0 +1,239 @@
+use crate::byte::{self, Byte};
+use crate::error::{ErrorKind, PatternError};
+use crate::validate::{self, error};
+use core::fmt;
+
+/// Textual signature formats.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Style {
+    /// `48 8B 05 ?? ?? ?? ??`, the format used by `Pattern::new`.
+    Elysium,
+    /// `48 8B 05 ? ? ? ?`, as copied from IDA.
+    Ida,
+    /// `488B05????????`, as copied from x64dbg.
+    X64dbg,
+    /// `48 8B 05 * * * *`, as copied from Cheat Engine.
+    CheatEngine,
+}
+
+impl Style {
+    /// Written for a byte that is entirely a wildcard.
+    #[inline]
+    const fn wildcard(&self) -> &'static str {
+        match self {
+            Style::Elysium | Style::X64dbg => "??",
+            Style::Ida => "?",
+            Style::CheatEngine => "*",
+        }
+    }
+
+    /// Written between bytes.
+    #[inline]
+    const fn separator(&self) -> &'static str {
+        match self {
+            Style::X64dbg => "",
+            _ => " ",
+        }
+    }
+
+    /// Whether `token` is an entire wildcard byte.
+    #[inline]
+    fn is_wildcard(&self, token: &str) -> bool {
+        match self {
+            Style::Elysium | Style::X64dbg => token == "??",
+            Style::Ida => matches!(token, "?" | "??"),
+            Style::CheatEngine => matches!(token, "?" | "??" | "*" | "**"),
+        }
+    }
+
+    /// Whether `character` is a wildcard nibble.
+    #[inline]
+    fn is_wildcard_nibble(&self, character: u8) -> bool {
+        match self {
+            Style::CheatEngine => matches!(character, b'?' | b'*'),
+            _ => character == b'?',
+        }
+    }
+}
+
+/// Parse one nibble of a byte.
+#[inline]
+fn nibble(style: Style, index: usize, character: u8) -> Result<Option<u8>, PatternError> {
+    if style.is_wildcard_nibble(character) {
+        return Ok(None);
+    }
+
+    match validate::nibble_of(character) {
+        Some(nibble) => Ok(Some(nibble)),
+        None => Err(error(index, ErrorKind::InvalidCharacter)),
+    }
+}
+
+/// Parse a pattern written in `style`.
+pub fn parse(pattern: &str, style: Style) -> Result<Box<[Byte]>, PatternError> {
+    if style == Style::Elysium {
+        let len = validate::validate_pattern(pattern)?;
+
+        return Ok(crate::parse::parse_dyn_pattern(pattern, len));
+    }
+
+    let mut bytes = Vec::new();
+    let mut index = 0;
+
+    for token in pattern.split(' ') {
+        let start = index;
+
+        index += token.len() + 1;
+
+        // tolerate repeated spaces, copied signatures are often sloppy
+        if token.is_empty() {
+            continue;
+        }
+
+        if style.is_wildcard(token) {
+            bytes.push(Byte::WILDCARD);
+
+            continue;
+        }
+
+        let characters = token.as_bytes();
+
+        // x64dbg doesn't require spaces between bytes
+        if style != Style::X64dbg && characters.len() > 2 {
+            return Err(error(start + 2, ErrorKind::ExpectedSpace));
+        }
+
+        for (offset, pair) in characters.chunks(2).enumerate() {
+            let index = start + offset * 2;
+            let high = nibble(style, index, pair[0])?;
+
+            let low = match pair.get(1) {
+                Some(&character) => nibble(style, index + 1, character)?,
+                None if high.is_some() => {
+                    return Err(error(index + 1, ErrorKind::ExpectedCharacterInOctal))
+                }
+                None => return Err(error(index + 1, ErrorKind::ExpectedCharacterInWildcard)),
+            };
+
+            bytes.push(Byte::from_nibbles(high, low));
+        }
+    }
+
+    if bytes.is_empty() {
+        return Err(error(0, ErrorKind::Empty));
+    }
+
+    Ok(bytes.into_boxed_slice())
+}
+
+/// Parse a code and mask pair, where `x` in `mask` compares the byte in `code` and `?` does not.
+pub fn parse_code_mask(code: &[u8], mask: &str) -> Result<Box<[Byte]>, PatternError> {
+    if mask.is_empty() {
+        return Err(error(0, ErrorKind::Empty));
+    }
+
+    if code.len() != mask.len() {
+        let index = code.len().min(mask.len());
+
+        return Err(error(index, ErrorKind::MaskLengthMismatch));
+    }
+
+    code.iter()
+        .zip(mask.bytes())
+        .enumerate()
+        .map(|(index, (&value, mask))| match mask {
+            b'x' | b'X' => Ok(Byte::exact(value)),
+            b'?' => Ok(Byte::WILDCARD),
+            _ => Err(error(index, ErrorKind::InvalidCharacter)),
+        })
+        .collect()
+}
+
+/// Parse the text of an escaped string, such as `\x48\x8B\x05`, into it's bytes.
+pub fn parse_escaped(code: &str) -> Result<Vec<u8>, PatternError> {
+    let characters = code.as_bytes();
+    let mut bytes = Vec::with_capacity(characters.len() / 4);
+    let mut index = 0;
+
+    while index < characters.len() {
+        if !characters[index..].starts_with(b"\\x") {
+            return Err(error(index, ErrorKind::ExpectedEscape));
+        }
+
+        let high = characters
+            .get(index + 2)
+            .copied()
+            .and_then(validate::nibble_of);
+        let low = characters
+            .get(index + 3)
+            .copied()
+            .and_then(validate::nibble_of);
+
+        match (high, low) {
+            (Some(high), Some(low)) => bytes.push(high << 4 | low),
+            (None, _) => return Err(error(index + 2, ErrorKind::ExpectedCharacterInOctal)),
+            (_, None) => return Err(error(index + 3, ErrorKind::ExpectedCharacterInOctal)),
+        }
+
+        index += 4;
+    }
+
+    Ok(bytes)
+}
+
+/// Formats bytes in a particular style.
+pub struct Display<'a> {
+    bytes: &'a [Byte],
+    style: Style,
+}
+
+impl<'a> Display<'a> {
+    #[inline]
+    pub(crate) fn new(bytes: &'a [Byte], style: Style) -> Self {
+        Self { bytes, style }
+    }
+}
+
+impl<'a> fmt::Display for Display<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = self.style;
+
+        for (index, byte) in self.bytes.iter().enumerate() {
+            if index != 0 {
+                fmt.write_str(style.separator())?;
+            }
+
+            if byte.is_wildcard() {
+                fmt.write_str(style.wildcard())?;
+            } else {
+                let (high, low) = byte.nibbles();
+
+                byte::write_nibble(fmt, high, '?')?;
+                byte::write_nibble(fmt, low, '?')?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Format bytes as a code and mask pair, `None` if any byte is partially a wildcard.
+pub fn to_code_mask(bytes: &[Byte]) -> Option<(String, String)> {
+    let mut code = String::with_capacity(bytes.len() * 4);
+    let mut mask = String::with_capacity(bytes.len());
+
+    for byte in bytes {
+        if byte.is_exact() {
+            mask.push('x');
+        } else if byte.is_wildcard() {
+            mask.push('?');
+        } else {
+            return None;
+        }
+
+        code.push_str(&format!("\\x{:02X}", byte.value()));
+    }
+
+    Some((code, mask))
+}
@@ -1,12 +1,35 @@
+use crate::Byte;
+use core::fmt::Write;
 use regex::bytes::Regex;
 
-pub const unsafe fn change_lifetime<'a, 'b, T>(a: &'a T) -> &'b T
-where
-    T: ?Sized,
-{
-    &*(a as *const T)
+const PREFIX: &str = "(?msx-u)";
+
+/// Translate bytes into an equivalent regex.
+pub fn regex_source(bytes: &[Byte]) -> String {
+    let mut regex = String::with_capacity(PREFIX.len() + bytes.len() * 4);
+
+    regex.push_str(PREFIX);
+
+    for byte in bytes {
+        if byte.is_exact() {
+            let _ = write!(regex, "\\x{:02x}", byte.value());
+        } else if byte.is_wildcard() {
+            regex.push('.');
+        } else {
+            // nibble wildcards become a class of every byte they match
+            regex.push('[');
+
+            for value in (0..=u8::MAX).filter(|value| byte.matches(*value)) {
+                let _ = write!(regex, "\\x{value:02x}");
+            }
+
+            regex.push(']');
+        }
+    }
+
+    regex
 }
 
-pub fn new_regex(pattern: &'static str) -> Regex {
-    unsafe { Regex::new(pattern).unwrap_unchecked() }
+pub fn new_regex(bytes: &[Byte]) -> Regex {
+    unsafe { Regex::new(&regex_source(bytes)).unwrap_unchecked() }
 }
@@ -1,17 +1,18 @@
-use crate::panic;
+use crate::error::{ErrorKind, PatternError};
 
 pub enum Kind {
     Digit,
     Space,
     Wildcard,
+    Invalid,
 }
 
 pub const fn kind_of(byte: u8) -> Kind {
     match byte {
-        b'A'..=b'F' | b'0'..=b'9' => Kind::Digit,
+        b'A'..=b'F' | b'a'..=b'f' | b'0'..=b'9' => Kind::Digit,
         b' ' => Kind::Space,
         b'?' => Kind::Wildcard,
-        _ => panic::invalid_character(),
+        _ => Kind::Invalid,
     }
 }
 
@@ -19,39 +20,59 @@ pub const fn kind_of_pair(a: u8, b: u8) -> (Kind, Kind) {
     (kind_of(a), kind_of(b))
 }
 
-const fn validate_two(a: u8, b: u8) {
-    use Kind::{Digit, Space, Wildcard};
+/// Value of a hex digit, `None` for a wildcard.
+pub const fn nibble_of(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        _ => None,
+    }
+}
+
+/// `index` is zero-based, columns are one-based.
+pub const fn error(index: usize, kind: ErrorKind) -> PatternError {
+    PatternError::new(index + 1, kind)
+}
+
+const fn validate_two(index: usize, a: u8, b: u8) -> Result<(), PatternError> {
+    use Kind::{Digit, Invalid, Space, Wildcard};
 
     match kind_of_pair(a, b) {
-        (Digit, Space) => panic::expected_character_in_octal(),
-        (Digit, Wildcard) => panic::unexpected_character_in_octal(),
-        (Wildcard, Digit) => panic::unexpected_character_in_wildcard(),
-        (Wildcard, Space) => panic::expected_character_in_wildcard(),
-        _ => {}
+        (Invalid, _) => Err(error(index, ErrorKind::InvalidCharacter)),
+        (Space, _) => Err(error(index, ErrorKind::UnexpectedSpace)),
+        (_, Invalid) => Err(error(index + 1, ErrorKind::InvalidCharacter)),
+        (Digit, Space) => Err(error(index + 1, ErrorKind::ExpectedCharacterInOctal)),
+        (Wildcard, Space) => Err(error(index + 1, ErrorKind::ExpectedCharacterInWildcard)),
+        // `4?` and `?4` match on a single nibble
+        (Digit | Wildcard, Digit | Wildcard) => Ok(()),
     }
 }
 
-const fn validate_three(a: u8, b: u8, c: u8) {
-    validate_two(a, b);
+const fn validate_three(index: usize, a: u8, b: u8, c: u8) -> Result<(), PatternError> {
+    if let Err(error) = validate_two(index, a, b) {
+        return Err(error);
+    }
 
     match kind_of(c) {
-        Kind::Space => {}
-        _ => panic::expected_space(),
+        Kind::Space => Ok(()),
+        Kind::Invalid => Err(error(index + 2, ErrorKind::InvalidCharacter)),
+        _ => Err(error(index + 2, ErrorKind::ExpectedSpace)),
     }
 }
 
-pub const fn validate_pattern(pattern: &'static str) {
-    let data_address = pattern.as_ptr();
-    let len = pattern.len();
+/// Validate a pattern in the form of `AA ?? B?`, returning the amount of bytes it matches.
+pub const fn validate_pattern(pattern: &str) -> Result<usize, PatternError> {
+    let bytes = pattern.as_bytes();
+    let len = bytes.len();
 
     // dont give an empty pattern
-    if len > 0 {
-        let a = unsafe { *data_address };
+    if len == 0 {
+        return Err(error(0, ErrorKind::Empty));
+    }
 
-        match kind_of(a) {
-            Kind::Space => panic::unexpected_space(),
-            _ => {}
-        }
+    if let Kind::Space = kind_of(bytes[0]) {
+        return Err(error(0, ErrorKind::UnexpectedSpace));
     }
 
     let rem = len % 3;
@@ -59,39 +80,32 @@ pub const fn validate_pattern(pattern: &'static str) {
 
     // validate in groups of 3
     while index + 2 < len {
-        let [a, b, c] = unsafe { *(data_address.add(index) as *const [u8; 3]) };
-
-        validate_three(a, b, c);
+        if let Err(error) = validate_three(index, bytes[index], bytes[index + 1], bytes[index + 2])
+        {
+            return Err(error);
+        }
 
         index += 3;
     }
 
     // check remainder
-    if rem == 2 {
-        let [a, b] = unsafe { *(data_address.add(index) as *const [u8; 2]) };
-
-        validate_two(a, b);
-    }
-
-    if rem == 1 {
-        let a = unsafe { *data_address.add(index) };
-
-        use Kind::{Digit, Space, Wildcard};
-
-        match kind_of(a) {
-            Digit => panic::expected_character_in_octal(),
-            Wildcard => panic::expected_character_in_wildcard(),
-            Space => panic::unexpected_trailing_space(),
-        }
-    }
-
-    // backtrack to check if last shit had a space
-    if rem == 0 {
-        let a = unsafe { *data_address.add(index) };
-
-        match kind_of(a) {
-            Kind::Space => panic::unexpected_trailing_space(),
-            _ => {}
+    match rem {
+        2 => match validate_two(index, bytes[index], bytes[index + 1]) {
+            Ok(()) => Ok(len / 3 + 1),
+            Err(error) => Err(error),
+        },
+        1 => {
+            use Kind::{Digit, Invalid, Space, Wildcard};
+
+            // the missing half would be at `index + 1`
+            match kind_of(bytes[index]) {
+                Digit => Err(error(index + 1, ErrorKind::ExpectedCharacterInOctal)),
+                Wildcard => Err(error(index + 1, ErrorKind::ExpectedCharacterInWildcard)),
+                Space => Err(error(index, ErrorKind::UnexpectedTrailingSpace)),
+                Invalid => Err(error(index, ErrorKind::InvalidCharacter)),
+            }
         }
+        // every group of 3 ends in a space, so the last character is one
+        _ => Err(error(len - 1, ErrorKind::UnexpectedTrailingSpace)),
     }
 }
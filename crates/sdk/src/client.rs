@@ -33,7 +33,7 @@ struct VTable {
     ) -> bool,
 }
 
-vtable_validate! {
+vtable_validate! { Client;
     get_all_classes => 8,
     hud_process_input => 10,
     hud_update => 11,
@@ -13,7 +13,7 @@ struct VTable {
     ) -> bool,
 }
 
-vtable_validate! {
+vtable_validate! { ClientMode;
     override_view => 19,
     create_move => 25,
 }
@@ -0,0 +1,21 @@
+//! Console commands and variables registered from Rust, `ConCommand` and `ConVar`.
+//!
+//! Both are unregistered when dropped.
+//!
+//! ```ignore
+//! let command = console.register_command("elysium_hello", "say hello", |args| {
+//!     println!("hello {}", args.arg_string());
+//! });
+//!
+//! let var = console.register_var("elysium_fov", 90.0_f32, "field of view", |fov| {
+//!     println!("fov is now {fov}");
+//! });
+//! ```
+
+pub use args::Args;
+pub use command::ConCommand;
+pub use var::ConVar;
+
+mod args;
+mod command;
+mod var;
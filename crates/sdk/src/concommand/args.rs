@@ -0,0 +1,84 @@
+use crate::object_validate;
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::str::FromStr;
+
+/// `COMMAND_MAX_ARGC`.
+const MAX_ARGC: usize = 64;
+
+/// `COMMAND_MAX_LENGTH`.
+const MAX_LENGTH: usize = 512;
+
+/// A tokenized command line, `CCommand`.
+#[repr(C)]
+pub struct Args {
+    argc: i32,
+    argv0_len: i32,
+    args_buffer: [u8; MAX_LENGTH],
+    argv_buffer: [u8; MAX_LENGTH],
+    argv: [*const u8; MAX_ARGC],
+}
+
+object_validate! {
+    Args;
+    argv0_len => 4,
+    args_buffer => 8,
+    argv => 1032,
+}
+
+impl Args {
+    /// Number of arguments, including the command itself.
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.argc.max(0) as usize).min(MAX_ARGC)
+    }
+
+    /// Whether there are no arguments, not even the command itself.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The argument at `index`, the command itself is at `0`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Cow<'_, str>> {
+        let ptr = *self.argv[..self.len()].get(index)?;
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        Some(unsafe { CStr::from_ptr(ptr.cast()).to_string_lossy() })
+    }
+
+    /// Parse the argument at `index`, `None` if it's missing or invalid.
+    #[inline]
+    pub fn parse<T>(&self, index: usize) -> Option<T>
+    where
+        T: FromStr,
+    {
+        self.get(index)?.parse().ok()
+    }
+
+    /// Iterate the arguments after the command.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        (1..self.len()).flat_map(move |index| self.get(index))
+    }
+
+    /// Everything after the command, as it was typed.
+    #[inline]
+    pub fn arg_string(&self) -> Cow<'_, str> {
+        let start = (self.argv0_len.max(0) as usize).min(MAX_LENGTH);
+        let bytes = &self.args_buffer[start..];
+        let len = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(bytes.len());
+
+        match String::from_utf8_lossy(&bytes[..len]) {
+            Cow::Borrowed(string) => Cow::Borrowed(string.trim()),
+            Cow::Owned(string) => Cow::Owned(string.trim().to_string()),
+        }
+    }
+}
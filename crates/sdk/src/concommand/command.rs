@@ -0,0 +1,231 @@
+use super::Args;
+use crate::convar::Flags;
+use crate::{object_validate, vtable_validate, Console};
+use core::ptr;
+use std::ffi::CString;
+
+type Callback = Box<dyn Fn(&Args) + Send + Sync>;
+
+#[repr(C)]
+struct VTable {
+    drop_complete: unsafe extern "C" fn(this: *mut RawCommand),
+    drop_deleting: unsafe extern "C" fn(this: *mut RawCommand),
+    is_command: unsafe extern "C" fn(this: *const RawCommand) -> bool,
+    is_flag_set: unsafe extern "C" fn(this: *const RawCommand, flag: i32) -> bool,
+    add_flags: unsafe extern "C" fn(this: *mut RawCommand, flags: i32),
+    remove_flags: unsafe extern "C" fn(this: *mut RawCommand, flags: i32),
+    get_flags: unsafe extern "C" fn(this: *const RawCommand) -> i32,
+    get_name: unsafe extern "C" fn(this: *const RawCommand) -> *const u8,
+    get_help_text: unsafe extern "C" fn(this: *const RawCommand) -> *const u8,
+    is_registered: unsafe extern "C" fn(this: *const RawCommand) -> bool,
+    get_dll_identifier: unsafe extern "C" fn(this: *const RawCommand) -> i32,
+    create:
+        unsafe extern "C" fn(this: *mut RawCommand, name: *const u8, help: *const u8, flags: i32),
+    init: unsafe extern "C" fn(this: *mut RawCommand),
+    auto_complete_suggest:
+        unsafe extern "C" fn(this: *mut RawCommand, partial: *const u8, commands: *mut ()) -> i32,
+    can_auto_complete: unsafe extern "C" fn(this: *mut RawCommand) -> bool,
+    dispatch: unsafe extern "C" fn(this: *mut RawCommand, args: *const Args),
+}
+
+vtable_validate! {
+    drop_complete => 0,
+    drop_deleting => 1,
+    is_command => 2,
+    is_flag_set => 3,
+    add_flags => 4,
+    remove_flags => 5,
+    get_flags => 6,
+    get_name => 7,
+    get_help_text => 8,
+    is_registered => 9,
+    get_dll_identifier => 10,
+    create => 11,
+    init => 12,
+    auto_complete_suggest => 13,
+    can_auto_complete => 14,
+    dispatch => 15,
+}
+
+/// Our implementation of `ConCommand`.
+///
+/// The fields up to `dll_identifier` are read directly by the game.
+#[repr(C)]
+struct RawCommand {
+    vtable: &'static VTable,
+    next: *mut (),
+    registered: bool,
+    name: *const u8,
+    help: *const u8,
+    flags: i32,
+    // the game's callback, and completion callback, unused as we implement `dispatch`
+    _callbacks: [*const (); 2],
+    _callback_kind: u8,
+    dll_identifier: i32,
+    name_string: CString,
+    help_string: CString,
+    callback: Callback,
+}
+
+object_validate! {
+    RawCommand;
+    next => 8,
+    registered => 16,
+    name => 24,
+    help => 32,
+    flags => 40,
+    _callbacks => 48,
+    _callback_kind => 64,
+}
+
+/// The game never destroys commands, it only forgets them.
+unsafe extern "C" fn drop_command(_this: *mut RawCommand) {}
+
+unsafe extern "C" fn is_command(_this: *const RawCommand) -> bool {
+    true
+}
+
+unsafe extern "C" fn is_flag_set(this: *const RawCommand, flag: i32) -> bool {
+    ((*this).flags & flag) != 0
+}
+
+unsafe extern "C" fn add_flags(this: *mut RawCommand, flags: i32) {
+    (*this).flags |= flags;
+}
+
+unsafe extern "C" fn remove_flags(this: *mut RawCommand, flags: i32) {
+    (*this).flags &= !flags;
+}
+
+unsafe extern "C" fn get_flags(this: *const RawCommand) -> i32 {
+    (*this).flags
+}
+
+unsafe extern "C" fn get_name(this: *const RawCommand) -> *const u8 {
+    (*this).name
+}
+
+unsafe extern "C" fn get_help_text(this: *const RawCommand) -> *const u8 {
+    (*this).help
+}
+
+unsafe extern "C" fn is_registered(this: *const RawCommand) -> bool {
+    (*this).registered
+}
+
+unsafe extern "C" fn get_dll_identifier(this: *const RawCommand) -> i32 {
+    (*this).dll_identifier
+}
+
+/// Only called by the game's constructors, which never construct ours.
+unsafe extern "C" fn create(
+    _this: *mut RawCommand,
+    _name: *const u8,
+    _help: *const u8,
+    _flags: i32,
+) {
+}
+
+unsafe extern "C" fn init(_this: *mut RawCommand) {}
+
+unsafe extern "C" fn auto_complete_suggest(
+    _this: *mut RawCommand,
+    _partial: *const u8,
+    _commands: *mut (),
+) -> i32 {
+    0
+}
+
+unsafe extern "C" fn can_auto_complete(_this: *mut RawCommand) -> bool {
+    false
+}
+
+unsafe extern "C" fn dispatch(this: *mut RawCommand, args: *const Args) {
+    if let Some(args) = args.as_ref() {
+        ((*this).callback)(args);
+    }
+}
+
+static VTABLE: VTable = VTable {
+    drop_complete: drop_command,
+    drop_deleting: drop_command,
+    is_command,
+    is_flag_set,
+    add_flags,
+    remove_flags,
+    get_flags,
+    get_name,
+    get_help_text,
+    is_registered,
+    get_dll_identifier,
+    create,
+    init,
+    auto_complete_suggest,
+    can_auto_complete,
+    dispatch,
+};
+
+/// A registered console command, which is unregistered when dropped.
+pub struct ConCommand {
+    console: &'static Console,
+    raw: Box<RawCommand>,
+}
+
+unsafe impl Send for ConCommand {}
+unsafe impl Sync for ConCommand {}
+
+impl ConCommand {
+    /// Register `callback` as the command `name`, which mustn't already be registered, as the game
+    /// silently refuses it, see `Console::register_command`.
+    ///
+    /// Returns `None` if `name`, or `help`, contain a nul byte.
+    pub(crate) fn new(
+        console: &'static Console,
+        name: &str,
+        help: &str,
+        callback: Callback,
+    ) -> Option<Self> {
+        let name = CString::new(name).ok()?;
+        let help = CString::new(help).ok()?;
+
+        let mut raw = Box::new(RawCommand {
+            vtable: &VTABLE,
+            next: ptr::null_mut(),
+            registered: false,
+            name: name.as_ptr().cast(),
+            help: help.as_ptr().cast(),
+            flags: Flags::CLIENT_DLL.0,
+            _callbacks: [ptr::null(); 2],
+            _callback_kind: 0,
+            dll_identifier: console.dll_identifier(),
+            name_string: name,
+            help_string: help,
+            callback,
+        });
+
+        unsafe {
+            console.register(ptr::addr_of_mut!(*raw).cast());
+        }
+
+        Some(Self { console, raw })
+    }
+
+    /// The command's name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.raw.name_string.to_str().unwrap_or_default()
+    }
+
+    /// Unregister the command, the same as dropping it.
+    #[inline]
+    pub fn remove(self) {}
+}
+
+impl Drop for ConCommand {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            self.console.unregister(ptr::addr_of_mut!(*self.raw).cast());
+        }
+    }
+}
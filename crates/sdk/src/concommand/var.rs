@@ -0,0 +1,189 @@
+use crate::convar::{ChangeCallback, Flags, Kind, Var, EXTERNAL_BUFFER, VAR_OFFSET};
+use crate::{object_validate, Console, UtlMem, UtlVec};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr;
+use std::ffi::CString;
+
+/// `COMMAND_MAX_LENGTH`, no value set from the console is longer.
+const MAX_LENGTH: usize = 512;
+
+type Callback = Box<dyn Fn(*const RawVar) + Send + Sync>;
+
+/// A `ConVar` of our own, using the game's virtual tables.
+///
+/// The fields up to `change_callbacks` are the game's.
+#[repr(C)]
+struct RawVar {
+    vtable: *const (),
+    next: *mut (),
+    registered: bool,
+    name: *const u8,
+    help: *const u8,
+    flags: i32,
+    var_vtable: *const (),
+    parent: *const RawVar,
+    default_value: *const u8,
+    string: *mut u8,
+    string_len: i32,
+    float_value: f32,
+    int_value: i32,
+    has_min: bool,
+    min: f32,
+    has_max: bool,
+    max: f32,
+    change_callbacks: UtlVec<ChangeCallback>,
+    change_callback: [ChangeCallback; 1],
+    // the game never reallocates a string that fits, so it never frees ours
+    string_buffer: [u8; MAX_LENGTH],
+    name_string: CString,
+    help_string: CString,
+    default_string: CString,
+    callback: Callback,
+}
+
+object_validate! {
+    RawVar;
+    var_vtable => 48,
+    parent => 56,
+    default_value => 64,
+    string => 72,
+    float_value => 84,
+    change_callbacks => 112,
+}
+
+unsafe extern "C" fn changed(var: *mut u8, _old_string: *const u8, _old_value: f32) {
+    let raw = var.sub(VAR_OFFSET).cast::<RawVar>();
+
+    ((*raw).callback)(raw);
+}
+
+/// A registered console variable, which is unregistered when dropped.
+pub struct ConVar<T> {
+    console: &'static Console,
+    raw: Box<RawVar>,
+    _phantom: PhantomData<T>,
+}
+
+unsafe impl<T> Send for ConVar<T> {}
+unsafe impl<T> Sync for ConVar<T> {}
+
+impl<T> ConVar<T>
+where
+    T: Kind,
+{
+    /// Register the variable `name`, calling `callback` with it's new value whenever it changes.
+    ///
+    /// `template` is any of the game's variables, who's virtual tables are borrowed.
+    ///
+    /// Returns `None` if `name`, or `help`, contain a nul byte.
+    pub(crate) fn new<F>(
+        console: &'static Console,
+        template: &Var<()>,
+        name: &str,
+        default: T,
+        help: &str,
+        callback: F,
+    ) -> Option<Self>
+    where
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        let name = CString::new(name).ok()?;
+        let help = CString::new(help).ok()?;
+        let (default, float_value, int_value) = default.to_raw();
+        let default = CString::new(default).ok()?;
+
+        let (vtable, var_vtable) = unsafe {
+            let template = (template as *const Var<()>).cast::<*const ()>();
+
+            (*template, *template.byte_add(VAR_OFFSET))
+        };
+
+        let callback: Callback = Box::new(move |raw| {
+            let var = unsafe { &*raw.cast::<Var<T>>() };
+
+            callback(var.read())
+        });
+
+        let mut raw = Box::new(RawVar {
+            vtable,
+            next: ptr::null_mut(),
+            registered: false,
+            name: name.as_ptr().cast(),
+            help: help.as_ptr().cast(),
+            flags: Flags::CLIENT_DLL.0,
+            var_vtable,
+            parent: ptr::null(),
+            default_value: default.as_ptr().cast(),
+            string: ptr::null_mut(),
+            string_len: MAX_LENGTH as i32,
+            float_value,
+            int_value,
+            has_min: false,
+            min: 0.0,
+            has_max: false,
+            max: 0.0,
+            change_callbacks: UtlVec {
+                mem: UtlMem {
+                    mem: ptr::null(),
+                    alloc_count: 1,
+                    grow_len: EXTERNAL_BUFFER,
+                },
+                len: 1,
+                elements: ptr::null(),
+            },
+            change_callback: [changed],
+            string_buffer: [0; MAX_LENGTH],
+            name_string: name,
+            help_string: help,
+            default_string: default,
+            callback,
+        });
+
+        let bytes = raw.default_string.as_bytes_with_nul();
+
+        raw.string_buffer[..bytes.len()].copy_from_slice(bytes);
+        raw.parent = ptr::addr_of!(*raw);
+        raw.string = raw.string_buffer.as_mut_ptr();
+        raw.change_callbacks.mem.mem = raw.change_callback.as_ptr();
+        raw.change_callbacks.elements = raw.change_callback.as_ptr();
+
+        unsafe {
+            console.register(ptr::addr_of_mut!(*raw).cast());
+        }
+
+        Some(Self {
+            console,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The variable's name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.raw.name_string.to_str().unwrap_or_default()
+    }
+
+    /// Unregister the variable, the same as dropping it.
+    #[inline]
+    pub fn remove(self) {}
+}
+
+impl<T> Deref for ConVar<T> {
+    type Target = Var<T>;
+
+    #[inline]
+    fn deref(&self) -> &Var<T> {
+        unsafe { &*ptr::addr_of!(*self.raw).cast() }
+    }
+}
+
+impl<T> Drop for ConVar<T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            self.console.unregister(ptr::addr_of_mut!(*self.raw).cast());
+        }
+    }
+}
@@ -1,13 +1,35 @@
-use crate::ffi;
+use crate::concommand::{Args, ConCommand, ConVar};
+use crate::convar::iter::RawIter;
+use crate::convar::{Iter, Kind, Var};
+use crate::{ffi, vtable_validate};
 use frosting::ffi::vtable;
 use std::ffi::OsStr;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// A variable every client has, who's virtual tables our variables borrow.
+const TEMPLATE_VAR: &str = "developer\0";
 
 #[repr(C)]
 pub struct VTable {
-    _pad0: vtable::Pad<15>,
+    _pad0: vtable::Pad<9>,
+    allocate_dll_identifier: unsafe extern "C" fn(this: *const Console) -> i32,
+    register_con_command: unsafe extern "C" fn(this: *const Console, command: *mut u8),
+    unregister_con_command: unsafe extern "C" fn(this: *const Console, command: *mut u8),
+    _pad1: vtable::Pad<3>,
     var: unsafe extern "C" fn(this: *const Console, var: *const u8) -> *const (),
-    _pad1: vtable::Pad<11>,
+    _pad2: vtable::Pad<11>,
     write: unsafe extern "C" fn(this: *const Console, fmt: *const u8, txt: *const u8),
+    _pad3: vtable::Pad<13>,
+    factory_internal_iterator: unsafe extern "C" fn(this: *const Console) -> *mut RawIter,
+}
+
+vtable_validate! {
+    allocate_dll_identifier => 9,
+    register_con_command => 10,
+    unregister_con_command => 11,
+    var => 15,
+    write => 27,
+    factory_internal_iterator => 41,
 }
 
 #[repr(C)]
@@ -27,6 +49,22 @@ impl Console {
         unsafe { (self.vtable.var)(self, ptr) }
     }
 
+    /// The variable `name`, read as `T`, `None` if the game doesn't have it.
+    #[inline]
+    pub fn find_var<T, S>(&self, name: S) -> Option<&Var<T>>
+    where
+        T: Kind,
+        S: AsRef<OsStr>,
+    {
+        unsafe { self.var(name).cast::<Var<T>>().as_ref() }
+    }
+
+    /// Iterate every registered command, and variable.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        unsafe { Iter::new((self.vtable.factory_internal_iterator)(self)) }
+    }
+
     #[inline]
     pub fn write<S>(&self, string: S)
     where
@@ -37,4 +75,90 @@ impl Console {
 
         unsafe { (self.vtable.write)(self, "%s\0".as_ptr(), ptr) }
     }
+
+    /// Identifier the game associates with our commands, allocated once.
+    #[inline]
+    pub(crate) fn dll_identifier(&self) -> i32 {
+        static IDENTIFIER: AtomicI32 = AtomicI32::new(-1);
+
+        match IDENTIFIER.load(Ordering::Acquire) {
+            -1 => {
+                let identifier = unsafe { (self.vtable.allocate_dll_identifier)(self) };
+
+                IDENTIFIER.store(identifier, Ordering::Release);
+
+                identifier
+            }
+            identifier => identifier,
+        }
+    }
+
+    /// Register a `ConCommandBase`.
+    #[inline]
+    pub(crate) unsafe fn register(&self, command: *mut u8) {
+        (self.vtable.register_con_command)(self, command)
+    }
+
+    /// Unregister a `ConCommandBase`.
+    #[inline]
+    pub(crate) unsafe fn unregister(&self, command: *mut u8) {
+        (self.vtable.unregister_con_command)(self, command)
+    }
+
+    /// Whether a command, or variable, is registered as `name`.
+    ///
+    /// Names are case insensitive, as they are to the game.
+    #[inline]
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.iter()
+            .any(|base| base.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Register the command `name`, which calls `callback` whenever it's executed, until it's
+    /// dropped.
+    ///
+    /// Returns `None` if `name` is already a command, or variable, as the game refuses it, or
+    /// `name`, or `help`, contain a nul byte.
+    #[inline]
+    pub fn register_command<F>(
+        &'static self,
+        name: &str,
+        help: &str,
+        callback: F,
+    ) -> Option<ConCommand>
+    where
+        F: Fn(&Args) + Send + Sync + 'static,
+    {
+        if self.is_registered(name) {
+            return None;
+        }
+
+        ConCommand::new(self, name, help, Box::new(callback))
+    }
+
+    /// Register the variable `name`, which calls `callback` with it's new value whenever it
+    /// changes, until it's dropped.
+    ///
+    /// Returns `None` if `name` is already a command, or variable, or `name`, or `help`, contain a
+    /// nul byte.
+    #[inline]
+    pub fn register_var<T, F>(
+        &'static self,
+        name: &str,
+        default: T,
+        help: &str,
+        callback: F,
+    ) -> Option<ConVar<T>>
+    where
+        T: Kind,
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        if self.is_registered(name) {
+            return None;
+        }
+
+        let template = unsafe { self.var(TEMPLATE_VAR).cast::<Var<()>>().as_ref()? };
+
+        ConVar::new(self, template, name, default, help, callback)
+    }
 }
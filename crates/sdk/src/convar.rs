@@ -1,6 +1,27 @@
 use crate::{Pad, UtlVec};
 use core::marker::PhantomData;
 use frosting::ffi::vtable;
+use std::borrow::Cow;
+use std::ffi::CStr;
+
+pub use base::{CommandBase, Flags};
+pub use iter::Iter;
+pub use subscription::Subscription;
+
+mod base;
+mod subscription;
+
+pub(crate) mod iter;
+
+/// offset of the `IConVar` part of a `ConVar`, which change callbacks are given
+pub(crate) const VAR_OFFSET: usize = 48;
+
+/// `CUtlMemory`'s `EXTERNAL_BUFFER_MARKER` grow length, the game never frees, nor reallocates,
+/// these
+pub(crate) const EXTERNAL_BUFFER: i32 = -1;
+
+/// `FnChangeCallback_t`, given the `IConVar` part of the variable that changed
+pub type ChangeCallback = unsafe extern "C" fn(var: *mut u8, old_string: *const u8, old_value: f32);
 
 mod sealed {
     use super::Var;
@@ -8,6 +29,9 @@ mod sealed {
     pub trait Sealed: Sized {
         fn read(var: &Var<Self>) -> Self;
         fn write(self, var: &Var<Self>);
+
+        /// The value as a string, float, and integer, as a `ConVar` stores it.
+        fn to_raw(self) -> (String, f32, i32);
     }
 
     impl Sealed for f32 {
@@ -20,6 +44,11 @@ mod sealed {
         fn write(self, var: &Var<f32>) {
             var.write_f32(self)
         }
+
+        #[inline]
+        fn to_raw(self) -> (String, f32, i32) {
+            (self.to_string(), self, self as i32)
+        }
     }
 
     impl Sealed for i32 {
@@ -32,6 +61,11 @@ mod sealed {
         fn write(self, var: &Var<i32>) {
             var.write_i32(self)
         }
+
+        #[inline]
+        fn to_raw(self) -> (String, f32, i32) {
+            (self.to_string(), self as f32, self)
+        }
     }
 
     impl Sealed for bool {
@@ -44,6 +78,11 @@ mod sealed {
         fn write(self, var: &Var<bool>) {
             var.write_i32(self as i32)
         }
+
+        #[inline]
+        fn to_raw(self) -> (String, f32, i32) {
+            (self as i32).to_raw()
+        }
     }
 }
 
@@ -71,13 +110,17 @@ struct VTable<T> {
 pub struct Var<T> {
     /// blah blah static
     vtable: *const VTable<T>,
-    _pad0: Pad<40>,
+    _next: *const (),
+    _registered: bool,
+    name: *const u8,
+    help: *const u8,
+    flags: Flags,
     pub change_callback: Option<unsafe extern "C" fn()>,
     pub parent: *const Var<()>,
     pub default_value: *const u8,
     pub string: *const u8,
     _pad1: Pad<28>,
-    pub on_change_callbacks: UtlVec<unsafe extern "C" fn()>,
+    pub on_change_callbacks: UtlVec<ChangeCallback>,
     // we do be owning T, tho
     _phantom: PhantomData<T>,
 }
@@ -102,6 +145,53 @@ impl<T> Var<T> {
     fn write_i32(&self, value: i32) {
         unsafe { ((*self.vtable).write_i32)(self, value) }
     }
+
+    /// the variable values are stored in, which is itself unless it was registered twice
+    #[inline]
+    fn parent(&self) -> &Var<()> {
+        unsafe {
+            match self.parent.as_ref() {
+                Some(parent) => parent,
+                None => self.erase(),
+            }
+        }
+    }
+
+    /// forget the variable's type
+    #[inline]
+    pub fn erase(&self) -> &Var<()> {
+        unsafe { &*(self as *const Var<T>).cast() }
+    }
+
+    /// the variable's name
+    #[inline]
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe { cow_from_ptr(self.name) }
+    }
+
+    /// the variable's description
+    #[inline]
+    pub fn help(&self) -> Cow<'_, str> {
+        unsafe { cow_from_ptr(self.help) }
+    }
+
+    /// the variable's flags
+    #[inline]
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    /// the variable's default value, as a string
+    #[inline]
+    pub fn default_value(&self) -> Cow<'_, str> {
+        unsafe { cow_from_ptr(self.parent().default_value) }
+    }
+
+    /// the variable's current value, as a string
+    #[inline]
+    pub fn string(&self) -> Cow<'_, str> {
+        unsafe { cow_from_ptr(self.parent().string) }
+    }
 }
 
 impl<T> Var<T>
@@ -119,6 +209,38 @@ where
     pub fn write(&self, value: T) {
         <T as sealed::Sealed>::write(value, self)
     }
+
+    /// call `callback` with the variable's new value whenever it changes, until the subscription
+    /// is dropped
+    ///
+    /// `None` if the variable has no room for another change callback, only the game may make
+    /// room
+    #[inline]
+    pub fn on_change<F>(&'static self, callback: F) -> Option<Subscription>
+    where
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        let address = self as *const Var<T> as usize;
+
+        Subscription::new(
+            self.erase(),
+            Box::new(move || {
+                let var = unsafe { &*(address as *const Var<T>) };
+
+                callback(var.read())
+            }),
+        )
+    }
+}
+
+/// read a nullable C string
+#[inline]
+pub(crate) unsafe fn cow_from_ptr<'a>(ptr: *const u8) -> Cow<'a, str> {
+    if ptr.is_null() {
+        Cow::Borrowed("")
+    } else {
+        CStr::from_ptr(ptr.cast()).to_string_lossy()
+    }
 }
 
 macro_rules! vars {
@@ -186,27 +308,21 @@ macro_rules! vars {
                 #[doc = "`"]
                 // doc's alias is the same as it's name, cring
                 //#[doc(alias = $string)]
-                pub $name: &'static Var<$type>,
+                ///
+                /// `None` if the game doesn't have it, features that need it should be disabled
+                pub $name: Option<&'static Var<$type>>,
             )*
         }
 
         impl Vars {
-            /// load all config variables
+            /// load all config variables, any the loader can't find are `None`
             #[inline]
             pub fn from_loader<L>(mut loader: L) -> Self
             where
                 L: FnMut(VarKind) -> *const ()
             {
                 $(
-                    let $name = {
-                        let var = loader(VarKind::$name).cast::<Var<$type>>().as_mut();
-
-                        if var.is_null() {
-                            panic!(concat!("config variable `", stringify!($name), "` is null"));
-                        }
-
-                        unsafe { &mut *var }
-                    };
+                    let $name = unsafe { loader(VarKind::$name).cast::<Var<$type>>().as_ref() };
                 )*
 
                 Self { $($name,)* }
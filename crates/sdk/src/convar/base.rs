@@ -0,0 +1,115 @@
+use super::{cow_from_ptr, Var};
+use crate::vtable_validate;
+use core::ops::{BitAnd, BitOr};
+use frosting::ffi::vtable;
+use std::borrow::Cow;
+
+#[derive(Debug)]
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<2>,
+    is_command: unsafe extern "C" fn(this: *const CommandBase) -> bool,
+}
+
+vtable_validate! {
+    is_command => 2,
+}
+
+/// `FCVAR_*` flags of a command, or variable
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct Flags(pub i32);
+
+impl Flags {
+    pub const NONE: Flags = Flags(0);
+    pub const UNREGISTERED: Flags = Flags(1 << 0);
+    pub const DEVELOPMENT_ONLY: Flags = Flags(1 << 1);
+    pub const GAME_DLL: Flags = Flags(1 << 2);
+    pub const CLIENT_DLL: Flags = Flags(1 << 3);
+    pub const HIDDEN: Flags = Flags(1 << 4);
+    pub const PROTECTED: Flags = Flags(1 << 5);
+    pub const SINGLEPLAYER_ONLY: Flags = Flags(1 << 6);
+    pub const ARCHIVE: Flags = Flags(1 << 7);
+    pub const NOTIFY: Flags = Flags(1 << 8);
+    pub const USER_INFO: Flags = Flags(1 << 9);
+    pub const PRINTABLE_ONLY: Flags = Flags(1 << 10);
+    pub const UNLOGGED: Flags = Flags(1 << 11);
+    pub const NEVER_AS_STRING: Flags = Flags(1 << 12);
+    pub const REPLICATED: Flags = Flags(1 << 13);
+    pub const CHEAT: Flags = Flags(1 << 14);
+    pub const SPLIT_SCREEN: Flags = Flags(1 << 15);
+    pub const DEMO: Flags = Flags(1 << 16);
+    pub const DONT_RECORD: Flags = Flags(1 << 17);
+    pub const RELEASE: Flags = Flags(1 << 19);
+
+    /// whether every flag in `other` is set
+    #[inline]
+    pub const fn contains(self, other: Flags) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl BitAnd for Flags {
+    type Output = Flags;
+
+    #[inline]
+    fn bitand(self, other: Flags) -> Flags {
+        Flags(self.0 & other.0)
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Flags;
+
+    #[inline]
+    fn bitor(self, other: Flags) -> Flags {
+        Flags(self.0 | other.0)
+    }
+}
+
+/// any registered command, or variable, `ConCommandBase`
+#[repr(C)]
+pub struct CommandBase {
+    vtable: &'static VTable,
+    _next: *const CommandBase,
+    _registered: bool,
+    name: *const u8,
+    help: *const u8,
+    flags: Flags,
+}
+
+impl CommandBase {
+    /// whether this is a command, rather than a variable
+    #[inline]
+    pub fn is_command(&self) -> bool {
+        unsafe { (self.vtable.is_command)(self) }
+    }
+
+    /// the variable, if this is one
+    #[inline]
+    pub fn as_var(&self) -> Option<&Var<()>> {
+        if self.is_command() {
+            None
+        } else {
+            Some(unsafe { &*(self as *const CommandBase).cast() })
+        }
+    }
+
+    /// the command's, or variable's, name
+    #[inline]
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe { cow_from_ptr(self.name) }
+    }
+
+    /// the command's, or variable's, description
+    #[inline]
+    pub fn help(&self) -> Cow<'_, str> {
+        unsafe { cow_from_ptr(self.help) }
+    }
+
+    /// the command's, or variable's, flags
+    #[inline]
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+}
@@ -0,0 +1,83 @@
+use super::CommandBase;
+use crate::vtable_validate;
+use core::marker::PhantomData;
+
+#[repr(C)]
+struct VTable {
+    drop_complete: unsafe extern "C" fn(this: *mut RawIter),
+    drop_deleting: unsafe extern "C" fn(this: *mut RawIter),
+    set_first: unsafe extern "C" fn(this: *mut RawIter),
+    next: unsafe extern "C" fn(this: *mut RawIter),
+    is_valid: unsafe extern "C" fn(this: *mut RawIter) -> bool,
+    get: unsafe extern "C" fn(this: *mut RawIter) -> *const CommandBase,
+}
+
+vtable_validate! {
+    drop_complete => 0,
+    drop_deleting => 1,
+    set_first => 2,
+    next => 3,
+    is_valid => 4,
+    get => 5,
+}
+
+/// `ICVarIteratorInternal`, allocated by the game
+#[repr(C)]
+pub(crate) struct RawIter {
+    vtable: &'static VTable,
+}
+
+/// iterator over every registered command, and variable
+pub struct Iter<'a> {
+    raw: *mut RawIter,
+    _phantom: PhantomData<&'a CommandBase>,
+}
+
+impl<'a> Iter<'a> {
+    /// take ownership of an iterator the game allocated
+    #[inline]
+    pub(crate) unsafe fn new(raw: *mut RawIter) -> Self {
+        if let Some(iter) = raw.as_ref() {
+            (iter.vtable.set_first)(raw);
+        }
+
+        Self {
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a CommandBase;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a CommandBase> {
+        unsafe {
+            let vtable = self.raw.as_ref()?.vtable;
+
+            while (vtable.is_valid)(self.raw) {
+                let base = (vtable.get)(self.raw).as_ref();
+
+                (vtable.next)(self.raw);
+
+                if base.is_some() {
+                    return base;
+                }
+            }
+
+            None
+        }
+    }
+}
+
+impl<'a> Drop for Iter<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(iter) = self.raw.as_ref() {
+                (iter.vtable.drop_deleting)(self.raw);
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use super::{ChangeCallback, Var, VAR_OFFSET};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type Callback = Arc<dyn Fn() + Send + Sync>;
+
+struct Subscriber {
+    id: usize,
+    var: usize,
+    callback: Callback,
+}
+
+struct Registry {
+    /// variables `changed` is installed in
+    installed: Vec<usize>,
+    subscribers: Vec<Subscriber>,
+    next_id: usize,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    installed: Vec::new(),
+    subscribers: Vec::new(),
+    next_id: 0,
+});
+
+#[inline]
+fn registry() -> MutexGuard<'static, Registry> {
+    // a panicking callback isn't called with the lock held
+    REGISTRY.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// the variable values are stored in, and callbacks are called from
+#[inline]
+fn parent_of(var: &Var<()>) -> *mut Var<()> {
+    if var.parent.is_null() {
+        var as *const Var<()> as *mut Var<()>
+    } else {
+        var.parent as *mut Var<()>
+    }
+}
+
+unsafe extern "C" fn changed(var: *mut u8, _old_string: *const u8, _old_value: f32) {
+    let var = match var.sub(VAR_OFFSET).cast::<Var<()>>().as_ref() {
+        Some(var) => parent_of(var) as usize,
+        None => return,
+    };
+
+    // callbacks may change variables themselves
+    let callbacks = registry()
+        .subscribers
+        .iter()
+        .filter(|subscriber| subscriber.var == var)
+        .map(|subscriber| Arc::clone(&subscriber.callback))
+        .collect::<Vec<_>>();
+
+    for callback in callbacks {
+        callback();
+    }
+}
+
+/// add `changed` to the variable's `on_change_callbacks`, in it's own buffer, so the game may still
+/// grow it
+///
+/// returns whether there was room, as only the game may allocate it, and it's not ours to replace,
+/// as the game doesn't check a buffer it can't grow before appending to it
+unsafe fn install(var: *mut Var<()>) -> bool {
+    let callbacks = &mut (*var).on_change_callbacks;
+    let len = callbacks.len.max(0);
+
+    if callbacks.mem.mem.is_null() || len >= callbacks.mem.alloc_count {
+        return false;
+    }
+
+    let mem = callbacks.mem.mem as *mut ChangeCallback;
+
+    mem.add(len as usize).write(changed as ChangeCallback);
+    callbacks.len = len + 1;
+    callbacks.elements = mem;
+
+    true
+}
+
+/// remove `changed` from the variable's `on_change_callbacks`, keeping the order of any others,
+/// including those the game added since it was installed
+unsafe fn uninstall(var: *mut Var<()>) {
+    let callbacks = &mut (*var).on_change_callbacks;
+
+    if callbacks.mem.mem.is_null() || callbacks.len <= 0 {
+        return;
+    }
+
+    let current = callbacks.as_mut_slice();
+    let index = match current
+        .iter()
+        .position(|callback| *callback as usize == changed as ChangeCallback as usize)
+    {
+        Some(index) => index,
+        None => return,
+    };
+
+    current.copy_within(index + 1.., index);
+    callbacks.len -= 1;
+}
+
+/// a change callback, which is removed when dropped
+///
+/// `changed` is removed from the variable's callbacks once every subscription to it is dropped,
+/// see `uninstall`
+pub struct Subscription {
+    id: usize,
+}
+
+impl Subscription {
+    /// `None` if the variable's callbacks are full, see `install`
+    pub(super) fn new(
+        var: &'static Var<()>,
+        callback: Box<dyn Fn() + Send + Sync>,
+    ) -> Option<Self> {
+        let var = parent_of(var);
+        let mut registry = registry();
+
+        if !registry.installed.contains(&(var as usize)) {
+            if !unsafe { install(var) } {
+                return None;
+            }
+
+            registry.installed.push(var as usize);
+        }
+
+        let id = registry.next_id;
+
+        registry.next_id += 1;
+        registry.subscribers.push(Subscriber {
+            id,
+            var: var as usize,
+            callback: Arc::from(callback),
+        });
+
+        Some(Self { id })
+    }
+
+    /// remove the callback, the same as dropping it
+    #[inline]
+    pub fn remove(self) {}
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut registry = registry();
+        let index = match registry
+            .subscribers
+            .iter()
+            .position(|subscriber| subscriber.id == self.id)
+        {
+            Some(index) => index,
+            None => return,
+        };
+
+        let var = registry.subscribers.remove(index).var;
+
+        if registry
+            .subscribers
+            .iter()
+            .any(|subscriber| subscriber.var == var)
+        {
+            return;
+        }
+
+        if let Some(index) = registry
+            .installed
+            .iter()
+            .position(|installed| *installed == var)
+        {
+            registry.installed.remove(index);
+
+            unsafe { uninstall(var as *mut Var<()>) };
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{ffi, vtable_validate};
+use elysium_math::Vec3;
+use frosting::ffi::vtable;
+use std::ffi::OsStr;
+use std::mem::MaybeUninit;
+
+// slots of the linux `VDebugOverlay`, which follow the declaration order of `IVDebugOverlay` in
+// csgo's `public/engine/ivdebugoverlay.h`, as the itanium abi doesn't group overloads like msvc,
+// so `ScreenPosition(const Vector&, Vector&)` is 11, before the `float` overload, and the `int`
+// `AddTextOverlayRGB` is 18, after the `float` one
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<1>,
+    add_box_overlay: unsafe extern "C" fn(
+        this: *const DebugOverlay,
+        origin: *const Vec3,
+        min: *const Vec3,
+        max: *const Vec3,
+        angle: *const Vec3,
+        r: i32,
+        g: i32,
+        b: i32,
+        a: i32,
+        duration: f32,
+    ),
+    add_sphere_overlay: unsafe extern "C" fn(
+        this: *const DebugOverlay,
+        origin: *const Vec3,
+        radius: f32,
+        theta: i32,
+        phi: i32,
+        r: i32,
+        g: i32,
+        b: i32,
+        a: i32,
+        duration: f32,
+    ),
+    _pad1: vtable::Pad<5>,
+    add_swept_box_overlay: unsafe extern "C" fn(
+        this: *const DebugOverlay,
+        start: *const Vec3,
+        end: *const Vec3,
+        min: *const Vec3,
+        max: *const Vec3,
+        angle: *const Vec3,
+        r: i32,
+        g: i32,
+        b: i32,
+        a: i32,
+        duration: f32,
+    ),
+    _pad2: vtable::Pad<2>,
+    screen_position: unsafe extern "C" fn(
+        this: *const DebugOverlay,
+        point: *const Vec3,
+        screen: *mut Vec3,
+    ) -> i32,
+    _pad3: vtable::Pad<6>,
+    // variadic, so `al` holds the number of vector registers used, as `duration` is one
+    add_text_overlay_rgb: unsafe extern "C" fn(
+        this: *const DebugOverlay,
+        origin: *const Vec3,
+        line_offset: i32,
+        duration: f32,
+        r: i32,
+        g: i32,
+        b: i32,
+        a: i32,
+        fmt: *const u8,
+        ...
+    ),
+    add_line_overlay_alpha: unsafe extern "C" fn(
+        this: *const DebugOverlay,
+        origin: *const Vec3,
+        dest: *const Vec3,
+        r: i32,
+        g: i32,
+        b: i32,
+        a: i32,
+        no_depth_test: bool,
+        duration: f32,
+    ),
+}
+
+vtable_validate! {
+    add_box_overlay => 1,
+    add_sphere_overlay => 2,
+    add_swept_box_overlay => 8,
+    screen_position => 11,
+    add_text_overlay_rgb => 18,
+    add_line_overlay_alpha => 19,
+}
+
+/// Number of segments spheres are drawn with.
+const SPHERE_SEGMENTS: i32 = 12;
+
+/// An overlay colour.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const RED: Color = Color::rgb(255, 0, 0);
+    pub const GREEN: Color = Color::rgb(0, 255, 0);
+    pub const BLUE: Color = Color::rgb(0, 0, 255);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    #[inline]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color::rgba(r, g, b, 255)
+    }
+
+    #[inline]
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+
+    #[inline]
+    const fn to_i32(self) -> (i32, i32, i32, i32) {
+        (self.r as i32, self.g as i32, self.b as i32, self.a as i32)
+    }
+}
+
+/// Debug overlay interface, for drawing in world-space.
+///
+/// Every overlay disappears after `duration` seconds, or the next frame if it's `0.0`.
+#[repr(C)]
+pub struct DebugOverlay {
+    vtable: &'static VTable,
+}
+
+impl DebugOverlay {
+    /// Draw a box from `min` to `max`, relative to `origin`, rotated by `angle`.
+    #[inline]
+    pub fn add_box(
+        &self,
+        origin: Vec3,
+        min: Vec3,
+        max: Vec3,
+        angle: Vec3,
+        color: Color,
+        duration: f32,
+    ) {
+        let (r, g, b, a) = color.to_i32();
+
+        unsafe {
+            (self.vtable.add_box_overlay)(self, &origin, &min, &max, &angle, r, g, b, a, duration)
+        }
+    }
+
+    /// Draw a line from `origin` to `dest`, through walls if `no_depth_test`.
+    #[inline]
+    pub fn add_line(
+        &self,
+        origin: Vec3,
+        dest: Vec3,
+        color: Color,
+        no_depth_test: bool,
+        duration: f32,
+    ) {
+        let (r, g, b, a) = color.to_i32();
+
+        unsafe {
+            (self.vtable.add_line_overlay_alpha)(
+                self,
+                &origin,
+                &dest,
+                r,
+                g,
+                b,
+                a,
+                no_depth_test,
+                duration,
+            )
+        }
+    }
+
+    /// Draw `text` at `origin`, `line_offset` lines down.
+    #[inline]
+    pub fn add_text<S>(&self, origin: Vec3, line_offset: i32, text: S, color: Color, duration: f32)
+    where
+        S: AsRef<OsStr>,
+    {
+        let (r, g, b, a) = color.to_i32();
+        let cstr = ffi::osstr_to_cstr_cow(text);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe {
+            (self.vtable.add_text_overlay_rgb)(
+                self,
+                &origin,
+                line_offset,
+                duration,
+                r,
+                g,
+                b,
+                a,
+                "%s\0".as_ptr(),
+                ptr,
+            )
+        }
+    }
+
+    /// Draw a sphere of `radius` around `origin`.
+    #[inline]
+    pub fn add_sphere(&self, origin: Vec3, radius: f32, color: Color, duration: f32) {
+        let (r, g, b, a) = color.to_i32();
+
+        unsafe {
+            (self.vtable.add_sphere_overlay)(
+                self,
+                &origin,
+                radius,
+                SPHERE_SEGMENTS,
+                SPHERE_SEGMENTS,
+                r,
+                g,
+                b,
+                a,
+                duration,
+            )
+        }
+    }
+
+    /// Draw a box from `min` to `max`, rotated by `angle`, swept from `start` to `end`, such as
+    /// a hull trace.
+    #[inline]
+    pub fn add_swept_box(
+        &self,
+        start: Vec3,
+        end: Vec3,
+        min: Vec3,
+        max: Vec3,
+        angle: Vec3,
+        color: Color,
+        duration: f32,
+    ) {
+        let (r, g, b, a) = color.to_i32();
+
+        unsafe {
+            (self.vtable.add_swept_box_overlay)(
+                self, &start, &end, &min, &max, &angle, r, g, b, a, duration,
+            )
+        }
+    }
+
+    /// Project `point` onto the screen, `None` if it's behind the view.
+    #[inline]
+    pub fn screen_position(&self, point: Vec3) -> Option<Vec3> {
+        let mut screen = MaybeUninit::uninit();
+
+        unsafe {
+            // returns non-zero when behind
+            if (self.vtable.screen_position)(self, &point, screen.as_mut_ptr()) != 0 {
+                None
+            } else {
+                Some(screen.assume_init())
+            }
+        }
+    }
+}
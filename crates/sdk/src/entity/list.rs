@@ -1,5 +1,9 @@
 use crate::vtable_validate;
+#[cfg(debug_assertions)]
+use elysium_mem::Maps;
 use frosting::ffi::vtable;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
 
 #[derive(Debug)]
 #[repr(C)]
@@ -24,15 +28,81 @@ pub struct EntityList {
     vtable: &'static VTable,
 }
 
+/// The last read of `/proc/self/maps`, forgotten by `EntityList::invalidate_maps` every network
+/// update, so an entity freed since isn't trusted, and read again when something isn't readable
+/// in it.
+#[cfg(debug_assertions)]
+static MAPS: Mutex<Option<Maps>> = Mutex::new(None);
+
+/// Whether `entity`, and it's vtable, can be read according to `maps`.
+#[cfg(debug_assertions)]
+fn is_readable(maps: &Maps, entity: *const u8) -> bool {
+    if !maps.is_readable(entity, 8) {
+        return false;
+    }
+
+    let vtable = unsafe { entity.cast::<*const u8>().read() };
+
+    maps.is_readable(vtable, 8)
+}
+
+/// Whether `entity`, and it's vtable, can be read.
+#[cfg(debug_assertions)]
+fn is_mapped(entity: *const u8) -> bool {
+    let mut maps = MAPS.lock().unwrap_or_else(|error| error.into_inner());
+
+    if let Some(maps) = &*maps {
+        if is_readable(maps, entity) {
+            return true;
+        }
+    }
+
+    // mapped since the last read, or really unmapped
+    let fresh = match Maps::read() {
+        Ok(fresh) => fresh,
+        Err(_) => return true,
+    };
+
+    let readable = is_readable(&fresh, entity);
+
+    *maps = Some(fresh);
+
+    readable
+}
+
+/// In debug builds, replace entities that point to unmapped memory with null.
+#[inline]
+fn checked(entity: *const u8) -> *const u8 {
+    #[cfg(debug_assertions)]
+    if !entity.is_null() && !is_mapped(entity) {
+        println!("elysium | entity at \x1b[38;5;3m{entity:?}\x1b[m isn't mapped, ignored");
+
+        return core::ptr::null();
+    }
+
+    entity
+}
+
 impl EntityList {
+    /// Forget the last read of the memory maps, which debug builds check entities against.
+    ///
+    /// Should be called whenever entities may have been freed, i.e. every network update.
+    #[inline]
+    pub fn invalidate_maps() {
+        #[cfg(debug_assertions)]
+        {
+            *MAPS.lock().unwrap_or_else(|error| error.into_inner()) = None;
+        }
+    }
+
     #[inline]
     pub fn get(&self, index: usize) -> *const u8 {
-        unsafe { (self.vtable.get)(self, index as i32) }
+        checked(unsafe { (self.vtable.get)(self, index as i32) })
     }
 
     #[inline]
     pub fn from_handle(&self, handle: *const u8) -> *const u8 {
-        unsafe { (self.vtable.from_handle)(self, handle) }
+        checked(unsafe { (self.vtable.from_handle)(self, handle) })
     }
 
     #[inline]
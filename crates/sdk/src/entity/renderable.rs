@@ -1,4 +1,4 @@
-use crate::vtable_validate;
+use crate::{vtable_validate, Model};
 use elysium_math::Matrix3x4;
 use frosting::ffi::vtable;
 
@@ -8,7 +8,7 @@ struct VTable {
     _pad0: vtable::Pad<5>,
     should_draw: unsafe extern "C" fn(this: *const Renderable) -> bool,
     _pad1: vtable::Pad<2>,
-    get_model: unsafe extern "C" fn(this: *const Renderable) -> *const u8,
+    get_model: unsafe extern "C" fn(this: *const Renderable) -> *const Model,
     _pad2: vtable::Pad<4>,
     setup_bones: unsafe extern "C" fn(
         this: *const Renderable,
@@ -38,7 +38,7 @@ impl Renderable {
     }
 
     #[inline]
-    pub fn model(&self) -> *const u8 {
+    pub fn model(&self) -> *const Model {
         unsafe { (self.vtable.get_model)(self) }
     }
 
@@ -92,6 +92,7 @@ entries! {
 
     PoseParameter => "m_flPoseParameter",
     RenderMode => "m_nRenderMode",
+    ReserveAmmo => "m_iPrimaryReserveAmmoCount",
 
     RevolverCockTime => "m_flPostponeFireReadyTime",
 
@@ -0,0 +1,17 @@
+//! Game events, `IGameEventManager2` (`GAMEEVENTSMANAGER002`).
+//!
+//! ```ignore
+//! let listener = events.on(|death: PlayerDeath| {
+//!     println!("{} killed {}", death.attacker, death.user_id);
+//! });
+//! ```
+
+pub use game_event::Event;
+pub use listener::Listener;
+pub use manager::EventManager;
+pub use payload::{BulletImpact, GameEvent, PlayerDeath, PlayerHurt, RoundStart};
+
+mod game_event;
+mod listener;
+mod manager;
+mod payload;
@@ -0,0 +1,101 @@
+use crate::{ffi, vtable_validate};
+use frosting::ffi::vtable;
+use std::ffi::{CStr, OsStr};
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<2>,
+    get_name: unsafe extern "C" fn(this: *const Event) -> *const u8,
+    _pad1: vtable::Pad<3>,
+    get_bool: unsafe extern "C" fn(this: *const Event, key: *const u8, default: bool) -> bool,
+    get_int: unsafe extern "C" fn(this: *const Event, key: *const u8, default: i32) -> i32,
+    get_u64: unsafe extern "C" fn(this: *const Event, key: *const u8, default: u64) -> u64,
+    get_float: unsafe extern "C" fn(this: *const Event, key: *const u8, default: f32) -> f32,
+    get_string:
+        unsafe extern "C" fn(this: *const Event, key: *const u8, default: *const u8) -> *const u8,
+}
+
+vtable_validate! {
+    get_name => 2,
+    get_bool => 6,
+    get_int => 7,
+    get_u64 => 8,
+    get_float => 9,
+    get_string => 10,
+}
+
+/// A game event, `IGameEvent`, only valid for the duration of the listener's callback.
+#[repr(C)]
+pub struct Event {
+    vtable: &'static VTable,
+}
+
+/// Call `$method` with `key` as a C string.
+macro_rules! with_key {
+    ($self:ident.$method:ident($key:ident, $($arg:expr),*)) => {{
+        let cstr = ffi::osstr_to_cstr_cow($key);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { ($self.vtable.$method)($self, ptr, $($arg),*) }
+    }};
+}
+
+impl Event {
+    /// The event's name, such as `player_death`.
+    #[inline]
+    pub fn name(&self) -> &str {
+        unsafe { ffi::str_from_ptr_nullable((self.vtable.get_name)(self)) }
+    }
+
+    /// Value of `key` as a `bool`, or `false` if absent.
+    #[inline]
+    pub fn get_bool<S>(&self, key: S) -> bool
+    where
+        S: AsRef<OsStr>,
+    {
+        with_key!(self.get_bool(key, false))
+    }
+
+    /// Value of `key` as an `i32`, or `0` if absent.
+    #[inline]
+    pub fn get_int<S>(&self, key: S) -> i32
+    where
+        S: AsRef<OsStr>,
+    {
+        with_key!(self.get_int(key, 0))
+    }
+
+    /// Value of `key` as a `u64`, or `0` if absent.
+    #[inline]
+    pub fn get_u64<S>(&self, key: S) -> u64
+    where
+        S: AsRef<OsStr>,
+    {
+        with_key!(self.get_u64(key, 0))
+    }
+
+    /// Value of `key` as an `f32`, or `0.0` if absent.
+    #[inline]
+    pub fn get_float<S>(&self, key: S) -> f32
+    where
+        S: AsRef<OsStr>,
+    {
+        with_key!(self.get_float(key, 0.0))
+    }
+
+    /// Value of `key` as a string, or `""` if absent.
+    #[inline]
+    pub fn get_string<S>(&self, key: S) -> String
+    where
+        S: AsRef<OsStr>,
+    {
+        let string = with_key!(self.get_string(key, "\0".as_ptr()));
+
+        if string.is_null() {
+            return String::new();
+        }
+
+        // names are chosen by players, so they may not be valid UTF-8
+        unsafe { CStr::from_ptr(string.cast()).to_string_lossy().into_owned() }
+    }
+}
@@ -0,0 +1,91 @@
+use super::{Event, EventManager};
+use crate::vtable_validate;
+
+/// `EVENT_DEBUG_ID_INIT`, the game refuses listeners that return anything else.
+const EVENT_DEBUG_ID_INIT: i32 = 42;
+
+type Callback = Box<dyn FnMut(&Event) + Send>;
+
+#[repr(C)]
+struct VTable {
+    drop_complete: unsafe extern "C" fn(this: *mut RawListener),
+    drop_deleting: unsafe extern "C" fn(this: *mut RawListener),
+    fire_game_event: unsafe extern "C" fn(this: *mut RawListener, event: *const Event),
+    get_event_debug_id: unsafe extern "C" fn(this: *const RawListener) -> i32,
+}
+
+vtable_validate! {
+    drop_complete => 0,
+    drop_deleting => 1,
+    fire_game_event => 2,
+    get_event_debug_id => 3,
+}
+
+/// Our implementation of `IGameEventListener2`.
+#[repr(C)]
+pub(super) struct RawListener {
+    vtable: &'static VTable,
+    callback: Callback,
+}
+
+/// The game never destroys listeners, it only forgets them.
+unsafe extern "C" fn drop_listener(_this: *mut RawListener) {}
+
+unsafe extern "C" fn fire_game_event(this: *mut RawListener, event: *const Event) {
+    if let Some(event) = event.as_ref() {
+        ((*this).callback)(event);
+    }
+}
+
+unsafe extern "C" fn get_event_debug_id(_this: *const RawListener) -> i32 {
+    EVENT_DEBUG_ID_INIT
+}
+
+static VTABLE: VTable = VTable {
+    drop_complete: drop_listener,
+    drop_deleting: drop_listener,
+    fire_game_event,
+    get_event_debug_id,
+};
+
+/// A registered listener, which is removed when dropped.
+pub struct Listener {
+    manager: &'static EventManager,
+    raw: Box<RawListener>,
+}
+
+unsafe impl Send for Listener {}
+unsafe impl Sync for Listener {}
+
+impl Listener {
+    /// Register `callback` for the event `name`.
+    ///
+    /// Returns `None` if the game refused it, such as when the event doesn't exist.
+    pub(super) fn new(
+        manager: &'static EventManager,
+        name: &str,
+        callback: Callback,
+    ) -> Option<Self> {
+        let mut raw = Box::new(RawListener {
+            vtable: &VTABLE,
+            callback,
+        });
+
+        if manager.add_listener(&mut *raw, name) {
+            Some(Self { manager, raw })
+        } else {
+            None
+        }
+    }
+
+    /// Remove the listener, the same as dropping it.
+    #[inline]
+    pub fn remove(self) {}
+}
+
+impl Drop for Listener {
+    #[inline]
+    fn drop(&mut self) {
+        self.manager.remove_listener(&mut *self.raw);
+    }
+}
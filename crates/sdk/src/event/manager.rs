@@ -0,0 +1,64 @@
+use super::listener::RawListener;
+use super::{Event, GameEvent, Listener};
+use crate::{ffi, vtable_validate};
+use frosting::ffi::vtable;
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<4>,
+    add_listener: unsafe extern "C" fn(
+        this: *const EventManager,
+        listener: *mut RawListener,
+        name: *const u8,
+        server_side: bool,
+    ) -> bool,
+    _pad1: vtable::Pad<1>,
+    remove_listener: unsafe extern "C" fn(this: *const EventManager, listener: *mut RawListener),
+}
+
+vtable_validate! {
+    add_listener => 4,
+    remove_listener => 6,
+}
+
+/// Game event manager interface.
+#[repr(C)]
+pub struct EventManager {
+    vtable: &'static VTable,
+}
+
+impl EventManager {
+    #[inline]
+    pub(super) fn add_listener(&self, listener: *mut RawListener, name: &str) -> bool {
+        let cstr = ffi::osstr_to_cstr_cow(name);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        unsafe { (self.vtable.add_listener)(self, listener, ptr, false) }
+    }
+
+    #[inline]
+    pub(super) fn remove_listener(&self, listener: *mut RawListener) {
+        unsafe { (self.vtable.remove_listener)(self, listener) }
+    }
+
+    /// Call `callback` whenever the event `name` fires, until the listener is dropped.
+    ///
+    /// Returns `None` if the game refused it, such as when the event doesn't exist.
+    #[inline]
+    pub fn listen<F>(&'static self, name: &str, callback: F) -> Option<Listener>
+    where
+        F: FnMut(&Event) + Send + 'static,
+    {
+        Listener::new(self, name, Box::new(callback))
+    }
+
+    /// Call `callback` with the payload of `E` whenever it fires, until the listener is dropped.
+    #[inline]
+    pub fn on<E, F>(&'static self, mut callback: F) -> Option<Listener>
+    where
+        E: GameEvent,
+        F: FnMut(E) + Send + 'static,
+    {
+        self.listen(E::NAME, move |event| callback(E::from_event(event)))
+    }
+}
@@ -0,0 +1,125 @@
+use super::Event;
+use crate::HitGroup;
+use elysium_math::Vec3;
+
+/// A game event with a typed payload.
+pub trait GameEvent: Sized {
+    /// The event's name, as within `resource/modevents.res`.
+    const NAME: &'static str;
+
+    /// Read the payload from `event`.
+    fn from_event(event: &Event) -> Self;
+}
+
+/// `player_death`
+#[derive(Clone, Debug)]
+pub struct PlayerDeath {
+    /// User ID of who died.
+    pub user_id: i32,
+    /// User ID of the killer.
+    pub attacker: i32,
+    /// User ID of who assisted.
+    pub assister: i32,
+    pub weapon: String,
+    pub headshot: bool,
+    /// Number of objects penetrated.
+    pub penetrated: i32,
+}
+
+impl GameEvent for PlayerDeath {
+    const NAME: &'static str = "player_death";
+
+    #[inline]
+    fn from_event(event: &Event) -> Self {
+        Self {
+            user_id: event.get_int("userid"),
+            attacker: event.get_int("attacker"),
+            assister: event.get_int("assister"),
+            weapon: event.get_string("weapon"),
+            headshot: event.get_bool("headshot"),
+            penetrated: event.get_int("penetrated"),
+        }
+    }
+}
+
+/// `player_hurt`
+#[derive(Clone, Debug)]
+pub struct PlayerHurt {
+    /// User ID of who was hurt.
+    pub user_id: i32,
+    /// User ID of who hurt them.
+    pub attacker: i32,
+    /// Remaining health.
+    pub health: i32,
+    /// Remaining armor.
+    pub armor: i32,
+    pub weapon: String,
+    pub damage_health: i32,
+    pub damage_armor: i32,
+    pub hit_group: HitGroup,
+}
+
+impl GameEvent for PlayerHurt {
+    const NAME: &'static str = "player_hurt";
+
+    #[inline]
+    fn from_event(event: &Event) -> Self {
+        Self {
+            user_id: event.get_int("userid"),
+            attacker: event.get_int("attacker"),
+            health: event.get_int("health"),
+            armor: event.get_int("armor"),
+            weapon: event.get_string("weapon"),
+            damage_health: event.get_int("dmg_health"),
+            damage_armor: event.get_int("dmg_armor"),
+            hit_group: HitGroup::from_raw(event.get_int("hitgroup")),
+        }
+    }
+}
+
+/// `bullet_impact`
+#[derive(Clone, Copy, Debug)]
+pub struct BulletImpact {
+    /// User ID of who fired.
+    pub user_id: i32,
+    pub position: Vec3,
+}
+
+impl GameEvent for BulletImpact {
+    const NAME: &'static str = "bullet_impact";
+
+    #[inline]
+    fn from_event(event: &Event) -> Self {
+        Self {
+            user_id: event.get_int("userid"),
+            position: Vec3::from_xyz(
+                event.get_float("x"),
+                event.get_float("y"),
+                event.get_float("z"),
+            ),
+        }
+    }
+}
+
+/// `round_start`
+#[derive(Clone, Debug)]
+pub struct RoundStart {
+    /// Round time limit, in seconds.
+    pub time_limit: i32,
+    pub frag_limit: i32,
+    /// Round objective.
+    pub objective: String,
+}
+
+impl GameEvent for RoundStart {
+    const NAME: &'static str = "round_start";
+
+    #[inline]
+    fn from_event(event: &Event) -> Self {
+        Self {
+            time_limit: event.get_int("timelimit"),
+            frag_limit: event.get_int("fraglimit"),
+            objective: event.get_string("objective"),
+        }
+    }
+}
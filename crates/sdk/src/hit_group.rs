@@ -14,6 +14,22 @@ pub enum HitGroup {
 }
 
 impl HitGroup {
+    /// map a raw hit group, unknown ones are `Generic`
+    #[inline]
+    pub const fn from_raw(hit_group: i32) -> Self {
+        match hit_group {
+            1 => HitGroup::Head,
+            2 => HitGroup::Chest,
+            3 => HitGroup::Stomach,
+            4 => HitGroup::LeftArm,
+            5 => HitGroup::RightArm,
+            6 => HitGroup::LeftLeg,
+            7 => HitGroup::RightLeg,
+            8 => HitGroup::Gear,
+            _ => HitGroup::Generic,
+        }
+    }
+
     /// returns the damage modifier for the given hit group
     #[inline]
     pub const fn damage_modifier(&self) -> f32 {
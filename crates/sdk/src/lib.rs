@@ -22,9 +22,11 @@ pub use class::Class;
 pub use client::Client;
 pub use client_mode::ClientMode;
 pub use console::Console;
+pub use debug_overlay::{Color, DebugOverlay};
 pub use engine::{Engine, PlayerInfo};
 pub use entity::EntityList;
 pub use entry::Entry;
+pub use event::{Event, EventManager};
 pub use frame::Frame;
 pub use globals::Globals;
 pub use hit_group::HitGroup;
@@ -34,6 +36,8 @@ pub use input_system::InputSystem;
 pub use interfaces::{InterfaceKind, Interfaces, LibraryKind};
 pub use item_kind::ItemKind;
 pub use material::MaterialKind;
+pub use model::{Model, ModelInfo};
+pub use model_render::ModelRender;
 pub use network::{Flow, NetworkChannel};
 pub use pad::Pad;
 pub use panorama::{PanoramaEventRegistration, PanoramaUIEngine, UIEngine, UIPanel};
@@ -55,6 +59,7 @@ mod beam;
 mod class;
 mod client_mode;
 mod console;
+mod debug_overlay;
 mod engine;
 mod entry;
 mod frame;
@@ -65,6 +70,7 @@ mod interfaces;
 mod item_kind;
 mod macros;
 mod material;
+mod model_render;
 mod pad;
 mod panorama;
 mod render;
@@ -79,11 +85,14 @@ mod view;
 mod weapon;
 
 pub mod client;
+pub mod concommand;
 pub mod convar;
 pub mod entity;
+pub mod event;
 pub mod ffi;
 pub mod id;
 pub mod input;
+pub mod model;
 pub mod network;
 pub mod player_model;
 pub mod trace;
@@ -34,8 +34,30 @@ macro_rules! vtable_export {
 }
 
 /// validate vtable indicies
+///
+/// given a type, the indices are also available as `Type::VTABLE`, for hooking
 #[macro_export]
 macro_rules! vtable_validate {
+    ($type:ident; $(
+        $ident:ident => $offset:literal,
+    )*) => {
+        $crate::vtable_validate! { $(
+            $ident => $offset,
+        )* }
+
+        /// validated vtable indices
+        #[allow(dead_code)]
+        #[derive(Clone, Copy, Debug)]
+        pub struct VTableIndices {
+            $(pub $ident: usize,)*
+        }
+
+        impl $type {
+            pub const VTABLE: VTableIndices = VTableIndices {
+                $($ident: $offset,)*
+            };
+        }
+    };
     ($(
         $ident:ident => $offset:literal,
     )*) => {
@@ -0,0 +1,17 @@
+//! Models, `IVModelInfoClient` (`VModelInfoClient`), and their studio headers.
+//!
+//! ```ignore
+//! let hdr = model_info.studio_model(model)?;
+//!
+//! for hitbox in hdr.hitbox_set(0)?.hitboxes() {
+//!     println!("{:?} on bone {}", hitbox.name(), hitbox.bone);
+//! }
+//! ```
+
+pub use draw::{DrawModelState, ModelRenderInfo};
+pub use info::{Model, ModelInfo};
+pub use studio::{Bone, BoundingBox, Hdr, HitboxSet, MagicArray, UsedBy};
+
+mod draw;
+mod info;
+mod studio;
@@ -0,0 +1,69 @@
+use super::Model;
+use crate::Pad;
+use core::fmt;
+use elysium_math::{Matrix3x4, Vec3};
+
+#[non_exhaustive]
+#[repr(C)]
+pub struct DrawModelState {
+    pub studio: *const (),
+    pub hardware_data: *const (),
+    pub renderable: *const (),
+    pub model_to_world: *const Matrix3x4,
+    pub decals: *const (),
+    pub draw_flags: i32,
+    pub lod: i32,
+}
+
+impl fmt::Debug for DrawModelState {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("DrawModelState")
+            .field("studio", &self.studio)
+            .field("hardware_data", &self.hardware_data)
+            .field("renderable", &self.renderable)
+            .field("model_to_world", &self.model_to_world)
+            .field("decals", &self.decals)
+            .field("draw_flags", &self.draw_flags)
+            .field("lod", &self.lod)
+            .finish()
+    }
+}
+
+#[non_exhaustive]
+#[repr(C)]
+pub struct ModelRenderInfo {
+    pub origin: Vec3,
+    pub angles: Vec3,
+    _pad0: Pad<4>,
+    pub renderable: *const *const (),
+    pub model: *const Model,
+    pub model_to_world: *const Matrix3x4,
+    pub lighting_offset: *const Matrix3x4,
+    pub lighting_origin: *const Vec3,
+    pub flags: i32,
+    pub entity_index: i32,
+    pub skin: i32,
+    pub body: i32,
+    pub hitboxset: i32,
+    pub instance: *const (),
+}
+
+impl fmt::Debug for ModelRenderInfo {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("ModelRenderInfo")
+            .field("origin", &self.origin)
+            .field("angles", &self.angles)
+            .field("renderable", &self.renderable)
+            .field("model", &self.model)
+            .field("model_to_world", &self.model_to_world)
+            .field("lighting_offset", &self.lighting_offset)
+            .field("lighting_origin", &self.lighting_origin)
+            .field("flags", &self.flags)
+            .field("entity_index", &self.entity_index)
+            .field("skin", &self.skin)
+            .field("body", &self.body)
+            .field("hitboxset", &self.hitboxset)
+            .field("instance", &self.instance)
+            .finish()
+    }
+}
@@ -0,0 +1,104 @@
+use super::Hdr;
+use crate::{ffi, vtable_validate};
+use elysium_math::Vec3;
+use frosting::ffi::vtable;
+use std::borrow::Cow;
+use std::ffi::{CStr, OsStr};
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<2>,
+    get_model: unsafe extern "C" fn(this: *const ModelInfo, index: i32) -> *const Model,
+    get_model_index: unsafe extern "C" fn(this: *const ModelInfo, name: *const u8) -> i32,
+    get_model_name: unsafe extern "C" fn(this: *const ModelInfo, model: *const Model) -> *const u8,
+    _pad1: vtable::Pad<26>,
+    get_studio_model:
+        unsafe extern "C" fn(this: *const ModelInfo, model: *const Model) -> *const Hdr,
+}
+
+vtable_validate! {
+    get_model => 2,
+    get_model_index => 3,
+    get_model_name => 4,
+    get_studio_model => 31,
+}
+
+/// A loaded model.
+#[derive(Debug)]
+#[non_exhaustive]
+#[repr(C)]
+pub struct Model {
+    pub name: [u8; 255],
+    pub needload: i32,
+    pub kind: i32,
+    pub flags: i32,
+    pub mins: Vec3,
+    pub maxs: Vec3,
+    pub radius: f32,
+    pub extradatasize: i32,
+    pub cache: i32,
+    pub data: *const (),
+}
+
+impl Model {
+    /// The model's path, such as `models/player/ctm_fbi.mdl`.
+    #[inline]
+    pub fn name(&self) -> Cow<'_, str> {
+        let len = self
+            .name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(self.name.len());
+
+        String::from_utf8_lossy(&self.name[..len])
+    }
+}
+
+/// Model info interface.
+#[repr(C)]
+pub struct ModelInfo {
+    vtable: &'static VTable,
+}
+
+impl ModelInfo {
+    /// The model at `index`, as in a model precache index.
+    #[inline]
+    pub fn model(&self, index: i32) -> Option<&Model> {
+        unsafe { (self.vtable.get_model)(self, index).as_ref() }
+    }
+
+    /// The index of the model at `name`, `None` if it isn't precached.
+    #[inline]
+    pub fn index_of<S>(&self, name: S) -> Option<i32>
+    where
+        S: AsRef<OsStr>,
+    {
+        let cstr = ffi::osstr_to_cstr_cow(name);
+        let ptr = ffi::cstr_cow_as_ptr(cstr.as_ref());
+
+        match unsafe { (self.vtable.get_model_index)(self, ptr) } {
+            -1 => None,
+            index => Some(index),
+        }
+    }
+
+    /// The path of `model`.
+    #[inline]
+    pub fn name_of(&self, model: &Model) -> Cow<'_, str> {
+        unsafe {
+            let ptr = (self.vtable.get_model_name)(self, model);
+
+            if ptr.is_null() {
+                Cow::Borrowed("")
+            } else {
+                CStr::from_ptr(ptr.cast()).to_string_lossy()
+            }
+        }
+    }
+
+    /// The studio header of `model`, `None` for brush and sprite models.
+    #[inline]
+    pub fn studio_model(&self, model: &Model) -> Option<&Hdr> {
+        unsafe { (self.vtable.get_studio_model)(self, model).as_ref() }
+    }
+}
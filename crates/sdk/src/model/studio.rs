@@ -0,0 +1,293 @@
+use crate::{object_validate, Pad};
+use core::marker::PhantomData;
+use elysium_math::{Matrix3x4, Vec3};
+use std::borrow::Cow;
+use std::ffi::CStr;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+#[repr(i32)]
+#[rustfmt::skip] // rustfmt does an ugly
+pub enum UsedBy {
+    Anything    = 0x00_0F_FF_00,
+    Hitbox      = 0x00_00_01_00, // bone (or child) used by a hitbox
+    Attachment  = 0x00_00_02_00, // bone (or child) used by an attachment point
+    VertexMask  = 0x00_03_FC_00,
+    VertexLoD0  = 0x00_00_04_00, // bone (or child) used by the top-level model via skinned vertex
+    VertexLoD1  = 0x00_00_08_00,
+    VertexLoD2  = 0x00_00_10_00,
+    VertexLoD3  = 0x00_00_20_00,
+    VertexLoD4  = 0x00_00_40_00,
+    VertexLoD5  = 0x00_00_80_00,
+    VertexLoD6  = 0x00_01_00_00,
+    VertexLoD7  = 0x00_02_00_00,
+    BoneMerge   = 0x00_04_00_00, // bone is available for bone merge to occur
+    AlwaysSetup = 0x00_08_00_00,
+}
+
+/// An array stored after the structure containing it, `offset` bytes from its start.
+#[derive(Debug)]
+#[repr(C)]
+pub struct MagicArray<T> {
+    len: i32,
+    offset: i32,
+    phantom: PhantomData<T>,
+}
+
+impl<T> MagicArray<T> {
+    pub const fn len(&self) -> i32 {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len <= 0
+    }
+
+    pub const fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Pointer to the element at `index`, relative to `base_address`, the containing structure.
+    pub unsafe fn get_unchecked(&self, base_address: *const u8, index: i32) -> *const T {
+        base_address
+            .offset(self.offset as isize)
+            .cast::<T>()
+            .offset(index as isize)
+    }
+
+    /// The element at `index`, relative to `base_address`, the containing structure.
+    ///
+    /// # Safety
+    ///
+    /// `base_address` must be the structure this array is a field of.
+    pub unsafe fn get<'a>(&self, base_address: *const u8, index: i32) -> Option<&'a T> {
+        if index < 0 || index >= self.len {
+            return None;
+        }
+
+        Some(&*self.get_unchecked(base_address, index))
+    }
+
+    /// Iterate elements, relative to `base_address`, the containing structure.
+    ///
+    /// # Safety
+    ///
+    /// `base_address` must be the structure this array is a field of.
+    pub unsafe fn iter<'a>(&self, base_address: *const u8) -> impl ExactSizeIterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        let array = MagicArray::<T> {
+            len: self.len,
+            offset: self.offset,
+            phantom: PhantomData,
+        };
+
+        (0..self.len.max(0)).map(move |index| &*array.get_unchecked(base_address, index))
+    }
+}
+
+/// Read the string `offset` bytes from `base_address`.
+unsafe fn name_at<'a>(base_address: *const u8, offset: i32) -> Cow<'a, str> {
+    CStr::from_ptr(base_address.offset(offset as isize).cast()).to_string_lossy()
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[repr(C)]
+pub struct Bone {
+    pub name_offset: i32,
+    pub parent: i32,
+    pub bone_controller: [i32; 6],
+    pub position: Vec3,
+    pub quaternion: [f32; 4],
+    pub rotation: [f32; 3],
+    pub position_scale: Vec3,
+    pub rotation_scale: Vec3,
+    pub position_to_bone: Matrix3x4,
+    pub quaternion_alignment: [f32; 4],
+    pub flags: i32,
+    pub procedural_kind: i32,
+    pub procedural_offset: i32,
+    pub physics_bone: i32,
+    pub surface_prop_offset: i32,
+    pub contents: i32,
+    pub surface_prop_lookup: i32,
+    _pad0: Pad<28>,
+}
+
+impl Bone {
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe { name_at(self.as_ptr(), self.name_offset) }
+    }
+
+    /// Index of the parent bone, `None` for the root.
+    pub fn parent(&self) -> Option<i32> {
+        match self.parent {
+            -1 => None,
+            parent => Some(parent),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[repr(C)]
+pub struct BoundingBox {
+    pub bone: i32,
+    pub group: i32,
+    pub max: Vec3,
+    pub min: Vec3,
+    pub hitbox_name_offset: i32,
+    _pad0: Pad<12>,
+    pub radius: f32,
+    _pad1: Pad<16>,
+}
+
+impl BoundingBox {
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    pub fn name(&self) -> Option<Cow<'_, str>> {
+        unsafe {
+            match self.hitbox_name_offset {
+                0 => None,
+                offset => Some(name_at(self.as_ptr(), offset)),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[repr(C)]
+pub struct HitboxSet {
+    pub name_offset: i32,
+    pub hitboxes: MagicArray<BoundingBox>,
+}
+
+impl HitboxSet {
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe { name_at(self.as_ptr(), self.name_offset) }
+    }
+
+    pub fn hitbox(&self, index: i32) -> Option<&BoundingBox> {
+        unsafe { self.hitboxes.get(self.as_ptr(), index) }
+    }
+
+    pub fn hitboxes(&self) -> impl ExactSizeIterator<Item = &BoundingBox> {
+        unsafe { self.hitboxes.iter(self.as_ptr()) }
+    }
+
+    pub unsafe fn hitbox_unchecked(&self, index: i32) -> *const BoundingBox {
+        self.hitboxes.get_unchecked(self.as_ptr(), index)
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[repr(C)]
+pub struct Hdr {
+    pub id: i32,
+    pub version: i32,
+    pub checksum: i32,
+    pub name: [u8; 64],
+    pub length: i32,
+    pub eye_position: Vec3,
+    pub illumination_position: Vec3,
+    pub hull_min: Vec3,
+    pub hull_max: Vec3,
+    pub view_bounding_box_min: Vec3,
+    pub view_bounding_box_max: Vec3,
+    pub flags: i32,
+    pub bones: MagicArray<Bone>,
+    pub bone_controllers: MagicArray<()>,
+    pub hitbox_sets: MagicArray<HitboxSet>,
+    pub local_anims: MagicArray<()>,
+    pub local_seqs: MagicArray<()>,
+    pub textures: MagicArray<()>,
+    pub raw_textures: MagicArray<()>,
+    pub replacable_textures: MagicArray<()>,
+    pub body_parts: MagicArray<()>,
+    pub local_attachments: MagicArray<()>,
+    pub local_nodes: MagicArray<()>,
+    pub flex_desc: MagicArray<()>,
+    pub flex_controllers: MagicArray<()>,
+    pub flex_rules: MagicArray<()>,
+    pub ik_chains: MagicArray<()>,
+    pub mouths: MagicArray<()>,
+    pub local_pose_parameters: MagicArray<()>,
+    pub surface_pos_offset: i32,
+    pub key_values: MagicArray<()>,
+    pub local_ik_autoplaylocks: MagicArray<()>,
+    pub mass: f32,
+    pub contents: i32,
+    pub include_models: MagicArray<()>,
+    pub virtual_model: *mut (),
+    pub animation_block_name_offset: i32,
+    pub animation_blocks: MagicArray<()>,
+    pub bone_table_by_name_index: i32,
+    pub vertex_base: *const (),
+    pub index_base: *const (),
+    pub constant_directional_light_dot: u8,
+    pub root_lod: u8,
+    pub allowed_root_lods: u8,
+    _pad0: Pad<5>,
+    pub flex_controller_ui: MagicArray<()>,
+    _pad1: Pad<16>,
+}
+
+object_validate! {
+    Hdr;
+    bones => 156,
+    hitbox_sets => 172,
+}
+
+impl Hdr {
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+
+    /// The model's name, as compiled.
+    pub fn name(&self) -> Cow<'_, str> {
+        let len = self
+            .name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(self.name.len());
+
+        String::from_utf8_lossy(&self.name[..len])
+    }
+
+    pub fn bone(&self, index: i32) -> Option<&Bone> {
+        unsafe { self.bones.get(self.as_ptr(), index) }
+    }
+
+    pub fn bones(&self) -> impl ExactSizeIterator<Item = &Bone> {
+        unsafe { self.bones.iter(self.as_ptr()) }
+    }
+
+    /// Index of the bone named `name`.
+    pub fn bone_index(&self, name: &str) -> Option<i32> {
+        self.bones()
+            .position(|bone| bone.name() == name)
+            .map(|index| index as i32)
+    }
+
+    pub fn hitbox_set(&self, index: i32) -> Option<&HitboxSet> {
+        unsafe { self.hitbox_sets.get(self.as_ptr(), index) }
+    }
+
+    pub fn hitbox_sets(&self) -> impl ExactSizeIterator<Item = &HitboxSet> {
+        unsafe { self.hitbox_sets.iter(self.as_ptr()) }
+    }
+}
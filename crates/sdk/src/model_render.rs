@@ -0,0 +1,38 @@
+use crate::vtable_validate;
+use elysium_math::Matrix3x4;
+use frosting::ffi::vtable;
+
+#[repr(C)]
+struct VTable {
+    _pad0: vtable::Pad<21>,
+    draw_model_execute: unsafe extern "C" fn(
+        this: *const ModelRender,
+        context: *const (),
+        state: *const (),
+        info: *const (),
+        bone_to_world: *const Matrix3x4,
+    ),
+}
+
+vtable_validate! { ModelRender;
+    draw_model_execute => 21,
+}
+
+/// Model Render interface.
+#[repr(C)]
+pub struct ModelRender {
+    vtable: &'static VTable,
+}
+
+impl ModelRender {
+    #[inline]
+    pub unsafe fn draw_model_execute(
+        &self,
+        context: *const (),
+        state: *const (),
+        info: *const (),
+        bone_to_world: *const Matrix3x4,
+    ) {
+        (self.vtable.draw_model_execute)(self, context, state, info, bone_to_world)
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{Entry, Target};
+use elysium_elf::{Elf, ProgramHeader};
+use providence_pattern::{Memory, ResolveError, Resolved};
+
+/// A library as it would be loaded, built from the `PT_LOAD` segments of it's file.
+///
+/// Addresses are the virtual addresses within the file, which are offsets from where the
+/// library is loaded.
+pub struct Image<'a> {
+    elf: Elf<'a>,
+    segments: Vec<ProgramHeader>,
+}
+
+impl<'a> Image<'a> {
+    pub fn new(elf: Elf<'a>) -> Self {
+        let segments = elf
+            .program_headers()
+            .filter(ProgramHeader::is_load)
+            .collect();
+
+        Self { elf, segments }
+    }
+
+    /// Executable segments, and their addresses.
+    pub fn executable(&self) -> impl Iterator<Item = (u64, &'a [u8])> + '_ {
+        self.segments
+            .iter()
+            .filter(|segment| segment.is_executable())
+            .filter_map(|segment| Some((segment.address, self.elf.segment_data(segment)?)))
+    }
+
+    /// The section named `name`, and it's address, if it's loaded.
+    pub fn section(&self, name: &str) -> Option<(u64, &'a [u8])> {
+        let section = self.elf.section(name)?;
+
+        if !section.header.is_alloc() {
+            return None;
+        }
+
+        Some((
+            section.header.address,
+            self.elf.section_data(&section.header)?,
+        ))
+    }
+
+    /// What the game scans for `target`, and their addresses, see `Target::ranges`.
+    pub fn ranges(&self, target: Target) -> Vec<(u64, &'a [u8])> {
+        target.ranges(self.section(target.section_name()), self.executable())
+    }
+}
+
+impl<'a> Memory for Image<'a> {
+    fn read(&self, address: usize, bytes: &mut [u8]) -> bool {
+        let address = address as u64;
+        let end = match address.checked_add(bytes.len() as u64) {
+            Some(end) => end,
+            None => return false,
+        };
+
+        let segment = self.segments.iter().find(|segment| {
+            address >= segment.address && end <= segment.address + segment.memory_size
+        });
+
+        let (segment, data) = match segment {
+            Some(segment) => match self.elf.segment_data(segment) {
+                Some(data) => (segment, data),
+                None => return false,
+            },
+            None => return false,
+        };
+
+        let offset = (address - segment.address) as usize;
+
+        // what's past the file data is zeroed, such as `.bss`
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = data.get(offset + index).copied().unwrap_or(0);
+        }
+
+        true
+    }
+}
+
+/// Result of checking a signature against a library.
+#[derive(Debug)]
+pub struct Report {
+    /// Addresses of every match.
+    pub addresses: Vec<u64>,
+    /// The first match, after the signature's steps, if there was a match.
+    pub resolved: Option<Result<Resolved, ResolveError>>,
+}
+
+impl Report {
+    /// Whether the signature has exactly one match, which resolves.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.addresses.len() == 1 && matches!(self.resolved, Some(Ok(_)))
+    }
+}
+
+/// Scan `.text` of `image` for `entry`, or it's executable segments if it has no sections, as the
+/// game does.
+pub fn check(image: &Image<'_>, entry: &Entry) -> Report {
+    let scanner = entry.signature.scanner();
+    let addresses: Vec<u64> = image
+        .ranges(Target::Text)
+        .into_iter()
+        .flat_map(|(address, data)| {
+            scanner
+                .find_iter(data)
+                .map(move |offset| address + offset as u64)
+        })
+        .collect();
+
+    let resolved = addresses
+        .first()
+        .map(|address| entry.signature.resolve(image, *address as usize));
+
+    Report {
+        addresses,
+        resolved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elysium_elf::{Builder, PF_R, PF_W, PF_X};
+    use providence_pattern::{Signature, Step};
+
+    // 1000: mov eax, [rip + 0xffa]
+    // 1006: ret
+    // 1007: push rbp
+    static LOAD: Signature<2> =
+        Signature::new("8B 05", &[Step::Rip { disp: 2, len: 6 }, Step::U32]);
+    static PUSH: Signature<1> = Signature::new("55", &[]);
+    static MISSING: Signature<2> = Signature::new("0F 0B", &[]);
+
+    fn entry(signature: &'static dyn providence_pattern::AnySignature) -> Entry {
+        Entry {
+            library: "fixture.so",
+            name: "fixture",
+            signature,
+        }
+    }
+
+    #[test]
+    fn check() {
+        let code = [0x8B, 0x05, 0xFA, 0x0F, 0x00, 0x00, 0xC3, 0x55, 0x55];
+        let bytes = Builder::new()
+            .segment(".text", PF_R | PF_X, 0x1000, &code)
+            .segment(".data", PF_R | PF_W, 0x2000, &0xDEADBEEFu32.to_le_bytes())
+            // data that looks like code, which mustn't be scanned
+            .segment(".rodata", PF_R, 0x3000, &[0x8B, 0x05])
+            .build();
+
+        let elf = Elf::parse(&bytes).unwrap();
+        let image = Image::new(elf);
+
+        let report = super::check(&image, &entry(&LOAD));
+
+        assert!(report.is_ok());
+        assert_eq!(report.addresses, [0x1000]);
+        assert_eq!(report.resolved, Some(Ok(Resolved::Value(0xDEADBEEF))));
+
+        let report = super::check(&image, &entry(&PUSH));
+
+        assert!(!report.is_ok());
+        assert_eq!(report.addresses, [0x1007, 0x1008]);
+
+        let report = super::check(&image, &entry(&MISSING));
+
+        assert!(!report.is_ok());
+        assert_eq!(report.resolved, None);
+    }
+
+    #[test]
+    fn check_text_only() {
+        let bytes = Builder::new()
+            .segment(".plt", PF_R | PF_X, 0x1000, &[0x0F, 0x0B])
+            .segment(".text", PF_R | PF_X, 0x2000, &[0x55, 0xC3])
+            .build();
+
+        let elf = Elf::parse(&bytes).unwrap();
+        let image = Image::new(elf);
+
+        // executable, but not scanned by the game, as the sections are known
+        let report = super::check(&image, &entry(&MISSING));
+
+        assert!(report.addresses.is_empty());
+
+        let report = super::check(&image, &entry(&PUSH));
+
+        assert!(report.is_ok());
+        assert_eq!(report.addresses, [0x2000]);
+    }
+}
@@ -0,0 +1,111 @@
+//! Every signature elysium scans for, along with the library it's within.
+
+use providence_pattern::{AnySignature, Signature, Step};
+
+pub use image::{check, Image, Report};
+pub use target::Target;
+
+mod image;
+mod target;
+
+/// a signature, the library it's within, and a name for logging
+pub struct Entry {
+    pub library: &'static str,
+    pub name: &'static str,
+    pub signature: &'static dyn AnySignature,
+}
+
+/// every signature, checked by `elysium-sigcheck`
+pub static SIGNATURES: &[Entry] = &[
+    Entry {
+        library: "client_client.so",
+        name: "animation_layers",
+        signature: &ANIMATION_LAYERS,
+    },
+    Entry {
+        library: "client_client.so",
+        name: "animation_state",
+        signature: &ANIMATION_STATE,
+    },
+    Entry {
+        library: "client_client.so",
+        name: "save_data",
+        signature: &SAVE_DATA,
+    },
+    Entry {
+        library: "client_client.so",
+        name: "restore_data",
+        signature: &RESTORE_DATA,
+    },
+    Entry {
+        library: "client_client.so",
+        name: "on_post_restore_data",
+        signature: &ON_POST_RESTORE_DATA,
+    },
+    Entry {
+        library: "engine_client.so",
+        name: "cl_move",
+        signature: &CL_MOVE,
+    },
+    Entry {
+        library: "engine_client.so",
+        name: "host_run_frame_input",
+        signature: &HOST_RUN_FRAME_INPUT,
+    },
+    Entry {
+        library: "client_client.so",
+        name: "write_user_command",
+        signature: &WRITE_USER_COMMAND,
+    },
+    Entry {
+        library: "client_client.so",
+        name: "write_user_command_delta_to_buffer",
+        signature: &WRITE_USER_COMMAND_DELTA_TO_BUFFER,
+    },
+];
+
+/// offset of the animation layers within the player, read from an instruction in the function
+pub static ANIMATION_LAYERS: Signature<18> = Signature::new(
+    "55 48 89 E5 41 56 41 55 41 89 F5 41 54 53 48 89 FB 8B",
+    &[Step::Add(35), Step::U32],
+);
+
+/// offset of the animation state within the player, read from an instruction in the function
+pub static ANIMATION_STATE: Signature<22> = Signature::new(
+    "55 48 89 E5 53 48 89 FB 48 83 EC 28 48 8B 05 ?? ?? ?? ?? 48 8B 00",
+    &[Step::Add(52), Step::U32],
+);
+
+pub static SAVE_DATA: Signature<25> = Signature::new(
+    "55 48 89 E5 41 57 41 89 CF 41 56 41 55 41 89 D5 41 54 53 48 89 FB 48 81 EC",
+    &[],
+);
+
+pub static RESTORE_DATA: Signature<10> = Signature::new("E9 ?? ?? ?? ?? 90 55 48 63 F6", &[]);
+
+pub static ON_POST_RESTORE_DATA: Signature<16> =
+    Signature::new("55 BE ?? ?? ?? ?? 48 89 E5 41 54 53 48 89 FB E8", &[]);
+
+/// xref `"CL_Move"` in `VPROF` macro
+///
+/// [engine/cl_main.cpp](https://github.com/VSES/SourceEngine2007/blob/master/se2007/engine/cl_main.cpp)
+pub static CL_MOVE: Signature<31> = Signature::new(
+    "55 48 89 E5 41 57 41 56 41 89 FE 41 55 41 54 53 48 81 EC 98 01 00 00 F3 0F 11 85 5C FE FF FF",
+    &[],
+);
+
+/// xref `CL_Move`
+///
+/// [engine/host.cpp](https://github.com/VSES/SourceEngine2007/blob/master/se2007/engine/host.cpp)
+pub static HOST_RUN_FRAME_INPUT: Signature<39> =
+    Signature::new("55 48 89 E5 41 57 66 41 0F 7E C7 41 56 41 55 41 89 FD 41 54 53 48 83 EC 08 48 8B 1D C8 25 94 00 44 8B 83 0C 10 00 00", &[]);
+
+/// xref `"WriteUsercmd: from=%d to=%d\"`
+///
+/// [game/shared/usercmd.cpp](https://github.com/VSES/SourceEngine2007/blob/master/se2007/game/shared/usercmd.cpp)
+pub static WRITE_USER_COMMAND: Signature<15> =
+    Signature::new("55 48 89 E5 41 56 41 55 4C 8D 35 B1 19 17 02", &[]);
+
+/// xref `WriteUsercmd`
+pub static WRITE_USER_COMMAND_DELTA_TO_BUFFER: Signature<16> =
+    Signature::new("55 48 8D 05 38 BC 68 01 41 89 F2 48 89 E5 41 57", &[]);
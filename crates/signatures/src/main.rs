@@ -0,0 +1,115 @@
+//! Check every signature against the game's libraries on disk.
+
+use elysium_elf::Elf;
+use elysium_signatures::{Image, SIGNATURES};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+/// where libraries are within the game's directory
+const LIBRARY_DIRECTORIES: [&str; 2] = ["bin/linux64", "csgo/bin/linux64"];
+
+/// find `library_name` within `paths`, which are libraries or directories containing them
+fn find_library(paths: &[PathBuf], library_name: &str) -> Option<PathBuf> {
+    paths.iter().find_map(|path| {
+        if path.is_file() {
+            return (path.file_name()? == library_name).then(|| path.clone());
+        }
+
+        let candidates = [path.join(library_name)].into_iter().chain(
+            LIBRARY_DIRECTORIES
+                .iter()
+                .map(|directory| path.join(directory).join(library_name)),
+        );
+
+        candidates.into_iter().find(|candidate| candidate.is_file())
+    })
+}
+
+/// check every signature within `library_name`, `false` if any failed
+fn check_library(path: &Path, library_name: &str) -> bool {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            println!("\x1b[38;5;1m{library_name}\x1b[m: unable to read {path:?}: {error}");
+
+            return false;
+        }
+    };
+
+    let elf = match Elf::parse(&bytes) {
+        Ok(elf) => elf,
+        Err(error) => {
+            println!("\x1b[38;5;1m{library_name}\x1b[m: {error}");
+
+            return false;
+        }
+    };
+
+    println!("\x1b[38;5;2m{library_name}\x1b[m ({path:?})");
+
+    let image = Image::new(elf);
+    let mut ok = true;
+
+    for entry in SIGNATURES
+        .iter()
+        .filter(|entry| entry.library == library_name)
+    {
+        let report = elysium_signatures::check(&image, entry);
+        let name = entry.name;
+        let matches = report.addresses.len();
+        let color = if report.is_ok() { 2 } else { 1 };
+
+        print!(
+            "  \x1b[38;5;{color}m{name}\x1b[m: \x1b[38;5;3m{matches}\x1b[m matches at \x1b[38;5;3m{:x?}\x1b[m",
+            report.addresses
+        );
+
+        match report.resolved {
+            Some(Ok(resolved)) if !entry.signature.steps().is_empty() => {
+                println!(", resolved to \x1b[38;5;3m{resolved:x?}\x1b[m")
+            }
+            Some(Err(error)) => println!(", unable to resolve: \x1b[38;5;1m{error}\x1b[m"),
+            _ => println!(),
+        }
+
+        if !report.is_ok() {
+            println!("    pattern \x1b[38;5;2m{:?}\x1b[m", entry.signature);
+        }
+
+        ok &= report.is_ok();
+    }
+
+    ok
+}
+
+fn main() -> ExitCode {
+    let paths: Vec<PathBuf> = env::args_os().skip(1).map(PathBuf::from).collect();
+
+    if paths.is_empty() {
+        println!("usage: elysium-sigcheck <game directory | library>...");
+
+        return ExitCode::FAILURE;
+    }
+
+    let library_names: BTreeSet<&str> = SIGNATURES.iter().map(|entry| entry.library).collect();
+    let mut ok = true;
+
+    for library_name in library_names {
+        match find_library(&paths, library_name) {
+            Some(path) => ok &= check_library(&path, library_name),
+            None => {
+                println!("\x1b[38;5;1m{library_name}\x1b[m: not found");
+
+                ok = false;
+            }
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,47 @@
+use core::fmt;
+
+/// what part of a library to scan
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Target {
+    /// `.text`, or every executable segment if the library's sections are unknown
+    Text,
+    /// `.rodata`
+    ReadOnlyData,
+    /// `.data`
+    Data,
+}
+
+impl Target {
+    pub const fn section_name(&self) -> &'static str {
+        match self {
+            Target::Text => ".text",
+            Target::ReadOnlyData => ".rodata",
+            Target::Data => ".data",
+        }
+    }
+
+    /// ranges to scan, given the section named `section_name`, if the library's sections are
+    /// known, and the library's executable segments
+    ///
+    /// both elysium, and `elysium-sigcheck`, choose what to scan with this, so a signature is
+    /// checked against exactly what the game scans
+    pub fn ranges<R, I>(&self, section: Option<R>, executable: I) -> Vec<R>
+    where
+        I: IntoIterator<Item = R>,
+    {
+        if let Some(section) = section {
+            return vec![section];
+        }
+
+        match self {
+            Target::Text => executable.into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.section_name())
+    }
+}
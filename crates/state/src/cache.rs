@@ -5,6 +5,8 @@ use providence_model::Bones;
 
 /// A cached player.
 pub struct Player {
+    /// Whether the rest is up to date, cleared when the player disconnects, or goes dormant.
+    pub valid: bool,
     pub bones: Bones,
     pub id: u64,
     pub index: i32,
@@ -18,6 +20,7 @@ pub struct Player {
 
 impl Player {
     pub const fn new() -> Player {
+        let valid = false;
         let bones = Bones::zero();
         let id = 0;
         let index = 0;
@@ -29,6 +32,7 @@ impl Player {
         let total_ammo = 0;
 
         Self {
+            valid,
             bones,
             health,
             id,
@@ -44,6 +48,11 @@ impl Player {
     pub fn name(&self) -> &str {
         &*self.name
     }
+
+    /// Mark the player as out of date.
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
 }
 
 /// An array of cached players.
@@ -59,6 +68,28 @@ impl Players {
 
         Self { players }
     }
+
+    /// The player at `index`, the engine's entity index, if it's valid.
+    pub fn get(&self, index: usize) -> Option<&Player> {
+        let player = self.players.get(index.checked_sub(1)?)?;
+
+        player.valid.then(|| player)
+    }
+
+    /// The player at `index`, the engine's entity index, valid or not.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Player> {
+        self.players.get_mut(index.checked_sub(1)?)
+    }
+
+    /// Iterate every valid player.
+    pub fn iter_valid(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|player| player.valid)
+    }
+
+    /// Mark every player as out of date, such as when disconnected.
+    pub fn invalidate_all(&mut self) {
+        self.players.iter_mut().for_each(Player::invalidate);
+    }
 }
 
 impl Deref for Players {
@@ -0,0 +1,297 @@
+//! Per-player history of networked states, for lag compensation.
+
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use elysium_math::Vec3;
+use providence_model::Bones;
+
+/// Records kept per player, enough for `sv_maxunlag` (at most 1 second) at 64 tick.
+pub const MAX_RECORDS: usize = 64;
+
+/// Moving further than this between updates is treated as a teleport, which the server doesn't
+/// lag compensate (`sv_lagcompensation_teleport_dist`, squared).
+pub const TELEPORT_DISTANCE_SQUARED: f32 = 64.0 * 64.0;
+
+/// How far off the server's estimate of our latency can be.
+const MAX_CORRECTION: f32 = 0.2;
+
+/// A player's state, as of a network update.
+#[derive(Clone, Copy, Debug)]
+pub struct Record {
+    pub simulation_time: f32,
+    pub origin: Vec3,
+    pub eye_angle: Vec3,
+    pub bones: Bones,
+    pub flags: i32,
+    pub velocity: Vec3,
+    /// The model, to notice it changing.
+    pub model: *const u8,
+}
+
+/// What the server's lag compensation is limited by.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// The server's current time.
+    pub current_time: f32,
+    /// Incoming plus outgoing latency, from `NetworkChannel`.
+    pub latency: f32,
+    /// Interpolation amount.
+    pub lerp: f32,
+    /// `sv_maxunlag`.
+    pub max_unlag: f32,
+}
+
+impl Record {
+    /// Whether the server would still lag compensate to this record.
+    pub fn is_valid(&self, limits: &Limits) -> bool {
+        // older than the server keeps
+        let dead_time = (limits.current_time - limits.max_unlag).floor();
+
+        if self.simulation_time < dead_time {
+            return false;
+        }
+
+        let correct = (limits.latency + limits.lerp).clamp(0.0, limits.max_unlag);
+        let delta = correct - (limits.current_time - self.simulation_time);
+
+        delta.abs() <= MAX_CORRECTION
+    }
+}
+
+/// A ring buffer of a player's records, newest last.
+pub struct History {
+    records: Vec<Record>,
+    /// Index of the oldest record, once full.
+    head: usize,
+}
+
+impl History {
+    pub const fn new() -> History {
+        let records = Vec::new();
+        let head = 0;
+
+        Self { records, head }
+    }
+
+    /// Number of records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// The most recent record.
+    pub fn latest(&self) -> Option<&Record> {
+        let index = match self.head {
+            0 => self.records.len().checked_sub(1)?,
+            head => head - 1,
+        };
+
+        self.records.get(index)
+    }
+
+    /// Iterate records, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Record> {
+        let (newer, older) = self.records.split_at(self.head);
+
+        older.iter().chain(newer.iter())
+    }
+
+    /// Iterate records the server would still lag compensate to, from oldest to newest.
+    pub fn iter_valid<'a>(
+        &'a self,
+        limits: &'a Limits,
+    ) -> impl DoubleEndedIterator<Item = &'a Record> + 'a {
+        self.iter().filter(move |record| record.is_valid(limits))
+    }
+
+    /// Remove every record.
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.head = 0;
+    }
+
+    /// Add `record`, unless it's simulation time isn't newer than the latest.
+    ///
+    /// Every record is removed first if the player teleported, or their model changed, as the
+    /// server won't lag compensate across either.
+    pub fn push(&mut self, record: Record) {
+        if let Some(latest) = self.latest() {
+            if record.simulation_time <= latest.simulation_time {
+                return;
+            }
+
+            let teleported =
+                record.origin.distance_squared(latest.origin) > TELEPORT_DISTANCE_SQUARED;
+
+            if teleported || !ptr::eq(record.model, latest.model) {
+                self.clear();
+            }
+        }
+
+        if self.records.len() < MAX_RECORDS {
+            self.records.push(record);
+        } else {
+            self.records[self.head] = record;
+            self.head = (self.head + 1) % MAX_RECORDS;
+        }
+    }
+}
+
+/// Every player's history.
+pub struct Histories {
+    histories: [History; 64],
+}
+
+impl Histories {
+    const INIT: History = History::new();
+
+    pub const fn new() -> Histories {
+        let histories = [Self::INIT; 64];
+
+        Self { histories }
+    }
+
+    /// History of the player at `index`, the engine's entity index.
+    pub fn get(&self, index: usize) -> Option<&History> {
+        self.histories.get(index.checked_sub(1)?)
+    }
+
+    /// History of the player at `index`, the engine's entity index.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut History> {
+        self.histories.get_mut(index.checked_sub(1)?)
+    }
+
+    /// Remove every record of every player, such as when disconnected.
+    pub fn clear(&mut self) {
+        self.histories.iter_mut().for_each(History::clear);
+    }
+}
+
+impl Deref for Histories {
+    type Target = [History; 64];
+
+    fn deref(&self) -> &Self::Target {
+        &self.histories
+    }
+}
+
+impl DerefMut for Histories {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.histories
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(simulation_time: f32, x: f32, model: usize) -> Record {
+        Record {
+            simulation_time,
+            origin: Vec3::from_xyz(x, 0.0, 0.0),
+            eye_angle: Vec3::zero(),
+            bones: Bones::zero(),
+            flags: 0,
+            velocity: Vec3::zero(),
+            model: model as *const u8,
+        }
+    }
+
+    fn times(history: &History) -> Vec<f32> {
+        history
+            .iter()
+            .map(|record| record.simulation_time)
+            .collect()
+    }
+
+    #[test]
+    fn push() {
+        let mut history = History::new();
+
+        assert!(history.is_empty());
+        assert!(history.latest().is_none());
+
+        for tick in 0..MAX_RECORDS + 3 {
+            history.push(record(tick as f32, 0.0, 1));
+        }
+
+        // the oldest are overwritten, and iteration is still oldest to newest
+        let expected: Vec<f32> = (3..MAX_RECORDS + 3).map(|tick| tick as f32).collect();
+
+        assert_eq!(history.len(), MAX_RECORDS);
+        assert_eq!(times(&history), expected);
+        assert_eq!(
+            history
+                .iter()
+                .next_back()
+                .map(|record| record.simulation_time),
+            Some((MAX_RECORDS + 2) as f32)
+        );
+        assert_eq!(
+            history.latest().map(|record| record.simulation_time),
+            Some((MAX_RECORDS + 2) as f32)
+        );
+
+        // not newer than the latest
+        history.push(record(1.0, 0.0, 1));
+
+        assert_eq!(times(&history), expected);
+
+        history.clear();
+
+        assert!(history.is_empty());
+        assert!(history.latest().is_none());
+    }
+
+    #[test]
+    fn push_clears() {
+        let mut history = History::new();
+
+        history.push(record(1.0, 0.0, 1));
+        history.push(record(2.0, 32.0, 1));
+
+        assert_eq!(times(&history), [1.0, 2.0]);
+
+        // teleported
+        history.push(record(3.0, 128.0, 1));
+
+        assert_eq!(times(&history), [3.0]);
+
+        // model changed
+        history.push(record(4.0, 128.0, 2));
+
+        assert_eq!(times(&history), [4.0]);
+    }
+
+    #[test]
+    fn is_valid() {
+        let limits = Limits {
+            current_time: 10.0,
+            latency: 0.05,
+            lerp: 0.05,
+            max_unlag: 0.2,
+        };
+
+        // exactly as far back as the server corrects to, and at the edges of the correction
+        assert!(record(9.9, 0.0, 1).is_valid(&limits));
+        assert!(record(9.75, 0.0, 1).is_valid(&limits));
+        assert!(record(10.05, 0.0, 1).is_valid(&limits));
+        assert!(!record(9.65, 0.0, 1).is_valid(&limits));
+        assert!(!record(10.15, 0.0, 1).is_valid(&limits));
+
+        // within the correction, but older than the server keeps
+        let limits = Limits {
+            current_time: 10.1,
+            latency: 1.0,
+            lerp: 0.0,
+            max_unlag: 1.0,
+        };
+
+        assert!(record(9.0, 0.0, 1).is_valid(&limits));
+        assert!(!record(8.99, 0.0, 1).is_valid(&limits));
+    }
+}
@@ -16,6 +16,7 @@ use elysium_menu::Menu;
 use hooks::Hooks;
 use iced_elysium_gl::Viewport;
 use iced_native::{Point, Size};
+use lag::Histories;
 use local::Local;
 use material::Materials;
 
@@ -29,8 +30,10 @@ mod shared_option;
 
 pub mod cache;
 pub mod hooks;
+pub mod lag;
 pub mod local;
 pub mod material;
+pub mod unload;
 
 struct State {
     gl_library: SharedOption<elysium_gl::Gl>,
@@ -45,6 +48,12 @@ struct State {
     window_size: Shared<Size<u32>>,
 
     hooks: Hooks,
+    patches: Shared<Vec<elysium_mem::Patch>>,
+    detours: Shared<Vec<elysium_mem::Detour>>,
+    call_sites: Shared<Vec<elysium_mem::CallSite>>,
+    vmt_hooks: Shared<Vec<Box<dyn Send + Sync>>>,
+    listeners: Shared<Vec<Box<dyn Send + Sync>>>,
+    commands: Shared<Vec<Box<dyn Send + Sync>>>,
 
     materials: Materials,
 
@@ -55,6 +64,7 @@ struct State {
     local: Local,
 
     players: Shared<Players>,
+    lag_records: Shared<Histories>,
     prediction_time: Shared<f32>,
     send_packet: Shared<*mut bool>,
     tick_count: Shared<i32>,
@@ -92,6 +102,12 @@ static STATE: ManuallyDrop<State> = ManuallyDrop::new(State {
     window_size: Shared::new(Size::new(0, 0)),
 
     hooks: Hooks::new(),
+    patches: Shared::new(Vec::new()),
+    detours: Shared::new(Vec::new()),
+    call_sites: Shared::new(Vec::new()),
+    vmt_hooks: Shared::new(Vec::new()),
+    listeners: Shared::new(Vec::new()),
+    commands: Shared::new(Vec::new()),
 
     materials: Materials::new(),
 
@@ -102,6 +118,7 @@ static STATE: ManuallyDrop<State> = ManuallyDrop::new(State {
     local: Local::new(),
 
     players: Shared::new(Players::new()),
+    lag_records: Shared::new(Histories::new()),
     prediction_time: Shared::new(0.0),
     send_packet: Shared::new(ptr::null_mut()),
     tick_count: Shared::new(0),
@@ -235,12 +252,75 @@ pub fn update_window_size(size: Size<u32>) {
     }
 }
 
+/// Keep a patch applied, rather than reverting it when dropped.
+#[inline]
+pub fn keep_patch(patch: elysium_mem::Patch) {
+    unsafe {
+        STATE.patches.as_mut().push(patch);
+    }
+}
+
+/// Keep a detour installed, rather than uninstalling it when dropped.
+#[inline]
+pub fn keep_detour(detour: elysium_mem::Detour) {
+    unsafe {
+        STATE.detours.as_mut().push(detour);
+    }
+}
+
+/// Keep a call redirected, rather than restoring it when dropped.
+#[inline]
+pub fn keep_call_site(call_site: elysium_mem::CallSite) {
+    unsafe {
+        STATE.call_sites.as_mut().push(call_site);
+    }
+}
+
+/// Keep a virtual function hooked, rather than unhooking it when dropped.
+#[inline]
+pub fn keep_vmt_hook<F>(hook: elysium_mem::VmtHook<F>)
+where
+    F: Copy + Send + Sync + 'static,
+{
+    unsafe {
+        STATE.vmt_hooks.as_mut().push(Box::new(hook));
+    }
+}
+
+/// Keep a game event listener registered, rather than removing it when dropped.
+#[inline]
+pub fn keep_listener<L>(listener: L)
+where
+    L: Send + Sync + 'static,
+{
+    unsafe {
+        STATE.listeners.as_mut().push(Box::new(listener));
+    }
+}
+
+/// Keep a console command, or variable, registered, rather than unregistering it when dropped.
+#[inline]
+pub fn keep_command<C>(command: C)
+where
+    C: Send + Sync + 'static,
+{
+    unsafe {
+        STATE.commands.as_mut().push(Box::new(command));
+    }
+}
+
 /// Returns a reference to the player cache.
 #[inline]
 pub unsafe fn players() -> &'static mut Players {
     STATE.players.as_mut()
 }
 
+/// Returns a reference to every player's lag records.
+#[inline]
+pub unsafe fn lag_records() -> &'static mut Histories {
+    STATE.lag_records.as_mut()
+}
+
 /// Return's a reference to engine prediction time.
 #[inline]
 pub unsafe fn prediction_time() -> &'static mut f32 {
@@ -305,7 +385,7 @@ pub unsafe fn network_channel() -> *const u8 {
 #[inline]
 pub unsafe fn set_network_channel(network_channel: *const u8) {
     STATE
-        .engine
+        .network_channel
         .write(NonNull::new_unchecked(network_channel.as_mut()));
 }
 
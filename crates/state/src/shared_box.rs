@@ -33,4 +33,9 @@ impl<T> SharedBox<T> {
     pub unsafe fn write(&self, value: T) {
         self.0.write(Box::new(value));
     }
+
+    #[inline]
+    pub unsafe fn take(&self) -> Option<Box<T>> {
+        self.0.take()
+    }
 }
@@ -0,0 +1,135 @@
+//! Unloading elysium from the game.
+//!
+//! Unloading happens in stages, `request` is called from the keybind (or a console command), then
+//! the next `SDL_GL_SwapWindow` frees the menu's OpenGL objects, as that's the only place with
+//! the context current, restores every hook, and starts the thread which waits for them to
+//! return, before unloading the library.
+
+use super::STATE;
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const RUNNING: u8 = 0;
+const REQUESTED: u8 = 1;
+const UNLOADING: u8 = 2;
+
+static STAGE: AtomicU8 = AtomicU8::new(RUNNING);
+
+/// How long a thread may take between jumping to a hook, and calling `enter_hook`.
+///
+/// A thread that loaded the hook's address before it was restored may still enter it, which
+/// `active_hooks` can't count until it reaches `enter_hook`.
+const ENTER_GRACE: Duration = Duration::from_millis(100);
+
+/// How long a thread may take between dropping it's `HookGuard`, and leaving the hook.
+///
+/// The guard is dropped before the hook's epilogue, and `ret`, so `active_hooks` reaching zero
+/// doesn't mean nothing is executing our code.
+const LEAVE_GRACE: Duration = Duration::from_millis(100);
+
+/// Number of hooks currently executing.
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Marks a hook as executing, for as long as it's alive.
+pub struct HookGuard(());
+
+impl Drop for HookGuard {
+    #[inline]
+    fn drop(&mut self) {
+        ACTIVE.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Mark the calling hook as executing, until the guard is dropped.
+///
+/// Every hook should hold one for it's whole body, including the call to the original.
+#[inline]
+pub fn enter_hook() -> HookGuard {
+    ACTIVE.fetch_add(1, Ordering::AcqRel);
+
+    HookGuard(())
+}
+
+/// Number of hooks currently executing.
+#[inline]
+pub fn active_hooks() -> usize {
+    ACTIVE.load(Ordering::Acquire)
+}
+
+/// Request elysium to be unloaded.
+///
+/// Does nothing if already requested.
+#[inline]
+pub fn request() {
+    let _ = STAGE.compare_exchange(RUNNING, REQUESTED, Ordering::AcqRel, Ordering::Acquire);
+}
+
+/// Has an unload been requested, that hasn't been started yet?
+#[inline]
+pub fn is_requested() -> bool {
+    STAGE.load(Ordering::Acquire) == REQUESTED
+}
+
+/// Is elysium being unloaded?
+///
+/// Hooks should pass straight through to the original function once this is true.
+#[inline]
+pub fn is_unloading() -> bool {
+    STAGE.load(Ordering::Acquire) != RUNNING
+}
+
+/// Claim the requested unload, returns `false` if another thread already has.
+#[inline]
+pub fn begin() -> bool {
+    STAGE
+        .compare_exchange(REQUESTED, UNLOADING, Ordering::AcqRel, Ordering::Acquire)
+        .is_ok()
+}
+
+/// Free the menu's OpenGL objects.
+///
+/// Must be called with `context` current, i.e. from within `SDL_GL_SwapWindow`.
+#[inline]
+pub unsafe fn destroy_menu(context: &elysium_gl::Context) {
+    if let Some(mut menu) = STATE.menu.take() {
+        menu.destroy(context);
+    }
+}
+
+/// Restore every hook, remove every game event listener and console command.
+///
+/// Must be called from the game's thread, i.e. from within `SDL_GL_SwapWindow`, as the game
+/// doesn't expect them to change beneath it. Hooks already executing continue to, see `wait`.
+#[inline]
+pub unsafe fn restore() {
+    // dropping them restores the original
+    STATE.commands.as_mut().clear();
+    STATE.listeners.as_mut().clear();
+    STATE.vmt_hooks.as_mut().clear();
+    STATE.call_sites.as_mut().clear();
+    STATE.detours.as_mut().clear();
+    STATE.patches.as_mut().clear();
+}
+
+/// Wait until no hook is executing, after `restore`, then drop the library loaders.
+///
+/// Hooks are only counted between `enter_hook`, and dropping the guard, so this also waits for
+/// `ENTER_GRACE` before counting, and `LEAVE_GRACE` after none are counted. Both are grace
+/// periods, long enough for a few instructions, rather than guarantees, a thread descheduled for
+/// longer within either window may still be executing our code.
+///
+/// Mustn't be called from within a hook, as it'd wait on itself.
+pub unsafe fn wait() {
+    thread::sleep(ENTER_GRACE);
+
+    while active_hooks() != 0 {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    thread::sleep(LEAVE_GRACE);
+
+    // nothing can reach them anymore
+    let _ = STATE.gl_library.take();
+    let _ = STATE.sdl_library.take();
+}
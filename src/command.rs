@@ -0,0 +1,195 @@
+//! console command handlers, each returns the text to write to the console
+
+use crate::pattern::Libraries;
+use crate::{module, state};
+use elysium_sdk::convar::Kind;
+use elysium_sdk::Console;
+use std::fmt::Write;
+use std::fs;
+
+/// where `elysium_config_save` saves to, relative to the game's directory
+const CONFIG_PATH: &str = "csgo/cfg/elysium.cfg";
+
+/// parse a hexadecimal number, with or without `0x`
+fn parse_hex(text: &str) -> Option<usize> {
+    let text = text.strip_prefix("0x").unwrap_or(text);
+
+    usize::from_str_radix(text, 16).ok()
+}
+
+/// `elysium_signature <library> <offset>` or `elysium_signature <address>`
+///
+/// generate the shortest unique pattern for some code
+pub unsafe fn signature(libraries: &Libraries, args: &[&str]) -> String {
+    let result = match args {
+        [address] => match parse_hex(address) {
+            Some(address) => libraries.generate_at(address as *const u8).map(
+                |(library_name, offset, pattern)| {
+                    format!("{library_name}+{offset:#x}: {pattern:?}\n")
+                },
+            ),
+            None => return format!("invalid address `{address}`\n"),
+        },
+        [library_name, offset] => match parse_hex(offset) {
+            Some(offset) => libraries
+                .generate(library_name, offset)
+                .map(|pattern| format!("{library_name}+{offset:#x}: {pattern:?}\n")),
+            None => return format!("invalid offset `{offset}`\n"),
+        },
+        _ => return "usage: elysium_signature <library> <offset> | <address>\n".into(),
+    };
+
+    match result {
+        Ok(text) => text,
+        Err(error) => format!("{error}\n"),
+    }
+}
+
+/// `elysium_reload`
+///
+/// rebuild and reload the feature module
+pub fn reload() -> String {
+    module::request_reload();
+
+    "reloading elysium-features\n".into()
+}
+
+/// `elysium_unload`
+///
+/// unload elysium from the game
+pub fn unload() -> String {
+    state::unload::request();
+
+    "unloading elysium\n".into()
+}
+
+/// `elysium_config_save`
+///
+/// save the config, as commands setting our variables, for `exec elysium`
+pub fn config_save() -> String {
+    let config = format!(
+        "elysium_thirdperson {}\n",
+        unsafe { state::local::thirdperson() } as i32
+    );
+
+    match fs::write(CONFIG_PATH, config) {
+        Ok(()) => format!("saved config to `{CONFIG_PATH}`\n"),
+        Err(error) => format!("unable to save config to `{CONFIG_PATH}`: {error}\n"),
+    }
+}
+
+/// `elysium_cvars [filter]`
+///
+/// list every variable containing `filter`, with it's flags, default, and description
+pub fn cvars(console: &Console, args: &[&str]) -> String {
+    let filter = args.first().copied().unwrap_or("");
+    let mut text = String::new();
+
+    for var in console.iter().filter_map(|base| base.as_var()) {
+        let name = var.name();
+
+        if !name.contains(filter) {
+            continue;
+        }
+
+        let _ = writeln!(
+            text,
+            "{name} = {:?} (default {:?}, flags {:#x}) {}",
+            var.string(),
+            var.default_value(),
+            var.flags().0,
+            var.help(),
+        );
+    }
+
+    text
+}
+
+/// register `handler` as the command `name`, it's unregistered when elysium is unloaded
+fn command<F>(console: &'static Console, name: &str, help: &str, handler: F)
+where
+    F: Fn(&[&str]) -> String + Send + Sync + 'static,
+{
+    let command = console.register_command(name, help, move |args| {
+        let _guard = state::unload::enter_hook();
+        let args = args.iter().collect::<Vec<_>>();
+        let args = args.iter().map(|arg| arg.as_ref()).collect::<Vec<_>>();
+
+        // the console truncates long writes
+        for line in handler(&args).split_inclusive('\n') {
+            console.write(line);
+        }
+    });
+
+    match command {
+        Some(command) => {
+            state::keep_command(command);
+            println!("elysium | registered command \x1b[38;5;2m{name}\x1b[m");
+        }
+        None => println!("elysium | unable to register command \x1b[38;5;2m{name}\x1b[m"),
+    }
+}
+
+/// register the variable `name`, it's unregistered when elysium is unloaded
+fn var<T, F>(console: &'static Console, name: &str, default: T, help: &str, set: F)
+where
+    T: Kind,
+    F: Fn(T) + Send + Sync + 'static,
+{
+    let var = console.register_var(name, default, help, move |value| {
+        let _guard = state::unload::enter_hook();
+
+        set(value)
+    });
+
+    match var {
+        Some(var) => {
+            state::keep_command(var);
+            println!("elysium | registered variable \x1b[38;5;2m{name}\x1b[m");
+        }
+        None => println!("elysium | unable to register variable \x1b[38;5;2m{name}\x1b[m"),
+    }
+}
+
+/// register every command and variable
+pub fn register(console: &'static Console) {
+    command(
+        console,
+        "elysium_signature",
+        "generate the shortest unique pattern for some code",
+        |args| unsafe { signature(&Libraries::new(), args) },
+    );
+
+    command(
+        console,
+        "elysium_cvars",
+        "list every variable, optionally only those containing a filter",
+        move |args| cvars(console, args),
+    );
+
+    command(
+        console,
+        "elysium_reload",
+        "rebuild and reload the feature module",
+        |_args| reload(),
+    );
+
+    command(console, "elysium_unload", "unload elysium", |_args| {
+        unload()
+    });
+
+    command(
+        console,
+        "elysium_config_save",
+        "save the config, load it with `exec elysium`",
+        |_args| config_save(),
+    );
+
+    var(
+        console,
+        "elysium_thirdperson",
+        false,
+        "whether thirdperson is enabled",
+        state::local::set_thirdperson,
+    );
+}
@@ -1,7 +1,7 @@
 use crate::{state, Networked};
 use elysium_math::{Matrix3x4, Vec3};
 use elysium_sdk::entity::{Networkable, ObserverMode, Renderable};
-use elysium_sdk::{object_validate, vtable_validate};
+use elysium_sdk::{object_validate, vtable_validate, EntityList, Model};
 use frosting::ffi::vtable;
 
 #[derive(Debug)]
@@ -51,7 +51,7 @@ impl Entity {
 
     /// the entity's model
     #[inline]
-    pub fn model(&self) -> *const u8 {
+    pub fn model(&self) -> *const Model {
         self.renderable.model()
     }
 
@@ -160,4 +160,96 @@ impl Entity {
             *this.byte_add(networked.player.has_helmet).cast()
         }
     }
+
+    /// only for base_players
+    #[inline]
+    pub fn health(&self) -> i32 {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+
+            *this.byte_add(networked.base_player.health).cast()
+        }
+    }
+
+    /// only for players, the active weapon
+    #[inline]
+    pub fn weapon(&self) -> *const Entity {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+            let entity_list = &*state::entity_list().cast::<EntityList>();
+            let handle: u32 = *this.byte_add(networked.player.weapon).cast();
+
+            // `CBaseHandle` is passed by value
+            entity_list.from_handle(handle as usize as *const u8).cast()
+        }
+    }
+
+    /// only for base_weapons
+    #[inline]
+    pub fn magazine_ammo(&self) -> i32 {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+
+            *this.byte_add(networked.base_weapon.magazine).cast()
+        }
+    }
+
+    /// only for base_weapons
+    #[inline]
+    pub fn reserve_ammo(&self) -> i32 {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+
+            *this.byte_add(networked.base_weapon.reserve_ammo).cast()
+        }
+    }
+
+    /// only for base_weapons
+    #[inline]
+    pub fn next_attack_available_after(&self) -> f32 {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+
+            *this
+                .byte_add(networked.base_weapon.next_attack_available_after)
+                .cast()
+        }
+    }
+
+    /// only for weapons
+    #[inline]
+    pub fn revolver_cock_time(&self) -> f32 {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+
+            *this.byte_add(networked.weapon.revolver_cock_time).cast()
+        }
+    }
+
+    #[inline]
+    pub fn simulation_time(&self) -> f32 {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+
+            *this.byte_add(networked.base_entity.simulation_time).cast()
+        }
+    }
+
+    /// only for players
+    #[inline]
+    pub fn eye_angle(&self) -> Vec3 {
+        unsafe {
+            let this = (self as *const Self).cast::<u8>();
+            let networked = &*state::networked().cast::<Networked>();
+
+            *this.byte_add(networked.player.eye_angle).cast()
+        }
+    }
 }
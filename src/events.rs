@@ -0,0 +1,40 @@
+//! Game event listeners.
+
+use crate::state;
+use elysium_sdk::event::{GameEvent, PlayerHurt};
+use elysium_sdk::EventManager;
+
+/// Register `callback` for `E`, it's removed when elysium is unloaded.
+pub fn on<E, F>(events: &'static EventManager, mut callback: F)
+where
+    E: GameEvent,
+    F: FnMut(E) + Send + 'static,
+{
+    let listener = events.on(move |event: E| {
+        let _guard = state::unload::enter_hook();
+
+        callback(event)
+    });
+
+    match listener {
+        Some(listener) => {
+            state::keep_listener(listener);
+            println!("elysium | listening for \x1b[38;5;2m{}\x1b[m", E::NAME);
+        }
+        None => println!(
+            "elysium | unable to listen for \x1b[38;5;2m{}\x1b[m",
+            E::NAME
+        ),
+    }
+}
+
+/// Register every listener.
+pub fn register(events: &'static EventManager) {
+    // damage log
+    on(events, |hurt: PlayerHurt| {
+        println!(
+            "elysium | user \x1b[38;5;2m{}\x1b[m hit user \x1b[38;5;2m{}\x1b[m in the \x1b[38;5;2m{:?}\x1b[m for \x1b[38;5;3m{}\x1b[m ({} remaining)",
+            hurt.attacker, hurt.user_id, hurt.hit_group, hurt.damage_health, hurt.health
+        );
+    });
+}
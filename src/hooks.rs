@@ -1,6 +1,9 @@
 //! Function hooks.
 
+use crate::state;
+
 pub use create_move::create_move;
+pub use draw_model_execute::draw_model_execute;
 pub use frame_stage_notify::frame_stage_notify;
 pub use override_view::override_view;
 pub use poll_event::poll_event;
@@ -9,14 +12,45 @@ pub use write_user_command_delta_to_buffer::write_user_command_delta_to_buffer;
 
 #[allow(dead_code, unused_imports)]
 mod create_move;
+mod draw_model_execute;
 mod frame_stage_notify;
 mod override_view;
 mod poll_event;
 mod swap_window;
 mod write_user_command_delta_to_buffer;
 
+/// Hook a virtual function, by it's index within `vtable_validate!`, and set the original.
+///
+/// ```ignore
+/// vmt_hook!(client, Client.frame_stage_notify => frame_stage_notify as FrameStageNotify, set_frame_stage_notify);
+/// ```
+macro_rules! vmt_hook {
+    ($object:expr, $type:ident.$slot:ident => $hook:ident as $signature:ident, $set:ident) => {{
+        let hook = elysium_mem::VmtHook::swap(
+            $object as *const $type,
+            $type::VTABLE.$slot,
+            $crate::hooks::$hook as $crate::state::hooks::$signature,
+        )
+        .expect(stringify!($signature));
+
+        $crate::state::hooks::$set(hook.original());
+        $crate::state::keep_vmt_hook(hook);
+        println!(concat!(
+            "elysium | hooked \x1b[38;5;2m",
+            stringify!($signature),
+            "\x1b[m"
+        ));
+    }};
+}
+
+pub(crate) use vmt_hook;
+
 /// `CL_Move` hook.
-pub unsafe extern "C" fn cl_move(_accumulated_extra_samples: f32, _final_tick: bool) {}
+pub unsafe extern "C" fn cl_move(accumulated_extra_samples: f32, final_tick: bool) {
+    let _guard = state::unload::enter_hook();
+
+    state::hooks::cl_move(accumulated_extra_samples, final_tick);
+}
 
 /// `CL_SendMove` hook.
 pub unsafe extern "C" fn cl_send_move(_accumulated_extra_samples: f32, _final_tick: bool) {}
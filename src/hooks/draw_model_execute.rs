@@ -0,0 +1,14 @@
+use crate::state;
+
+/// `DrawModelExecute` hook.
+pub unsafe extern "C" fn draw_model_execute(
+    this: *const u8,
+    context: *const u8,
+    draw_state: *const u8,
+    info: *const u8,
+    bone_to_world: *const u8,
+) {
+    let _guard = state::unload::enter_hook();
+
+    state::hooks::draw_model_execute(this, context, draw_state, info, bone_to_world);
+}
@@ -1,75 +1,202 @@
+use crate::state::lag::Record;
 use crate::{state, Entity};
-use core::mem;
 use core::ptr::NonNull;
+use core::{mem, slice};
 use elysium_math::Vec3;
-use elysium_sdk::convar::Vars;
+use elysium_sdk::convar::{Kind, Var, Vars};
 use elysium_sdk::{Engine, EntityList, Frame, Globals, Input};
+use providence_model::MAX_BONES;
+
+/// `BONE_USED_BY_ANYTHING`
+const BONE_MASK: i32 = 0x0007FF00;
+
+/// Update the player cache, and lag records, from the latest network update.
+#[inline]
+unsafe fn update_players(engine: &Engine, entity_list: &EntityList, globals: &Globals) {
+    let players = state::players();
+    let histories = state::lag_records();
+
+    if !engine.is_in_game() {
+        players.invalidate_all();
+        histories.clear();
+
+        return;
+    }
+
+    for index in 1..=64 {
+        let (player, history) = match (players.get_mut(index), histories.get_mut(index)) {
+            (Some(player), Some(history)) => (player, history),
+            _ => continue,
+        };
+
+        let entity = entity_list.get(index);
+
+        // disconnected, or not networked to us
+        if entity.is_null() {
+            player.invalidate();
+            history.clear();
+            continue;
+        }
+
+        let entity = &*entity.cast::<Entity>();
+
+        if entity.is_dormant() || !entity.is_player() {
+            player.invalidate();
+            history.clear();
+            continue;
+        }
+
+        let info = match engine.get_player_info(index as i32) {
+            Some(info) => info,
+            None => {
+                player.invalidate();
+                history.clear();
+                continue;
+            }
+        };
+
+        let name_len = info
+            .name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(info.name.len());
+
+        player.name.clear();
+        player
+            .name
+            .push_str(&String::from_utf8_lossy(&info.name[..name_len]));
+
+        player.id = info.steam_id;
+        player.index = index as i32;
+        player.health = entity.health();
+
+        let bones = slice::from_raw_parts_mut(player.bones.as_mut_ptr(), MAX_BONES);
+
+        entity.setup_bones(bones, BONE_MASK, globals.current_time);
+
+        history.push(Record {
+            simulation_time: entity.simulation_time(),
+            origin: entity.origin(),
+            eye_angle: entity.eye_angle(),
+            bones: player.bones,
+            flags: entity.flags(),
+            velocity: entity.velocity(),
+            model: entity.model().cast(),
+        });
+
+        let weapon = entity.weapon();
+
+        if weapon.is_null() {
+            player.magazine_ammo = 0;
+            player.total_ammo = 0;
+            player.next_attack_available_after = 0.0;
+            player.revolver_cock_time = 0.0;
+        } else {
+            let weapon = &*weapon;
+
+            player.magazine_ammo = weapon.magazine_ammo();
+            player.total_ammo = weapon.reserve_ammo();
+            player.next_attack_available_after = weapon.next_attack_available_after();
+            player.revolver_cock_time = weapon.revolver_cock_time();
+        }
+
+        player.valid = true;
+    }
+}
+
+/// Write `value` to `var`, unless the game doesn't have it.
+#[inline]
+fn write<T>(var: Option<&Var<T>>, value: T)
+where
+    T: Kind,
+{
+    if let Some(var) = var {
+        var.write(value);
+    }
+}
 
 /// `FrameStageNotify` hook.
 pub unsafe extern "C" fn frame_stage_notify(this: *const u8, frame: i32) {
+    let _guard = state::unload::enter_hook();
+
     let engine = &*state::engine().cast::<Engine>();
     let entity_list = &*state::entity_list().cast::<EntityList>();
     let globals = &*state::globals().cast::<Globals>();
     let input = &mut *state::input().as_mut().cast::<Input>();
     let vars = &*state::vars().cast::<Vars>();
 
+    // leave the game as it was found
+    if state::unload::is_unloading() {
+        input.thirdperson = false;
+        state::hooks::frame_stage_notify(this, frame);
+
+        return;
+    }
+
     *state::view_angle() = engine.view_angle();
 
     let frame: Frame = mem::transmute(frame);
+
+    if frame == Frame::UpdateEnd {
+        // entities may have been freed
+        EntityList::invalidate_maps();
+        update_players(engine, entity_list, globals);
+    }
+
     let index = engine.local_player_index();
     let entity = entity_list.get(index);
 
     // misc
-    vars.allow_developer.write(true);
-    vars.cheats.write(true);
-    vars.developer.write(true);
+    write(vars.allow_developer, true);
+    write(vars.cheats, true);
+    write(vars.developer, true);
 
     // useful
-    vars.show_grenade_path.write(true);
+    write(vars.show_grenade_path, true);
 
     // annoying
-    vars.auto_help.write(false);
-    vars.show_help.write(false);
+    write(vars.auto_help, false);
+    write(vars.show_help, false);
 
     // these disable when true
-    vars.engine_sleep.write(true);
-    vars.html_motd.write(true);
-    vars.freeze_cam.write(true);
-    vars.panorama_blur.write(true);
+    write(vars.engine_sleep, true);
+    write(vars.html_motd, true);
+    write(vars.freeze_cam, true);
+    write(vars.panorama_blur, true);
 
     // p100
-    vars.hud.write(false);
+    write(vars.hud, false);
 
     // shadows
-    vars.csm.write(false);
-    vars.csm_shadows.write(false);
-    vars.feet_shadows.write(false);
-    vars.prop_shadows.write(false);
-    vars.rope_shadows.write(false);
-    vars.shadows.write(false);
-    vars.skybox3d.write(false);
-    vars.viewmodel_shadows.write(false);
-    vars.world_shadows.write(false);
+    write(vars.csm, false);
+    write(vars.csm_shadows, false);
+    write(vars.feet_shadows, false);
+    write(vars.prop_shadows, false);
+    write(vars.rope_shadows, false);
+    write(vars.shadows, false);
+    write(vars.skybox3d, false);
+    write(vars.viewmodel_shadows, false);
+    write(vars.world_shadows, false);
 
     // useless objects
-    vars.ropes.write(false);
-    vars.sprites.write(false);
+    write(vars.ropes, false);
+    write(vars.sprites, false);
 
     // translucent things
-    vars.water_fog.write(false);
+    write(vars.water_fog, false);
 
     // overlay
-    vars.underwater_overlay.write(false);
+    write(vars.underwater_overlay, false);
 
     // effects
-    vars.alien_blood.write(false);
-    vars.human_blood.write(false);
-    vars.decals.write(false);
-    vars.jiggle_bones.write(false);
-    vars.rain.write(false);
+    write(vars.alien_blood, false);
+    write(vars.human_blood, false);
+    write(vars.decals, false);
+    write(vars.jiggle_bones, false);
+    write(vars.rain, false);
 
     // phsyics
-    vars.physics_timescale.write(0.5);
+    write(vars.physics_timescale, 0.5);
 
     if entity.is_null() {
         state::local::set_aim_punch_angle(Vec3::zero());
@@ -3,6 +3,8 @@ use elysium_sdk::View;
 
 /// `OverrideView` hook.
 pub unsafe extern "C" fn override_view(this: *const u8, view: *mut u8) {
+    let _guard = state::unload::enter_hook();
+
     let view = &mut *view.cast::<View>();
 
     view.angle = *state::view_angle();
@@ -1,14 +1,29 @@
 use crate::state;
 use iced_native::keyboard::Event::{KeyPressed, KeyReleased};
-use iced_native::keyboard::KeyCode::Insert;
+use iced_native::keyboard::KeyCode::{End, Insert};
 use iced_native::mouse::Button::Other;
 use iced_native::mouse::Event::{ButtonPressed, ButtonReleased};
 use iced_native::{mouse, Event};
 
 /// `SDL_PollEvent` hook.
 pub unsafe extern "C" fn poll_event(sdl_event: *mut sdl2_sys::SDL_Event) -> i32 {
+    let _guard = state::unload::enter_hook();
     let result = state::hooks::poll_event(sdl_event);
 
+    // the menu is freed by the next frame
+    if state::unload::is_unloading() {
+        return result;
+    }
+
+    // end, unless it's typed into a text field, such as the console, or chat
+    if sdl2_sys::SDL_IsTextInputActive() == sdl2_sys::SDL_bool::SDL_FALSE {
+        elysium_input::map_event(*sdl_event, |event| {
+            if let Event::Keyboard(KeyPressed { key_code: End, .. }) = event {
+                state::unload::request();
+            }
+        });
+    }
+
     if !state::is_menu_none() {
         let menu = state::menu_unchecked();
 
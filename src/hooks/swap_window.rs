@@ -5,6 +5,21 @@ use iced_native::Size;
 
 /// `SDL_GL_SwapWindow` hook.
 pub unsafe extern "C" fn swap_window(sdl_window: *mut sdl2_sys::SDL_Window) {
+    let _guard = state::unload::enter_hook();
+
+    if state::unload::is_unloading() {
+        // the context is only current here, so the menu is freed before anything else
+        if state::unload::begin() {
+            state::unload::destroy_menu(state::gl_context());
+            state::unload::restore();
+            crate::unload::spawn();
+        }
+
+        state::hooks::swap_window(sdl_window);
+
+        return;
+    }
+
     let mut width = MaybeUninit::uninit();
     let mut height = MaybeUninit::uninit();
 
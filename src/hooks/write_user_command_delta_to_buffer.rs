@@ -11,6 +11,8 @@ pub unsafe extern "C" fn write_user_command_delta_to_buffer(
     to: i32,
     _new_command: u8,
 ) -> bool {
+    let _guard = state::unload::enter_hook();
+
     let mut zero_command = MaybeUninit::<Command>::zeroed();
     let zero_command = zero_command.as_mut_ptr();
     let input = &*state::input().cast::<Input>();
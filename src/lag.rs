@@ -0,0 +1,39 @@
+//! Lag compensation limits.
+
+use crate::state;
+use crate::state::lag::Limits;
+use elysium_sdk::convar::Vars;
+use elysium_sdk::{Engine, Flow, Globals};
+
+/// Interpolation amount, as the server computes it from our cvars.
+#[inline]
+fn lerp(vars: &Vars) -> Option<f32> {
+    let update_rate = vars.update_rate?.read();
+    // not `clamp`, which panics if the server's bounds are inverted
+    let interp_ratio = vars
+        .interp_ratio?
+        .read()
+        .max(vars.min_interp_ratio?.read())
+        .min(vars.max_interp_ratio?.read());
+
+    Some(vars.interp?.read().max(interp_ratio / update_rate))
+}
+
+/// What the server's lag compensation is currently limited by, if connected, and every cvar it
+/// depends on exists.
+pub unsafe fn limits() -> Option<Limits> {
+    let engine = &*state::engine().cast::<Engine>();
+    let globals = &*state::globals().cast::<Globals>();
+    let vars = &*state::vars().cast::<Vars>();
+    let network_channel = engine.get_network_channel().as_ref()?;
+
+    let latency =
+        network_channel.get_latency(Flow::Outgoing) + network_channel.get_latency(Flow::Incoming);
+
+    Some(Limits {
+        current_time: globals.current_time,
+        latency,
+        lerp: lerp(vars)?,
+        max_unlag: vars.max_lag_comp?.read(),
+    })
+}
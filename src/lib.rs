@@ -7,10 +7,11 @@
 #![feature(const_ptr_offset_from)]
 
 use elysium_dl::Library;
+use elysium_mem::{CallSite, Patch};
 use elysium_sdk::convar::Vars;
-use elysium_sdk::{Client, Console};
+use elysium_sdk::{Client, ClientMode, Console, EventManager, ModelRender};
+use hooks::vmt_hook;
 use std::path::Path;
-use std::time::Duration;
 use std::{mem, thread};
 
 pub use elysium_state as state;
@@ -22,9 +23,14 @@ mod entity;
 pub mod networked;
 //mod simulation;
 
+pub mod command;
+pub mod events;
 pub mod hooks;
+pub mod lag;
 pub mod library;
+pub mod module;
 pub mod pattern;
+pub mod unload;
 
 // this is called by glibc after the library is loaded into a process
 #[link_section = ".init_array"]
@@ -65,19 +71,14 @@ fn main() {
     // wait for serverbrowser.so to load as it is the last to load.
     println!("elysium | waiting for \x1b[38;5;2m`serverbrowser_client.so`\x1b[m to load");
 
-    loop {
-        if Library::exists("./bin/linux64/serverbrowser_client.so") {
-            break;
-        }
-
-        thread::sleep(Duration::from_millis(500));
-    }
+    Library::wait("./bin/linux64/serverbrowser_client.so");
 
     println!("elysium | \x1b[38;5;2m`serverbrowser_client.so`\x1b[m loaded, continuing...");
 
     let interfaces = library::load_interfaces();
     let console: &'static Console = unsafe { &*interfaces.convar.cast() };
     let client: &'static Client = unsafe { &*interfaces.client.cast() };
+    let events: &'static EventManager = unsafe { &*interfaces.events.cast() };
     let globals = client.globals();
     let input = client.input();
 
@@ -88,7 +89,11 @@ fn main() {
         let var_name = var_kind.as_str();
         let address = console.var(var_nul_name);
 
-        println!("elysium | config variable \x1b[38;5;2m{var_name}\x1b[m found at \x1b[38;5;3m{address:?}\x1b[m");
+        if address.is_null() {
+            println!("elysium | config variable \x1b[38;5;2m{var_name}\x1b[m not found, features using it are disabled");
+        } else {
+            println!("elysium | config variable \x1b[38;5;2m{var_name}\x1b[m found at \x1b[38;5;3m{address:?}\x1b[m");
+        }
 
         address
     });
@@ -112,45 +117,46 @@ fn main() {
     let swap_window = unsafe { sdl.swap_window().expect("SDL_GL_SwapWindow") };
     let poll_event = unsafe { sdl.poll_event().expect("SDL_PollEvent") };
 
-    let patterns = pattern::Libraries::new();
+    // refuse ambiguous patterns rather than hooking whatever matched first
+    let mut patterns = pattern::Libraries::new().strict(true);
+
+    // skip rescanning libraries that haven't changed since the last injection
+    if let Some(directory) = pattern::Cache::default_directory() {
+        patterns = patterns.cached(directory);
+    }
+
     let _animation_layers = unsafe {
-        let address = patterns
-            .address_of(
+        patterns
+            .value_of(
                 "client_client.so",
                 &pattern::ANIMATION_LAYERS,
                 "animation_layers",
             )
-            .expect("animation layers");
-
-        address.byte_add(35).cast::<u32>().read()
+            .expect("animation layers")
     };
 
     let _animation_state = unsafe {
-        let address = patterns
-            .address_of(
+        patterns
+            .value_of(
                 "client_client.so",
                 &pattern::ANIMATION_STATE,
                 "animation_state",
             )
-            .expect("animation state");
-
-        address.byte_add(52).cast::<u32>().read()
+            .expect("animation state")
     };
 
-    /* pattern is brokey
-    * let host_run_frame_input = unsafe {
-        let address = patterns
+    // pattern is brokey, the call to CL_Move within is checked before it's hooked
+    let host_run_frame_input = unsafe {
+        patterns
             .address_of(
                 "engine_client.so",
                 &pattern::HOST_RUN_FRAME_INPUT,
                 "host_run_frame_input",
             )
-            .expect("host run frame input");
-
-        address
-    };*/
+            .ok()
+    };
 
-    let _cl_move = unsafe {
+    let cl_move = unsafe {
         let cl_move = patterns
             .address_of("engine_client.so", &pattern::CL_MOVE, "cl_move")
             .expect("cl move");
@@ -162,36 +168,32 @@ fn main() {
         cl_move
     };
 
-    /*let write_user_command = unsafe {
-        let address = patterns
-            .address_of(
-                "client_client.so",
-                &pattern::WRITE_USER_COMMAND,
-                "write_user_command",
-            )
-            .expect("write user command");
-
-        let write_user_command: state::hooks::WriteUserCommand = mem::transmute(address);
-
-        state::hooks::set_write_user_command(write_user_command);
-
-        write_user_command
-    };
-
+    // the hook writes commands itself, so it's only hooked if both are found
     let write_user_command_delta_to_buffer = unsafe {
-        let address = patterns
-            .address_of(
-                "client_client.so",
-                &pattern::WRITE_USER_COMMAND_DELTA_TO_BUFFER,
-                "write_user_command_delta_to_buffer",
-            )
-            .expect("write user command delta to buffer");
-
-        let write_user_command_delta_to_buffer: state::hooks::WriteUserCommand =
-            mem::transmute(address);
-
-        write_user_command_delta_to_buffer
-    };*/
+        let write_user_command = patterns.address_of(
+            "client_client.so",
+            &pattern::WRITE_USER_COMMAND,
+            "write_user_command",
+        );
+
+        let write_user_command_delta_to_buffer = patterns.address_of(
+            "client_client.so",
+            &pattern::WRITE_USER_COMMAND_DELTA_TO_BUFFER,
+            "write_user_command_delta_to_buffer",
+        );
+
+        match (write_user_command, write_user_command_delta_to_buffer) {
+            (Ok(write_user_command), Ok(write_user_command_delta_to_buffer)) => {
+                let write_user_command: state::hooks::WriteUserCommand =
+                    mem::transmute(write_user_command);
+
+                state::hooks::set_write_user_command(write_user_command);
+
+                Some(write_user_command_delta_to_buffer)
+            }
+            _ => None,
+        }
+    };
 
     unsafe {
         let gl_context = elysium_gl::Context::new(|symbol| gl.get_proc_address(symbol).cast());
@@ -213,108 +215,69 @@ fn main() {
 
         // e8 <relative>  call  CL_Move
         // 0x005929d3 - 0x00592910 = 195
-        /*{
-            let cl_move_hook = hooks::cl_move as usize as *const u8;
-            let call_cl_move = host_run_frame_input.byte_offset(195);
-
-            // obtain rip
-            let rip = call_cl_move.byte_offset(5);
-
-            // calulate relative
-            let relative = cl_move_hook.byte_offset_from(rip);
-
-            // remove protection
-            let protection = elysium_mem::unprotect(call_cl_move);
-
-            // replace relative
-            /*let original = call_cl_move
-            .byte_offset(1)
-            .cast::<i32>()
-            .as_mut()
-            .replace(relative as i32);*/
+        if let Some(host_run_frame_input) = host_run_frame_input {
+            let call_cl_move = host_run_frame_input.byte_add(195).as_mut();
+
+            match CallSite::target_of(call_cl_move) {
+                Ok(target) if target == cl_move as *const u8 => {
+                    match CallSite::redirect(call_cl_move, hooks::cl_move as *const u8) {
+                        Ok(call_site) => {
+                            state::keep_call_site(call_site);
+                            println!("elysium | hooked \x1b[38;5;2mCL_Move\x1b[m");
+                        }
+                        Err(error) => println!("elysium | unable to hook \x1b[38;5;2mCL_Move\x1b[m: \x1b[38;5;1m{error}\x1b[m"),
+                    }
+                }
+                _ => println!("elysium | call to \x1b[38;5;2mCL_Move\x1b[m not found, skipped"),
+            }
+        }
 
-            //println!("cl_move_hook relative (original) = {original:?}");
+        let client_mode = client.client_mode();
+        let model_render = interfaces.model_render.cast::<ModelRender>();
 
-            // restore protection
-            elysium_mem::protect(call_cl_move, protection);
-        }*/
+        vmt_hook!(client_mode, ClientMode.create_move => create_move as CreateMove, set_create_move);
+        vmt_hook!(client, Client.frame_stage_notify => frame_stage_notify as FrameStageNotify, set_frame_stage_notify);
+        vmt_hook!(client_mode, ClientMode.override_view => override_view as OverrideView, set_override_view);
+        vmt_hook!(model_render, ModelRender.draw_model_execute => draw_model_execute as DrawModelExecute, set_draw_model_execute);
 
         {
-            let address = client
-                .create_move_address()
-                .as_mut()
-                .cast::<state::hooks::CreateMove>();
+            let patch = Patch::write(swap_window, hooks::swap_window as state::hooks::SwapWindow)
+                .expect("SDL_GL_SwapWindow");
 
-            // remove protection
-            let protection = elysium_mem::unprotect(address);
-
-            state::hooks::set_create_move(address.replace(hooks::create_move));
-            println!("elysium | hooked \x1b[38;5;2mCreateMove\x1b[m");
-
-            // restore protection
-            elysium_mem::protect(address, protection);
+            state::hooks::set_swap_window(patch.original_as());
+            state::keep_patch(patch);
+            println!("elysium | hooked \x1b[38;5;2mSDL_GL_SwapWindow\x1b[m");
         }
 
         {
-            let address = client
-                .frame_stage_notify_address()
-                .as_mut()
-                .cast::<state::hooks::FrameStageNotify>();
-
-            // remove protection
-            let protection = elysium_mem::unprotect(address);
+            let patch = Patch::write(poll_event, hooks::poll_event as state::hooks::PollEvent)
+                .expect("SDL_PollEvent");
 
-            state::hooks::set_frame_stage_notify(address.replace(hooks::frame_stage_notify));
-            println!("elysium | hooked \x1b[38;5;2mFrameStageNotify\x1b[m");
-
-            // restore protection
-            elysium_mem::protect(address, protection);
+            state::hooks::set_poll_event(patch.original_as());
+            state::keep_patch(patch);
+            println!("elysium | hooked \x1b[38;5;2mSDL_PollEvent\x1b[m");
         }
 
-        {
-            let address = client
-                .override_view_address()
-                .as_mut()
-                .cast::<state::hooks::OverrideView>();
-
-            // remove protection
-            let protection = elysium_mem::unprotect(address);
-
-            state::hooks::set_override_view(address.replace(hooks::override_view));
-            println!("elysium | hooked \x1b[38;5;2mOverrideView\x1b[m");
-
-            // restore protection
-            elysium_mem::protect(address, protection);
+        events::register(events);
+        command::register(console);
+        module::watch();
+
+        match write_user_command_delta_to_buffer {
+            Some(write_user_command_delta_to_buffer) => match elysium_mem::Detour::new(
+                write_user_command_delta_to_buffer,
+                hooks::write_user_command_delta_to_buffer as *const u8,
+            ) {
+                Ok(detour) => {
+                    state::hooks::set_write_user_command_delta_to_buffer(mem::transmute(
+                        detour.original(),
+                    ));
+
+                    state::keep_detour(detour);
+                    println!("elysium | hooked \x1b[38;5;2mWriteUsercmdDeltaToBuffer\x1b[m");
+                }
+                Err(error) => println!("elysium | unable to hook \x1b[38;5;2mWriteUsercmdDeltaToBuffer\x1b[m: \x1b[38;5;1m{error}\x1b[m"),
+            },
+            None => println!("elysium | \x1b[38;5;2mWriteUsercmdDeltaToBuffer\x1b[m not found, skipped"),
         }
-
-        state::hooks::set_swap_window(swap_window.replace(hooks::swap_window));
-        println!("elysium | hooked \x1b[38;5;2mSDL_GL_SwapWindow\x1b[m");
-
-        state::hooks::set_poll_event(poll_event.replace(hooks::poll_event));
-        println!("elysium | hooked \x1b[38;5;2mSDL_PollEvent\x1b[m");
-
-        /*{
-            #[repr(C, packed)]
-            struct Jmp4 {
-                jmp: u8,
-                rel: i32,
-            }
-
-            let hook = hooks::write_user_command_delta_to_buffer as *const u8;
-            let base = write_user_command_delta_to_buffer as *const u8;
-            let rip = base.byte_add(5);
-            let rel = (hook as *const u8).byte_offset_from(rip) as i32;
-            let jmp = Jmp4 { jmp: 0xE9, rel };
-
-            // remove protection
-            let protection = elysium_mem::unprotect(base);
-
-            // write jmp
-            base.as_mut().cast::<Jmp4>().write(jmp);
-            println!("elysium | hooked \x1b[38;5;2mWriteUsercmdDeltaToBuffer\x1b[m");
-
-            // restore protection
-            elysium_mem::protect(base, protection);
-        }*/
     }
 }
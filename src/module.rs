@@ -0,0 +1,302 @@
+//! Loading, and reloading, the feature module.
+//!
+//! The module is built from `crates/features` by cargo, and a copy of it is loaded, so that the
+//! dynamic linker sees every build as a new library, and cargo is free to replace the original.
+
+use elysium_dl::Library;
+use elysium_module::{Layout, Module, ABI_VERSION, SYMBOL};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+use std::{fmt, fs, io};
+
+/// Directory of the workspace elysium was built from.
+const WORKSPACE: &str = env!("CARGO_MANIFEST_DIR");
+
+/// Package name of the feature module.
+const PACKAGE: &str = "elysium-features";
+
+/// Every crate the module is built from, relative to the workspace, whose changes rebuild it.
+const SOURCES: &[&str] = &[
+    "crates/daisy_chain",
+    "crates/features",
+    "crates/math",
+    "crates/mem",
+    "crates/module",
+    "crates/sdk",
+];
+
+/// How often to check the module's sources for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+static MODULE: RwLock<Option<Loaded>> = parking_lot::const_rwlock(None);
+
+static WATCHER: Mutex<Option<JoinHandle<()>>> = parking_lot::const_mutex(None);
+
+/// Set by `request_reload`, checked by the watcher.
+static RELOAD: AtomicBool = AtomicBool::new(false);
+
+/// Why the feature module couldn't be loaded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// The module couldn't be built, copied, or opened.
+    Io(io::Error),
+    /// The module doesn't export `SYMBOL`.
+    Symbol,
+    /// The module was built against a different interface.
+    Abi { expected: u32, found: u32 },
+    /// The module was built against a different interface, without `ABI_VERSION` being bumped.
+    Layout { expected: Layout, found: Layout },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(fmt, "{error}"),
+            LoadError::Symbol => write!(fmt, "module doesn't export `{SYMBOL}`"),
+            LoadError::Abi { expected, found } => write!(
+                fmt,
+                "module was built for interface version {found}, expected {expected}"
+            ),
+            LoadError::Layout { expected, found } => write!(
+                fmt,
+                "module was built with layout {found:?}, expected {expected:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        LoadError::Io(error)
+    }
+}
+
+/// A loaded feature module, which is unloaded when dropped.
+pub struct Loaded {
+    module: *const Module,
+    _library: Library,
+}
+
+unsafe impl Send for Loaded {}
+unsafe impl Sync for Loaded {}
+
+impl Loaded {
+    /// Load a copy of the module at `path`, refusing it if it's interface doesn't match.
+    pub fn load(path: &Path) -> Result<Self, LoadError> {
+        static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+        let generation = GENERATION.fetch_add(1, Ordering::Relaxed);
+        let copy = std::env::temp_dir().join(format!(
+            "elysium-features-{}-{generation}.so",
+            std::process::id()
+        ));
+
+        fs::copy(path, &copy)?;
+
+        let library = Library::load(&copy);
+
+        // it remains mapped once loaded
+        let _ = fs::remove_file(&copy);
+
+        let library = library?;
+        let module = library
+            .symbol(SYMBOL)
+            .ok_or(LoadError::Symbol)?
+            .as_ptr()
+            .cast::<Module>();
+
+        // SAFETY: the version is first, and the same size, in every version of `Module`
+        let found = unsafe { (*module).abi_version };
+
+        if found != ABI_VERSION {
+            return Err(LoadError::Abi {
+                expected: ABI_VERSION,
+                found,
+            });
+        }
+
+        // SAFETY: the version matches, so `Module` is the same
+        let (expected, found) = (Layout::current(), unsafe { (*module).layout });
+
+        if found != expected {
+            return Err(LoadError::Layout { expected, found });
+        }
+
+        Ok(Self {
+            module,
+            _library: library,
+        })
+    }
+
+    /// The module's entry points.
+    #[inline]
+    pub fn module(&self) -> &Module {
+        unsafe { &*self.module }
+    }
+}
+
+/// The currently loaded module, if any.
+///
+/// The module can't be reloaded while the guard is held.
+#[inline]
+pub fn get() -> RwLockReadGuard<'static, Option<Loaded>> {
+    MODULE.read()
+}
+
+/// Path cargo builds the module to.
+pub fn path() -> PathBuf {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+
+    Path::new(WORKSPACE)
+        .join("target")
+        .join(profile)
+        .join(format!("lib{}.so", PACKAGE.replace('-', "_")))
+}
+
+/// Build the module with cargo.
+pub fn build() -> io::Result<()> {
+    let mut command = Command::new("cargo");
+
+    command
+        .current_dir(WORKSPACE)
+        .args(["build", "--package", PACKAGE]);
+
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    let status = command.status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("cargo exited with {status}"),
+        ))
+    }
+}
+
+/// Build and load the module, replacing the current one.
+///
+/// The current one is kept if the new one is refused.
+pub fn reload() -> Result<(), LoadError> {
+    build()?;
+    load()
+}
+
+/// Load the module as last built, without building it, replacing the current one.
+///
+/// The current one is kept if the new one is refused.
+pub fn load() -> Result<(), LoadError> {
+    let loaded = Loaded::load(&path())?;
+
+    // waits for `create_move` to leave the old module before it's dropped
+    *MODULE.write() = Some(loaded);
+
+    Ok(())
+}
+
+/// Stop the watcher, then unload the module, such as when elysium is unloaded.
+///
+/// Waits for a build in progress to finish.
+pub fn unload() {
+    if let Some(watcher) = WATCHER.lock().take() {
+        let _ = watcher.join();
+    }
+
+    *MODULE.write() = None;
+}
+
+/// Reload the module once the watcher next wakes, such as from a console command.
+#[inline]
+pub fn request_reload() {
+    RELOAD.store(true, Ordering::Release);
+}
+
+/// Most recent modification of any of the module's sources, see `SOURCES`.
+fn sources_modified() -> Option<SystemTime> {
+    SOURCES
+        .iter()
+        .filter_map(|source| last_modified(&Path::new(WORKSPACE).join(source)))
+        .max()
+}
+
+/// Most recent modification within `directory`.
+fn last_modified(directory: &Path) -> Option<SystemTime> {
+    let mut last = None;
+
+    for entry in fs::read_dir(directory).ok()?.flatten() {
+        let path = entry.path();
+        let modified = if path.is_dir() {
+            last_modified(&path)
+        } else {
+            entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        };
+
+        last = last.max(modified);
+    }
+
+    last
+}
+
+/// Build, and load, the module, then reload it whenever it's sources change, or it's requested.
+///
+/// It's always built first, as cargo knows best whether it's up to date, which is quick if it is.
+pub fn watch() {
+    let watcher = thread::spawn(|| {
+        let mut last = sources_modified();
+
+        RELOAD.store(true, Ordering::Release);
+
+        loop {
+            let modified = sources_modified();
+
+            if modified != last {
+                last = modified;
+                RELOAD.store(true, Ordering::Release);
+            }
+
+            if crate::state::unload::is_unloading() {
+                break;
+            }
+
+            if RELOAD.swap(false, Ordering::AcqRel) {
+                println!("elysium | building \x1b[38;5;2m{PACKAGE}\x1b[m");
+
+                match reload() {
+                    Ok(()) => println!("elysium | loaded \x1b[38;5;2m{PACKAGE}\x1b[m"),
+                    Err(error) => {
+                        println!("elysium | unable to load \x1b[38;5;2m{PACKAGE}\x1b[m: {error}")
+                    }
+                }
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+
+    *WATCHER.lock() = Some(watcher);
+}
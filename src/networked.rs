@@ -10,6 +10,7 @@ pub struct BaseAnimating {
 #[derive(Debug)]
 pub struct BaseEntity {
     pub render_mode: usize,
+    pub simulation_time: usize,
     pub team: usize,
 }
 
@@ -28,6 +29,7 @@ pub struct BasePlayer {
 pub struct BaseWeapon {
     pub next_attack_available_after: usize,
     pub magazine: usize,
+    pub reserve_ammo: usize,
 }
 
 #[derive(Debug)]
@@ -112,6 +114,7 @@ fn insert_entry(this: &mut Networked, class: Class, entry: Entry, offset: usize)
 
         // base_entity
         (Class::BaseEntity, Entry::RenderMode) => this.base_entity.render_mode = offset,
+        (Class::BaseEntity, Entry::SimulationTime) => this.base_entity.simulation_time = offset,
         (Class::BaseEntity, Entry::Team) => this.base_entity.team = offset,
 
         // base_player
@@ -128,6 +131,7 @@ fn insert_entry(this: &mut Networked, class: Class, entry: Entry, offset: usize)
             this.base_weapon.next_attack_available_after = offset
         }
         (Class::BaseWeapon, Entry::Magazine) => this.base_weapon.magazine = offset,
+        (Class::BaseWeapon, Entry::ReserveAmmo) => this.base_weapon.reserve_ammo = offset,
 
         // fog
         (Class::Fog, Entry::FogColorPrimary) => this.fog.color_primary = offset,
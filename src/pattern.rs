@@ -1,47 +1,24 @@
 use core::slice;
-use findshlibs::{Segment, SharedLibrary, TargetSharedLibrary};
-use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
-use providence_pattern::Pattern;
+use elysium_elf::{self as elf, Headers};
+use findshlibs::{Segment as _, SharedLibrary, SharedLibraryId, TargetSharedLibrary};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use providence_pattern::{
+    DynPattern, GenerateError, Memory, Pattern, ResolveError, Resolved, Signature,
+};
 use std::collections::HashMap;
-use std::path::Path;
+use std::fmt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::ptr;
 use std::sync::Arc;
 
-pub const ANIMATION_LAYERS: Pattern<80> =
-    Pattern::new("55 48 89 E5 41 56 41 55 41 89 F5 41 54 53 48 89 FB 8B");
+pub use cache::Cache;
+pub use elysium_signatures::{
+    Target, ANIMATION_LAYERS, ANIMATION_STATE, CL_MOVE, HOST_RUN_FRAME_INPUT, ON_POST_RESTORE_DATA,
+    RESTORE_DATA, SAVE_DATA, SIGNATURES, WRITE_USER_COMMAND, WRITE_USER_COMMAND_DELTA_TO_BUFFER,
+};
 
-pub const ANIMATION_STATE: Pattern<84> =
-    Pattern::new("55 48 89 E5 53 48 89 FB 48 83 EC 28 48 8B 05 ?? ?? ?? ?? 48 8B 00");
-
-pub const SAVE_DATA: Pattern<108> =
-    Pattern::new("55 48 89 E5 41 57 41 89 CF 41 56 41 55 41 89 D5 41 54 53 48 89 FB 48 81 EC");
-
-pub const RESTORE_DATA: Pattern<36> = Pattern::new("E9 ?? ?? ?? ?? 90 55 48 63 F6");
-
-pub const ON_POST_RESTORE_DATA: Pattern<60> =
-    Pattern::new("55 BE ?? ?? ?? ?? 48 89 E5 41 54 53 48 89 FB E8");
-
-/// xref `"CL_Move"` in `VPROF` macro
-///
-/// [engine/cl_main.cpp](https://github.com/VSES/SourceEngine2007/blob/master/se2007/engine/cl_main.cpp)
-pub const CL_MOVE: Pattern<132> = Pattern::new(
-    "55 48 89 E5 41 57 41 56 41 89 FE 41 55 41 54 53 48 81 EC 98 01 00 00 F3 0F 11 85 5C FE FF FF",
-);
-
-/// xref `CL_Move`
-///
-/// [engine/host.cpp](https://github.com/VSES/SourceEngine2007/blob/master/se2007/engine/host.cpp)
-pub const HOST_RUN_FRAME_INPUT: Pattern<164> =
-    Pattern::new("55 48 89 E5 41 57 66 41 0F 7E C7 41 56 41 55 41 89 FD 41 54 53 48 83 EC 08 48 8B 1D C8 25 94 00 44 8B 83 0C 10 00 00");
-
-/// xref `"WriteUsercmd: from=%d to=%d\"`
-///
-/// [game/shared/usercmd.cpp](https://github.com/VSES/SourceEngine2007/blob/master/se2007/game/shared/usercmd.cpp)
-pub const WRITE_USER_COMMAND: Pattern<68> =
-    Pattern::new("55 48 89 E5 41 56 41 55 4C 8D 35 B1 19 17 02");
-
-/// xref `WriteUsercmd`
-pub const WRITE_USER_COMMAND_DELTA_TO_BUFFER: Pattern<72> =
-    Pattern::new("55 48 8D 05 38 BC 68 01 41 89 F2 48 89 E5 41 57");
+pub mod cache;
 
 /// non-owning range over some memory
 #[derive(Clone, Copy, Debug)]
@@ -55,15 +32,20 @@ impl Range {
         Self { base_address, len }
     }
 
+    pub const fn base_address(&self) -> *const u8 {
+        self.base_address
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
     pub unsafe fn as_slice(&self) -> &[u8] {
         slice::from_raw_parts(self.base_address, self.len)
     }
 
     pub unsafe fn offset_of<const N: usize>(&self, pattern: &Pattern<N>) -> Option<usize> {
-        match pattern.regex().find(self.as_slice()) {
-            Some(r#match) => Some(r#match.start()),
-            None => None,
-        }
+        pattern.find(self.as_slice())
     }
 
     pub unsafe fn address_of<const N: usize>(&self, pattern: &Pattern<N>) -> Option<*const u8> {
@@ -72,70 +54,407 @@ impl Range {
             None => None,
         }
     }
+
+    /// offsets of every match, including overlapping ones
+    pub unsafe fn find_all<const N: usize>(&self, pattern: &Pattern<N>) -> Vec<usize> {
+        pattern.find_iter(self.as_slice()).collect()
+    }
+
+    /// offset of `address` within this range, if it's within it
+    pub fn offset_of_address(&self, address: *const u8) -> Option<usize> {
+        let offset = (address as usize).checked_sub(self.base_address as usize)?;
+
+        if offset < self.len {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+}
+
+/// a loaded segment, and it's permissions
+#[derive(Clone, Copy, Debug)]
+pub struct Segment {
+    pub range: Range,
+    /// `PF_*` flags
+    pub flags: u32,
+}
+
+impl Segment {
+    pub const fn is_readable(&self) -> bool {
+        self.flags & elf::PF_R != 0
+    }
+
+    pub const fn is_writable(&self) -> bool {
+        self.flags & elf::PF_W != 0
+    }
+
+    pub const fn is_executable(&self) -> bool {
+        self.flags & elf::PF_X != 0
+    }
+}
+
+/// a loaded section
+#[derive(Clone, Debug)]
+pub struct Section {
+    pub name: Box<str>,
+    pub range: Range,
+}
+
+/// a loaded library's segments, and sections if it's file could be read
+pub struct Module {
+    /// where the library's virtual address zero is, offsets are relative to this
+    base_address: *const u8,
+    /// GNU build-id, which identifies this build of the library
+    build_id: Option<Box<[u8]>>,
+    segments: Vec<Segment>,
+    sections: Vec<Section>,
+}
+
+impl Module {
+    pub fn new(
+        base_address: *const u8,
+        build_id: Option<Box<[u8]>>,
+        segments: Vec<Segment>,
+        sections: Vec<Section>,
+    ) -> Self {
+        Self {
+            base_address,
+            build_id,
+            segments,
+            sections,
+        }
+    }
+
+    pub const fn base_address(&self) -> *const u8 {
+        self.base_address
+    }
+
+    pub fn build_id(&self) -> Option<&[u8]> {
+        self.build_id.as_deref()
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn section(&self, name: &str) -> Option<Range> {
+        self.sections
+            .iter()
+            .find(|section| &*section.name == name)
+            .map(|section| section.range)
+    }
+
+    /// ranges to scan for `target`
+    pub fn ranges(&self, target: Target) -> Vec<Range> {
+        target.ranges(self.section(target.section_name()), self.executable())
+    }
+
+    /// every executable segment
+    pub fn executable(&self) -> impl Iterator<Item = Range> + '_ {
+        self.segments
+            .iter()
+            .filter(|segment| segment.is_executable())
+            .map(|segment| segment.range)
+    }
+
+    /// segment containing `address`
+    pub fn segment_of(&self, address: *const u8) -> Option<&Segment> {
+        self.segments
+            .iter()
+            .find(|segment| segment.range.offset_of_address(address).is_some())
+    }
+
+    /// offset of `address` from the base address, if it's within a segment
+    pub fn offset_of_address(&self, address: *const u8) -> Option<usize> {
+        self.segment_of(address)?;
+
+        Some(address as usize - self.base_address as usize)
+    }
+
+    /// offsets of every match within `target`, including overlapping ones
+    pub unsafe fn find_all<const N: usize>(
+        &self,
+        pattern: &Pattern<N>,
+        target: Target,
+    ) -> Vec<usize> {
+        let base_address = self.base_address as usize;
+
+        self.ranges(target)
+            .iter()
+            .flat_map(|range| {
+                let offset = range.base_address as usize - base_address;
+
+                range
+                    .find_all(pattern)
+                    .into_iter()
+                    .map(move |found| offset + found)
+            })
+            .collect()
+    }
+
+    /// whether `pattern` matches at `offset`, which is within `target`
+    pub unsafe fn matches_at<const N: usize>(
+        &self,
+        pattern: &Pattern<N>,
+        target: Target,
+        offset: usize,
+    ) -> bool {
+        let address = self.base_address.wrapping_add(offset);
+
+        self.ranges(target)
+            .iter()
+            .any(|range| match range.offset_of_address(address) {
+                Some(offset) => pattern.scanner().matches(&range.as_slice()[offset..]),
+                None => false,
+            })
+    }
+
+    /// offset of the first match within `target`
+    pub unsafe fn offset_of<const N: usize>(
+        &self,
+        pattern: &Pattern<N>,
+        target: Target,
+    ) -> Option<usize> {
+        let base_address = self.base_address as usize;
+
+        self.ranges(target).iter().find_map(|range| {
+            let offset = range.base_address as usize - base_address;
+
+            Some(offset + range.offset_of(pattern)?)
+        })
+    }
+
+    /// shortest pattern uniquely matching the code at `offset`, within every executable segment
+    pub unsafe fn generate(&self, offset: usize) -> Result<DynPattern, GenerateError> {
+        let address = self.base_address.wrapping_add(offset);
+        let segment = self
+            .segment_of(address)
+            .filter(|segment| segment.is_executable())
+            .ok_or(GenerateError::OutOfBounds)?;
+
+        let code = segment.range.as_slice();
+        let executable: Vec<Range> = self.executable().collect();
+        let haystacks: Vec<&[u8]> = executable.iter().map(|range| range.as_slice()).collect();
+        let offset = address as usize - segment.range.base_address as usize;
+
+        providence_pattern::generate(code, offset, &haystacks)
+    }
 }
 
 pub struct Ranges {
-    ranges: HashMap<Box<str>, Range>,
+    modules: HashMap<Box<str>, Module>,
 }
 
 impl Ranges {
     pub fn new() -> Self {
         Self {
-            ranges: HashMap::new(),
+            modules: HashMap::new(),
         }
     }
 
-    pub fn insert(&mut self, library_name: &str, base_address: *const u8, len: usize) {
-        let range = Range::new(base_address, len);
+    pub fn insert(&mut self, library_name: &str, module: Module) {
+        self.modules.insert(library_name.into(), module);
+    }
 
-        self.ranges.insert(library_name.into(), range);
+    pub fn get(&self, library_name: &str) -> Option<&Module> {
+        self.modules.get(library_name)
     }
 
-    pub fn get(&self, library_name: &str) -> Option<Range> {
-        match self.ranges.get(library_name) {
-            Some(range) => Some(*range),
-            None => None,
-        }
+    /// library containing `address`, and the offset of `address` within it
+    pub fn find_address(&self, address: *const u8) -> Option<(&str, usize)> {
+        self.modules.iter().find_map(|(library_name, module)| {
+            let offset = module.offset_of_address(address)?;
+
+            Some((library_name.as_ref(), offset))
+        })
     }
 
     pub unsafe fn offset_of<const N: usize>(
         &self,
         library_name: &str,
         pattern: &Pattern<N>,
+        target: Target,
     ) -> Option<usize> {
-        match self.get(library_name) {
-            Some(range) => range.offset_of(pattern),
-            None => None,
-        }
+        self.get(library_name)?.offset_of(pattern, target)
     }
 
     pub unsafe fn address_of<const N: usize>(
         &self,
         library_name: &str,
         pattern: &Pattern<N>,
+        target: Target,
     ) -> Option<*const u8> {
+        let module = self.get(library_name)?;
+        let offset = module.offset_of(pattern, target)?;
+
+        Some(module.base_address.add(offset))
+    }
+
+    pub unsafe fn find_all<const N: usize>(
+        &self,
+        library_name: &str,
+        pattern: &Pattern<N>,
+        target: Target,
+    ) -> Option<Vec<usize>> {
         match self.get(library_name) {
-            Some(range) => range.address_of(pattern),
+            Some(module) => Some(module.find_all(pattern, target)),
             None => None,
         }
     }
 }
 
+/// segments and sections of `library`, it's headers are read for segment flags and sections
+fn module_of(library: &TargetSharedLibrary<'_>, library_name: &str) -> Module {
+    let bias = library.virtual_memory_bias().0;
+
+    // only the headers, the rest of the file is already mapped
+    let headers = match File::open(library.name()).and_then(|mut file| Headers::read(&mut file)) {
+        Ok(headers) => Some(headers),
+        Err(error) => {
+            println!("elysium | unable to read \x1b[38;5;1m{library_name}\x1b[m, it's sections are unknown: \x1b[38;5;1m{error}\x1b[m");
+
+            None
+        }
+    };
+
+    let segments = library
+        .segments()
+        .filter(|segment| segment.is_load())
+        .map(|segment| {
+            let address = segment.stated_virtual_memory_address().0;
+
+            // findshlibs only tells us whether a segment is executable
+            let flags = headers
+                .as_ref()
+                .and_then(|headers| {
+                    headers
+                        .program_headers()
+                        .find(|header| header.is_load() && header.address == address as u64)
+                })
+                .map(|header| header.flags)
+                .unwrap_or(if segment.is_code() {
+                    elf::PF_R | elf::PF_X
+                } else {
+                    elf::PF_R
+                });
+
+            let range = Range::new((bias + address) as *const u8, segment.len());
+
+            Segment { range, flags }
+        })
+        .collect();
+
+    let sections = match &headers {
+        Some(headers) => headers
+            .sections()
+            .filter(|section| section.header.is_alloc() && section.header.size != 0)
+            .map(|section| {
+                let address = bias + section.header.address as usize;
+                let range = Range::new(address as *const u8, section.header.size as usize);
+
+                Section {
+                    name: section.name.into(),
+                    range,
+                }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let build_id = match library.id() {
+        Some(SharedLibraryId::GnuBuildId(build_id)) => Some(build_id.into_boxed_slice()),
+        _ => None,
+    };
+
+    Module::new(bias as *const u8, build_id, segments, sections)
+}
+
+/// why a pattern couldn't be resolved to an address
+#[derive(Debug)]
+pub enum ScanError {
+    /// the library isn't loaded, or doesn't belong to csgo
+    UnknownLibrary,
+    /// the pattern has no matches, it's likely stale
+    NotFound,
+    /// the pattern has more than one match (strict mode only)
+    Ambiguous { offsets: Vec<usize> },
+    /// a step of the signature failed
+    Resolve(ResolveError),
+    /// the signature resolved to a value, rather than an address
+    ExpectedAddress(u32),
+    /// the signature resolved to an address, rather than a value
+    ExpectedValue(usize),
+    /// a pattern couldn't be generated
+    Generate(GenerateError),
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::UnknownLibrary => fmt.write_str("unknown library"),
+            ScanError::NotFound => fmt.write_str("pattern not found"),
+            ScanError::Ambiguous { offsets } => {
+                write!(fmt, "pattern is ambiguous, {} matches", offsets.len())
+            }
+            ScanError::Resolve(error) => write!(fmt, "unable to resolve: {error}"),
+            ScanError::ExpectedAddress(value) => {
+                write!(fmt, "expected an address, resolved to the value {value:#x}")
+            }
+            ScanError::ExpectedValue(address) => {
+                write!(
+                    fmt,
+                    "expected a value, resolved to the address {address:#x}"
+                )
+            }
+            ScanError::Generate(error) => write!(fmt, "unable to generate a pattern: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// the current process, only mapped memory is read, so a bad step is an error, not a crash
+struct Process;
+
+impl Memory for Process {
+    fn read(&self, address: usize, bytes: &mut [u8]) -> bool {
+        let address = address as *const u8;
+
+        if !elysium_mem::maps::is_readable(address, bytes.len()) {
+            return false;
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(address, bytes.as_mut_ptr(), bytes.len());
+        }
+
+        true
+    }
+}
+
 #[derive(Clone)]
-pub struct Libraries(pub Arc<RwLock<Ranges>>);
+pub struct Libraries {
+    ranges: Arc<RwLock<Ranges>>,
+    cache: Option<Arc<Mutex<Cache>>>,
+    strict: bool,
+}
 
 impl Libraries {
     pub fn new() -> Self {
-        let this = Self(Arc::new(RwLock::new(Ranges::new())));
+        let this = Self {
+            ranges: Arc::new(RwLock::new(Ranges::new())),
+            cache: None,
+            strict: false,
+        };
+
         let this2 = this.clone();
 
         TargetSharedLibrary::each(move |library| {
-            // skip libraries without a program header
-            let program_header = match library.segments().next() {
-                Some(program_header) => program_header,
-                None => return,
-            };
-
             let library_name = library.name().to_string_lossy();
 
             // skip libraries that dont belong to csgo
@@ -152,48 +471,265 @@ impl Libraries {
             };
 
             let library_name = library_name.to_string_lossy().into_owned().into_boxed_str();
+            let module = module_of(library, &library_name);
 
-            // library's base address and length
-            let base_address =
-                library.virtual_memory_bias().0 + program_header.stated_virtual_memory_address().0;
-            let len = program_header.len();
+            // skip libraries without a loaded segment
+            if module.segments().is_empty() {
+                return;
+            }
 
-            this2.insert(&library_name, base_address as *const u8, len);
+            this2.insert(&library_name, module);
         });
 
         this
     }
 
+    /// error on patterns with more than one match, instead of using the first
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// remember where patterns matched within `directory`, keyed by each library's build-id
+    pub fn cached(mut self, directory: PathBuf) -> Self {
+        self.cache = Some(Arc::new(Mutex::new(Cache::new(directory))));
+        self
+    }
+
     fn read(&self) -> RwLockReadGuard<'_, Ranges> {
-        self.0.read()
+        self.ranges.read()
     }
 
     fn write(&self) -> RwLockWriteGuard<'_, Ranges> {
-        self.0.write()
+        self.ranges.write()
     }
 
-    fn insert(&self, library_name: &str, base_address: *const u8, len: usize) {
-        self.write().insert(library_name, base_address, len);
+    fn insert(&self, library_name: &str, module: Module) {
+        self.write().insert(library_name, module);
     }
 
+    /// offset of the first match within `.text`
     pub unsafe fn offset_of<const N: usize>(
         &self,
         library_name: &str,
         pattern: &Pattern<N>,
     ) -> Option<usize> {
-        self.read().offset_of(library_name, pattern)
+        self.read().offset_of(library_name, pattern, Target::Text)
     }
 
-    pub unsafe fn address_of<const N: usize>(
+    /// offsets of every match within `.text`
+    pub unsafe fn find_all<const N: usize>(
+        &self,
+        library_name: &str,
+        pattern: &Pattern<N>,
+    ) -> Option<Vec<usize>> {
+        self.find_all_in(library_name, pattern, Target::Text)
+    }
+
+    /// offsets of every match within `target`
+    pub unsafe fn find_all_in<const N: usize>(
+        &self,
+        library_name: &str,
+        pattern: &Pattern<N>,
+        target: Target,
+    ) -> Option<Vec<usize>> {
+        self.read().find_all(library_name, pattern, target)
+    }
+
+    /// address of the first match of `pattern` within `.text`, logging how many matches there
+    /// were
+    pub unsafe fn match_of<const N: usize>(
         &self,
         library_name: &str,
         pattern: &Pattern<N>,
         name: &str,
-    ) -> Option<*const u8> {
-        self.read().address_of(library_name, pattern).map(|address| {
-            println!("elysium | found pattern \x1b[38;5;2m{pattern:?}\x1b[m (\x1b[38;5;2m{name}\x1b[m) within \x1b[38;5;2m{library_name}\x1b[m at \x1b[38;5;3m{address:?}\x1b[m");
+    ) -> Result<*const u8, ScanError> {
+        self.match_in(library_name, pattern, Target::Text, name)
+    }
 
-            address
-        })
+    /// address of the first match of `pattern` within `target`, logging how many matches there
+    /// were
+    pub unsafe fn match_in<const N: usize>(
+        &self,
+        library_name: &str,
+        pattern: &Pattern<N>,
+        target: Target,
+        name: &str,
+    ) -> Result<*const u8, ScanError> {
+        let ranges = self.read();
+        let module = match ranges.get(library_name) {
+            Some(module) => module,
+            None => {
+                println!("elysium | unable to search for pattern \x1b[38;5;2m{pattern:?}\x1b[m (\x1b[38;5;2m{name}\x1b[m), \x1b[38;5;1m{library_name}\x1b[m is not loaded");
+
+                return Err(ScanError::UnknownLibrary);
+            }
+        };
+
+        if let Some(offset) = self.cached_offset(library_name, module, pattern, target) {
+            let address = module.base_address().add(offset);
+
+            println!("elysium | found pattern \x1b[38;5;2m{pattern:?}\x1b[m (\x1b[38;5;2m{name}\x1b[m) within \x1b[38;5;2m{library_name}\x1b[m at \x1b[38;5;3m{address:?}\x1b[m (cached)");
+
+            return Ok(address);
+        }
+
+        let offsets = module.find_all(pattern, target);
+        let matches = offsets.len();
+
+        let offset = match offsets.first() {
+            Some(offset) => *offset,
+            None => {
+                println!("elysium | pattern \x1b[38;5;2m{pattern:?}\x1b[m (\x1b[38;5;2m{name}\x1b[m) has \x1b[38;5;1mno matches\x1b[m within \x1b[38;5;2m{library_name}\x1b[m (\x1b[38;5;2m{target}\x1b[m)");
+
+                return Err(ScanError::NotFound);
+            }
+        };
+
+        let address = module.base_address().add(offset);
+
+        if matches > 1 {
+            println!("elysium | pattern \x1b[38;5;2m{pattern:?}\x1b[m (\x1b[38;5;2m{name}\x1b[m) is \x1b[38;5;1mambiguous\x1b[m, \x1b[38;5;1m{matches}\x1b[m matches within \x1b[38;5;2m{library_name}\x1b[m at offsets \x1b[38;5;3m{offsets:x?}\x1b[m");
+
+            if self.strict {
+                return Err(ScanError::Ambiguous { offsets });
+            }
+        }
+
+        println!("elysium | found pattern \x1b[38;5;2m{pattern:?}\x1b[m (\x1b[38;5;2m{name}\x1b[m) within \x1b[38;5;2m{library_name}\x1b[m at \x1b[38;5;3m{address:?}\x1b[m (\x1b[38;5;3m{matches}\x1b[m matches)");
+
+        // only unique matches are cached, so a cache hit never hides ambiguity
+        if matches == 1 {
+            self.cache_offset(library_name, module, pattern, offset);
+        }
+
+        Ok(address)
+    }
+
+    /// offset of `pattern` from the cache, if the bytes there still match
+    unsafe fn cached_offset<const N: usize>(
+        &self,
+        library_name: &str,
+        module: &Module,
+        pattern: &Pattern<N>,
+        target: Target,
+    ) -> Option<usize> {
+        let build_id = module.build_id()?;
+        let mut cache = self.cache.as_ref()?.lock();
+        let offset = cache.get(library_name, build_id, pattern.source())?;
+
+        if module.matches_at(pattern, target, offset) {
+            return Some(offset);
+        }
+
+        println!("elysium | cached offset \x1b[38;5;3m{offset:#x}\x1b[m of pattern \x1b[38;5;2m{pattern:?}\x1b[m is \x1b[38;5;1mstale\x1b[m");
+
+        let _ = cache.remove(library_name, build_id, pattern.source());
+
+        None
+    }
+
+    fn cache_offset<const N: usize>(
+        &self,
+        library_name: &str,
+        module: &Module,
+        pattern: &Pattern<N>,
+        offset: usize,
+    ) {
+        let (cache, build_id) = match (&self.cache, module.build_id()) {
+            (Some(cache), Some(build_id)) => (cache, build_id),
+            _ => return,
+        };
+
+        if let Err(error) = cache
+            .lock()
+            .insert(library_name, build_id, pattern.source(), offset)
+        {
+            println!("elysium | unable to cache pattern \x1b[38;5;2m{pattern:?}\x1b[m: \x1b[38;5;1m{error}\x1b[m");
+        }
+    }
+
+    /// match `signature` and apply it's steps
+    pub unsafe fn resolve<const N: usize>(
+        &self,
+        library_name: &str,
+        signature: &Signature<N>,
+        name: &str,
+    ) -> Result<Resolved, ScanError> {
+        let address = self.match_of(library_name, signature.pattern(), name)?;
+
+        if signature.steps().is_empty() {
+            return Ok(Resolved::Address(address as usize));
+        }
+
+        match signature.resolve(&Process, address as usize) {
+            Ok(resolved) => {
+                println!("elysium | resolved \x1b[38;5;2m{name}\x1b[m to \x1b[38;5;3m{resolved:x?}\x1b[m");
+
+                Ok(resolved)
+            }
+            Err(error) => {
+                println!("elysium | unable to resolve \x1b[38;5;2m{name}\x1b[m: \x1b[38;5;1m{error}\x1b[m");
+
+                Err(ScanError::Resolve(error))
+            }
+        }
+    }
+
+    /// the address `signature` resolves to
+    pub unsafe fn address_of<const N: usize>(
+        &self,
+        library_name: &str,
+        signature: &Signature<N>,
+        name: &str,
+    ) -> Result<*const u8, ScanError> {
+        match self.resolve(library_name, signature, name)? {
+            Resolved::Address(address) => Ok(address as *const u8),
+            Resolved::Value(value) => Err(ScanError::ExpectedAddress(value)),
+        }
+    }
+
+    /// the value `signature` resolves to
+    pub unsafe fn value_of<const N: usize>(
+        &self,
+        library_name: &str,
+        signature: &Signature<N>,
+        name: &str,
+    ) -> Result<u32, ScanError> {
+        match self.resolve(library_name, signature, name)? {
+            Resolved::Address(address) => Err(ScanError::ExpectedValue(address)),
+            Resolved::Value(value) => Ok(value),
+        }
+    }
+
+    /// shortest unique pattern for the code at `offset` within `library_name`
+    pub unsafe fn generate(
+        &self,
+        library_name: &str,
+        offset: usize,
+    ) -> Result<DynPattern, ScanError> {
+        let pattern = match self.read().get(library_name) {
+            Some(module) => module.generate(offset).map_err(ScanError::Generate)?,
+            None => return Err(ScanError::UnknownLibrary),
+        };
+
+        println!("elysium | generated pattern \x1b[38;5;2m{pattern:?}\x1b[m for \x1b[38;5;2m{library_name}\x1b[m+\x1b[38;5;3m{offset:#x}\x1b[m");
+
+        Ok(pattern)
+    }
+
+    /// shortest unique pattern for the code at `address`, and the library it's within
+    pub unsafe fn generate_at(
+        &self,
+        address: *const u8,
+    ) -> Result<(Box<str>, usize, DynPattern), ScanError> {
+        let (library_name, offset) = match self.read().find_address(address) {
+            Some((library_name, offset)) => (Box::<str>::from(library_name), offset),
+            None => return Err(ScanError::UnknownLibrary),
+        };
+
+        let pattern = self.generate(&library_name, offset)?;
+
+        Ok((library_name, offset, pattern))
     }
 }
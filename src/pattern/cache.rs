@@ -0,0 +1,209 @@
+//! offsets of patterns that matched previously, so libraries aren't rescanned every injection
+//!
+//! each library has a file within the cache directory:
+//!
+//! ```text
+//! build-id 5dac61ec5a8eb1396f9fbd350e3169a558528a40
+//! 1a2b30 55 48 89 E5 41 57 41 56
+//! ```
+//!
+//! a different build-id means the library changed, which discards every offset for it.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+/// offsets within a single build of a library, keyed by pattern source
+#[derive(Debug, Default, PartialEq)]
+struct Entries {
+    build_id: Box<[u8]>,
+    offsets: HashMap<Box<str>, usize>,
+}
+
+impl Entries {
+    fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        let build_id = lines.next()?.strip_prefix("build-id ")?;
+        let build_id = parse_hex(build_id)?;
+        let mut offsets = HashMap::new();
+
+        for line in lines {
+            let (offset, source) = line.split_once(' ')?;
+            let offset = usize::from_str_radix(offset, 16).ok()?;
+
+            offsets.insert(source.into(), offset);
+        }
+
+        Some(Self { build_id, offsets })
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("build-id {}\n", to_hex(&self.build_id));
+
+        for (source, offset) in &self.offsets {
+            let _ = writeln!(text, "{offset:x} {source}");
+        }
+
+        text
+    }
+}
+
+fn parse_hex(text: &str) -> Option<Box<[u8]>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub struct Cache {
+    directory: PathBuf,
+    libraries: HashMap<Box<str>, Entries>,
+}
+
+impl Cache {
+    pub fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            libraries: HashMap::new(),
+        }
+    }
+
+    /// `$XDG_CACHE_HOME/elysium`, or `~/.cache/elysium`
+    pub fn default_directory() -> Option<PathBuf> {
+        if let Some(directory) = env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+            return Some(PathBuf::from(directory).join("elysium"));
+        }
+
+        let home = env::var_os("HOME").filter(|home| !home.is_empty())?;
+
+        Some(PathBuf::from(home).join(".cache").join("elysium"))
+    }
+
+    fn path(&self, library_name: &str) -> PathBuf {
+        self.directory.join(library_name)
+    }
+
+    /// entries for `library_name`, loaded from disk the first time, and discarded if they're for
+    /// a different build
+    fn entries(&mut self, library_name: &str, build_id: &[u8]) -> &mut Entries {
+        if !self.libraries.contains_key(library_name) {
+            let entries = fs::read_to_string(self.path(library_name))
+                .ok()
+                .and_then(|text| Entries::parse(&text))
+                .unwrap_or_default();
+
+            self.libraries.insert(library_name.into(), entries);
+        }
+
+        let entries = self.libraries.get_mut(library_name).unwrap();
+
+        if &*entries.build_id != build_id {
+            *entries = Entries {
+                build_id: build_id.into(),
+                offsets: HashMap::new(),
+            };
+        }
+
+        entries
+    }
+
+    /// cached offset of `source` within `library_name`, it must be verified before use
+    pub fn get(&mut self, library_name: &str, build_id: &[u8], source: &str) -> Option<usize> {
+        self.entries(library_name, build_id)
+            .offsets
+            .get(source)
+            .copied()
+    }
+
+    /// cache the offset of `source` within `library_name`, and save the library's entries
+    pub fn insert(
+        &mut self,
+        library_name: &str,
+        build_id: &[u8],
+        source: &str,
+        offset: usize,
+    ) -> io::Result<()> {
+        self.entries(library_name, build_id)
+            .offsets
+            .insert(source.into(), offset);
+
+        self.save(library_name)
+    }
+
+    /// forget the offset of `source`, such as when it no longer matches
+    pub fn remove(&mut self, library_name: &str, build_id: &[u8], source: &str) -> io::Result<()> {
+        if self
+            .entries(library_name, build_id)
+            .offsets
+            .remove(source)
+            .is_some()
+        {
+            self.save(library_name)?;
+        }
+
+        Ok(())
+    }
+
+    fn save(&self, library_name: &str) -> io::Result<()> {
+        let entries = match self.libraries.get(library_name) {
+            Some(entries) => entries,
+            None => return Ok(()),
+        };
+
+        fs::create_dir_all(&self.directory)?;
+
+        // write then rename, so a crash mid-write doesn't leave a truncated file
+        let path = self.path(library_name);
+        let temporary = path.with_extension("tmp");
+
+        fs::write(&temporary, entries.to_text())?;
+        fs::rename(temporary, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries() {
+        let text = "build-id 0102ff\n1a2b30 55 48 89 E5\n";
+        let entries = Entries::parse(text).unwrap();
+
+        assert_eq!(&*entries.build_id, [0x01, 0x02, 0xFF]);
+        assert_eq!(entries.offsets.get("55 48 89 E5"), Some(&0x1a2b30));
+        assert_eq!(Entries::parse(&entries.to_text()), Some(entries));
+        assert_eq!(Entries::parse("1a2b30 55\n"), None);
+    }
+
+    #[test]
+    fn invalidate() {
+        let directory = env::temp_dir().join(format!("elysium-cache-{}", std::process::id()));
+        let mut cache = Cache::new(directory.clone());
+
+        cache
+            .insert("client_client.so", &[1, 2], "55 48", 0x10)
+            .unwrap();
+
+        // reloaded from disk
+        let mut cache = Cache::new(directory.clone());
+
+        assert_eq!(cache.get("client_client.so", &[1, 2], "55 48"), Some(0x10));
+        assert_eq!(cache.get("client_client.so", &[1, 2], "55 49"), None);
+
+        // the library was updated
+        assert_eq!(cache.get("client_client.so", &[3, 4], "55 48"), None);
+        assert_eq!(cache.get("client_client.so", &[1, 2], "55 48"), None);
+
+        let _ = fs::remove_dir_all(directory);
+    }
+}
@@ -0,0 +1,71 @@
+//! Unloading elysium from the game.
+
+use crate::{module, state};
+use core::mem::MaybeUninit;
+use core::ptr;
+use elysium_dl::Library;
+
+/// Start unloading on a new thread, as hooks can't wait for themselves to return.
+///
+/// The menu must already have been freed, and every hook restored, see `state::unload`. The
+/// thread is started with `pthread_create`, so `Library::close_detached` can join it.
+pub fn spawn() {
+    extern "C" fn start(_arg: *mut libc::c_void) -> *mut libc::c_void {
+        // nothing will join this thread
+        if !unsafe { unload() } {
+            unsafe {
+                libc::pthread_detach(libc::pthread_self());
+            }
+        }
+
+        ptr::null_mut()
+    }
+
+    let mut thread = MaybeUninit::uninit();
+
+    let error =
+        unsafe { libc::pthread_create(thread.as_mut_ptr(), ptr::null(), start, ptr::null_mut()) };
+
+    if error != 0 {
+        println!("elysium | unable to start unloading, it is safe to `dlclose` now");
+    }
+}
+
+/// Returns whether the library will be closed, once this thread exits.
+unsafe fn unload() -> bool {
+    println!("elysium | unloading...");
+
+    state::unload::wait();
+
+    println!("elysium | no hook is counted as executing, and both grace periods have passed");
+
+    module::unload();
+
+    // the dynamic linker calls `_dl_debug_state` while unloading us
+    if let Err(error) = elysium_dl::uninstall() {
+        println!("elysium | unable to stop library notifications: {error}, staying loaded");
+
+        return false;
+    }
+
+    let library = match Library::containing(spawn as *const u8) {
+        Some(library) => library,
+        None => {
+            println!("elysium | unable to find elysium's library, it is safe to `dlclose` now");
+
+            return false;
+        }
+    };
+
+    println!("elysium | unloaded, goodbye");
+
+    // drop the reference the injector loaded us with, along with our own
+    match library.close_detached(1) {
+        Ok(()) => true,
+        Err(error) => {
+            println!("elysium | unable to `dlclose`: {error}, it is safe to do so manually");
+
+            false
+        }
+    }
+}